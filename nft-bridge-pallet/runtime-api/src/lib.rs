@@ -0,0 +1,100 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime API for querying the NFT bridge pallet
+
+use codec::Codec;
+pub use pallet_nft_bridge::{
+	BridgeStateEntry, BridgeStats, BridgeStatus, FeeBreakdown, InboundFailure, NftOrigin, NftRecord,
+	SendQuote, TransferRecord,
+};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+use xcm::VersionedXcm;
+
+sp_api::decl_runtime_apis! {
+	/// Read-only queries wallets and frontends make against the NFT bridge
+	pub trait NftBridgeApi<AccountId, CollectionId, ItemId, BlockNumber, Balance>
+	where
+		AccountId: Codec,
+		CollectionId: Codec,
+		ItemId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// Dry-run a send: run every check `send_nft` would and quote its cost
+		fn validate_send(
+			sender: AccountId,
+			collection_id: CollectionId,
+			item_id: ItemId,
+			dest_para_id: u32,
+			metadata_len: u32,
+			uri_len: u32,
+		) -> Result<SendQuote<Balance>, DispatchError>;
+
+		/// The exact XCM a send would emit, for inspecting interoperability problems
+		fn preview_send_xcm(
+			sender: AccountId,
+			collection_id: CollectionId,
+			item_id: ItemId,
+			dest_para_id: u32,
+			metadata: Vec<u8>,
+			metadata_uri: Option<Vec<u8>>,
+		) -> Result<VersionedXcm<()>, DispatchError>;
+
+		/// Best-effort fee breakdown for sending an NFT on behalf of its current owner
+		fn estimate_send_fee(
+			collection_id: CollectionId,
+			item_id: ItemId,
+			dest_para_id: u32,
+			metadata_len: u32,
+		) -> Result<FeeBreakdown<Balance>, DispatchError>;
+
+		/// Up to `limit` items currently in flight to `dest_para_id`
+		fn pending_to(dest_para_id: u32, limit: u32) -> Vec<(CollectionId, ItemId)>;
+
+		/// Up to `limit` items held in `dest_para_id`'s escrow account
+		fn escrowed_for(dest_para_id: u32, limit: u32) -> Vec<(CollectionId, ItemId)>;
+
+		/// Up to `limit` items in escrow for an outbound transfer and their destination, resuming
+		/// after `start_after`
+		fn escrowed_items(
+			start_after: Option<(CollectionId, ItemId, u32)>,
+			limit: u32,
+		) -> Vec<(CollectionId, ItemId, u32)>;
+
+		/// Resolve the hash of an outbound XCM message to the transfer it carried
+		fn transfer_by_message_hash(
+			message_hash: [u8; 32],
+		) -> Option<TransferRecord<CollectionId, ItemId, AccountId, BlockNumber>>;
+
+		/// Storage keys of the `NFTOwners` and `PendingTransfers` entries of an NFT, for
+		/// fetching state proofs
+		fn bridge_storage_keys(collection_id: CollectionId, item_id: ItemId) -> Vec<Vec<u8>>;
+
+		/// The pending transfer of an NFT and the block it entered the bridge in
+		fn transfer_status(
+			collection_id: CollectionId,
+			item_id: ItemId,
+		) -> Option<BridgeStatus<AccountId, BlockNumber>>;
+
+		/// Owner, metadata, origin and bridge status of an NFT. Prefer this over combining the
+		/// individual queries.
+		fn nft_record(
+			collection_id: CollectionId,
+			item_id: ItemId,
+		) -> Option<NftRecord<AccountId, BlockNumber>>;
+
+		/// Bridge health figures for operator dashboards in a single call
+		fn bridge_stats() -> BridgeStats<BlockNumber>;
+
+		/// The latest failed inbound deliveries from `para_id`, oldest first
+		fn inbound_failures(para_id: u32) -> Vec<InboundFailure<CollectionId, ItemId, BlockNumber>>;
+
+		/// A page of the bridge's owner, pending transfer and metadata records after `cursor`,
+		/// in storage key order, and the cursor of the next page if there is one
+		fn export_state(
+			cursor: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<BridgeStateEntry<CollectionId, ItemId, AccountId, BlockNumber>>, Option<Vec<u8>>);
+	}
+}
@@ -0,0 +1,272 @@
+//! Matching inbound XCM assets against this chain's NFTs
+//!
+//! `NftAssetConverter` represents an item as a concrete asset at
+//! `prefix / GeneralIndex(collection)` with an `AssetInstance::Index` instance, both folded from
+//! the SCALE encoding of the ids. `item_of_asset` reverses that and rejects every other shape, so
+//! an asset that isn't one of ours is never credited as one. `NftTransactor` builds a
+//! `TransactAsset` on top of it for the runtime's XCM executor.
+//!
+//! A deposit whose beneficiary `AccountIdConverter` can't resolve would strand the item
+//! mid-bridge, so it goes to the sovereign account of the sibling sending it instead and is
+//! recorded in `OrphanedInbound` until governance reassigns it, or refuses it with
+//! `reject_inbound`, which bounces it back to that sibling.
+
+use crate::*;
+use codec::{Decode, DecodeAll, Encode};
+use frame_support::traits::tokens::nonfungibles::{Inspect, Transfer};
+use sp_std::{borrow::Borrow, marker::PhantomData};
+use xcm::v3::prelude::*;
+use xcm_executor::{
+	traits::{Convert as XcmConvert, Error as MatchError, TransactAsset},
+	Assets,
+};
+
+/// Fold the first `max_len` bytes of `value`'s encoding into a `u128`
+pub(crate) fn fold_encoded<V: Encode>(value: &V, max_len: usize) -> u128 {
+	value.using_encoded(|b| b.iter().take(max_len).fold(0u128, |acc, &x| (acc << 8) | x as u128))
+}
+
+/// Reverse `fold_encoded`: the value decoded from the shortest tail of `folded`'s big-endian
+/// bytes that folds back to `folded`
+pub(crate) fn unfold_encoded<V: Decode + Encode>(folded: u128, max_len: usize) -> Option<V> {
+	let bytes = folded.to_be_bytes();
+	(1..=max_len).find_map(|len| {
+		let value = V::decode_all(&mut &bytes[bytes.len() - len..]).ok()?;
+		(fold_encoded(&value, max_len) == folded).then_some(value)
+	})
+}
+
+/// The canonical wire format of this chain's NFTs, shared by outbound transfers, the matcher and
+/// `NftTransactor`. Partner runtimes converting our assets must use it with the same
+/// `NftAssetPrefix`, or the two sides disagree on which asset is which item.
+///
+/// Item `(collection, item)` is the asset at `prefix / GeneralIndex(c)` with instance
+/// `Index(i)`, where `c` and `i` are the first 8 and 16 bytes of the ids' SCALE encodings read
+/// as big-endian integers.
+pub struct NftAssetConverter<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T, I> sp_runtime::traits::Convert<(T::CollectionId, T::ItemId), Option<MultiAsset>>
+	for NftAssetConverter<T, I>
+where
+	T: Config<I>,
+	I: 'static,
+{
+	/// `None` only if the prefix can't be resolved or has no room for the collection junction
+	fn convert((collection_id, item_id): (T::CollectionId, T::ItemId)) -> Option<MultiAsset> {
+		let interior = Pallet::<T, I>::nft_asset_prefix()
+			.ok()?
+			.pushed_with(GeneralIndex(fold_encoded(&collection_id, 8)))
+			.ok()?;
+		Some(MultiAsset {
+			id: AssetId::Concrete(MultiLocation { parents: 0, interior }),
+			fun: Fungibility::NonFungible(AssetInstance::Index(fold_encoded(&item_id, 16))),
+		})
+	}
+}
+
+impl<T: Config<I>, I: 'static> XcmConvert<MultiAsset, (T::CollectionId, T::ItemId)>
+	for NftAssetConverter<T, I>
+{
+	fn convert_ref(asset: impl Borrow<MultiAsset>) -> Result<(T::CollectionId, T::ItemId), ()> {
+		Pallet::<T, I>::item_of_asset(asset.borrow()).map_err(|_| ())
+	}
+
+	fn reverse_ref(item: impl Borrow<(T::CollectionId, T::ItemId)>) -> Result<MultiAsset, ()> {
+		<Self as sp_runtime::traits::Convert<_, Option<MultiAsset>>>::convert(*item.borrow())
+			.ok_or(())
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The item an XCM asset stands for, if it is one of this chain's NFTs
+	pub fn item_of_asset(asset: &MultiAsset) -> Result<(T::CollectionId, T::ItemId), MatchError> {
+		let AssetId::Concrete(location) = asset.id else {
+			return Err(MatchError::AssetNotHandled)
+		};
+		let index = match &asset.fun {
+			Fungibility::NonFungible(AssetInstance::Index(index)) => *index,
+			Fungibility::NonFungible(_) => return Err(MatchError::InstanceConversionFailed),
+			Fungibility::Fungible(_) => return Err(MatchError::AssetNotHandled),
+		};
+
+		let prefix = Self::nft_asset_prefix().map_err(|_| MatchError::AssetNotHandled)?;
+		let (head, Some(GeneralIndex(collection))) = location.interior.split_last() else {
+			return Err(MatchError::AssetNotHandled)
+		};
+		if location.parents != 0 || head != prefix {
+			return Err(MatchError::AssetNotHandled)
+		}
+
+		let collection_id =
+			unfold_encoded(collection, 8).ok_or(MatchError::AssetIdConversionFailed)?;
+		let item_id = unfold_encoded(index, 16).ok_or(MatchError::InstanceConversionFailed)?;
+		Ok((collection_id, item_id))
+	}
+
+	/// Record an inbound NFT parked with its source chain's sovereign account
+	pub(crate) fn note_orphaned(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		orphan: OrphanedNft<T::AccountId>,
+	) {
+		Self::deposit_event(Event::InboundOrphaned {
+			collection_id,
+			item_id,
+			from_para_id: orphan.from_para_id,
+			beneficiary: orphan.beneficiary,
+		});
+		OrphanedInbound::<T, I>::insert(collection_id, item_id, orphan);
+	}
+
+	/// Move an orphaned NFT from the sovereign account holding it to `to`
+	pub(crate) fn do_reassign_orphaned(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		to: T::AccountId,
+	) -> DispatchResult {
+		let orphan = OrphanedInbound::<T, I>::get(collection_id, item_id)
+			.ok_or(Error::<T, I>::NotOrphaned)?;
+		Self::ensure_not_bridge_locked(collection_id, item_id)?;
+		ensure!(
+			NFTOwners::<T, I>::get(collection_id, item_id) == Some(orphan.holder),
+			Error::<T, I>::OrphanNoLongerHeld
+		);
+		// A wrapper parked for lack of supply only joins it now
+		let wrapper = ItemOrigin::<T, I>::contains_key(collection_id, item_id);
+		ensure!(
+			!wrapper || Self::has_wrapper_capacity(collection_id),
+			Error::<T, I>::CollectionSupplyExceeded
+		);
+
+		OrphanedInbound::<T, I>::remove(collection_id, item_id);
+		if wrapper {
+			Self::note_wrapper_added(collection_id);
+		}
+		if T::Nfts::owner(&collection_id, &item_id).is_some() {
+			T::Nfts::transfer(&collection_id, &item_id, &to)?;
+		}
+		NFTOwners::<T, I>::insert(collection_id, item_id, &to);
+		Self::deposit_event(Event::OrphanReassigned { collection_id, item_id, to });
+
+		Ok(())
+	}
+
+	/// Remove an orphaned NFT from `from_para_id` and tell that chain to unlock the original
+	pub(crate) fn do_reject_orphaned(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		from_para_id: u32,
+		reason: RejectionReason,
+	) -> DispatchResult {
+		let orphan = OrphanedInbound::<T, I>::get(collection_id, item_id)
+			.filter(|orphan| orphan.from_para_id == from_para_id)
+			.ok_or(Error::<T, I>::NotOrphaned)?;
+		Self::ensure_not_bridge_locked(collection_id, item_id)?;
+		ensure!(
+			NFTOwners::<T, I>::get(collection_id, item_id).as_ref() == Some(&orphan.holder),
+			Error::<T, I>::OrphanNoLongerHeld
+		);
+
+		// The source unlocks the original to its sender, whoever is named, under its own ids
+		let remote_collection_id = Self::remote_collection_id(collection_id, from_para_id);
+		let remote_item_id = Self::remote_item_id(collection_id, item_id, from_para_id);
+		Self::do_bounce_inbound(
+			remote_collection_id,
+			remote_item_id,
+			from_para_id,
+			orphan.holder.clone(),
+			reason,
+		)?;
+
+		OrphanedInbound::<T, I>::remove(collection_id, item_id);
+		if !Self::transactor_withdraw(collection_id, item_id, &orphan.holder)? {
+			Self::backend_burn(collection_id, item_id);
+		}
+		ItemOrigin::<T, I>::remove(collection_id, item_id);
+		if let Some((para_id, remote)) = ItemRemap::<T, I>::take(collection_id, item_id) {
+			RemoteItemRemap::<T, I>::remove((para_id, collection_id), remote);
+		}
+		NFTOwners::<T, I>::remove(collection_id, item_id);
+		Self::clear_item_metadata(collection_id, item_id);
+		Self::clear_item_history(collection_id, item_id);
+
+		Ok(())
+	}
+}
+
+/// Moves this chain's NFTs in `NFTOwners` for the runtime's XCM executor, resolving accounts
+/// with `AccountIdConverter`. Assets `item_of_asset` doesn't match are `AssetNotFound`, and
+/// items the bridge holds are never moved. Not meant as `Config::AssetTransactor`, which the
+/// pallet calls while it holds the item.
+pub struct NftTransactor<T, AccountIdConverter, I = ()>(PhantomData<(T, AccountIdConverter, I)>);
+
+impl<T, AccountIdConverter, I> NftTransactor<T, AccountIdConverter, I>
+where
+	T: Config<I>,
+	I: 'static,
+	AccountIdConverter: XcmConvert<MultiLocation, T::AccountId>,
+{
+	fn resolve_item(what: &MultiAsset) -> Result<(T::CollectionId, T::ItemId), XcmError> {
+		let (collection_id, item_id) =
+			Pallet::<T, I>::item_of_asset(what).map_err(|_| XcmError::AssetNotFound)?;
+		if Pallet::<T, I>::is_bridge_locked(&collection_id, &item_id) {
+			return Err(XcmError::FailedToTransactAsset("ItemLocked"))
+		}
+		Ok((collection_id, item_id))
+	}
+
+	fn resolve_account(who: &MultiLocation) -> Result<T::AccountId, XcmError> {
+		AccountIdConverter::convert_ref(who)
+			.map_err(|()| XcmError::FailedToTransactAsset("AccountIdConversionFailed"))
+	}
+
+	/// The sibling sending the message and its sovereign account
+	fn sender_sovereign(context: &XcmContext) -> Result<(u32, T::AccountId), XcmError> {
+		let from_para_id = context
+			.origin
+			.as_ref()
+			.and_then(Pallet::<T, I>::sibling_para_id)
+			.ok_or(XcmError::FailedToTransactAsset("AccountIdConversionFailed"))?;
+		let sovereign = Self::resolve_account(&MultiLocation::new(1, X1(Parachain(from_para_id))))?;
+		Ok((from_para_id, sovereign))
+	}
+}
+
+impl<T, AccountIdConverter, I> TransactAsset for NftTransactor<T, AccountIdConverter, I>
+where
+	T: Config<I>,
+	I: 'static,
+	AccountIdConverter: XcmConvert<MultiLocation, T::AccountId>,
+{
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, context: &XcmContext) -> XcmResult {
+		let (collection_id, item_id) = Self::resolve_item(what)?;
+		if NFTOwners::<T, I>::contains_key(collection_id, item_id) {
+			return Err(XcmError::FailedToTransactAsset("ItemAlreadyOwned"))
+		}
+		let Ok(owner) = Self::resolve_account(who) else {
+			let (from_para_id, holder) = Self::sender_sovereign(context)?;
+			NFTOwners::<T, I>::insert(collection_id, item_id, &holder);
+			let orphan = OrphanedNft { from_para_id, holder, beneficiary: *who };
+			Pallet::<T, I>::note_orphaned(collection_id, item_id, orphan);
+			return Ok(())
+		};
+		NFTOwners::<T, I>::insert(collection_id, item_id, owner);
+		Ok(())
+	}
+
+	fn withdraw_asset(
+		what: &MultiAsset,
+		who: &MultiLocation,
+		_: Option<&XcmContext>,
+	) -> Result<Assets, XcmError> {
+		let (collection_id, item_id) = Self::resolve_item(what)?;
+		let who = Self::resolve_account(who)?;
+		if NFTOwners::<T, I>::get(collection_id, item_id) != Some(who) {
+			return Err(XcmError::FailedToTransactAsset("NotOwner"))
+		}
+		NFTOwners::<T, I>::remove(collection_id, item_id);
+		// A source chain taking back an orphan settles it
+		OrphanedInbound::<T, I>::remove(collection_id, item_id);
+		Ok(what.clone().into())
+	}
+}
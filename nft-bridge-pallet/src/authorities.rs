@@ -0,0 +1,62 @@
+//! On-chain list of bridge operators
+//!
+//! Root keeps `BridgeAuthorities` with `add_authority` and `remove_authority`, and a runtime
+//! may use `EnsureBridgeAuthority` as `AdminOrigin` instead of wiring its own governance. The
+//! list can't be emptied, so a runtime relying on it never loses every operator by accident.
+
+use crate::*;
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+use sp_std::marker::PhantomData;
+
+/// Passes signed origins of accounts in `BridgeAuthorities`, resolving to the account
+pub struct EnsureBridgeAuthority<T, I = ()>(PhantomData<(T, I)>);
+
+impl<O, T, I> EnsureOrigin<O> for EnsureBridgeAuthority<T, I>
+where
+	O: Into<Result<RawOrigin<T::AccountId>, O>> + From<RawOrigin<T::AccountId>>,
+	T: Config<I>,
+	I: 'static,
+{
+	type Success = T::AccountId;
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Signed(who) if BridgeAuthorities::<T, I>::get().contains(&who) => Ok(who),
+			r => Err(O::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<O, ()> {
+		let who = BridgeAuthorities::<T, I>::get().first().cloned().ok_or(())?;
+		Ok(O::from(RawOrigin::Signed(who)))
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	pub(crate) fn do_add_authority(who: T::AccountId) -> DispatchResult {
+		BridgeAuthorities::<T, I>::try_mutate(|authorities| {
+			ensure!(!authorities.contains(&who), Error::<T, I>::AlreadyAuthority);
+			authorities.try_push(who.clone()).map_err(|_| Error::<T, I>::TooManyAuthorities)
+		})?;
+		Self::deposit_event(Event::AuthorityAdded { who });
+
+		Ok(())
+	}
+
+	pub(crate) fn do_remove_authority(who: T::AccountId) -> DispatchResult {
+		BridgeAuthorities::<T, I>::try_mutate(|authorities| {
+			let index = authorities
+				.iter()
+				.position(|authority| *authority == who)
+				.ok_or(Error::<T, I>::NotAuthority)?;
+			ensure!(authorities.len() > 1, Error::<T, I>::LastAuthority);
+			authorities.remove(index);
+			Ok::<_, DispatchError>(())
+		})?;
+		Self::deposit_event(Event::AuthorityRemoved { who });
+
+		Ok(())
+	}
+}
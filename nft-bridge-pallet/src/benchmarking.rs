@@ -0,0 +1,192 @@
+//! Benchmarks for the NFT bridge pallet's block hooks and its send and receive calls
+
+use super::*;
+use crate::Pallet as NftBridge;
+use codec::{Decode, Encode};
+use frame_benchmarking::{account, benchmarks_instance_pallet};
+use frame_support::{
+	traits::{fungible::Mutate, EnsureOrigin},
+	weights::Weight,
+	BoundedVec,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::{Bounded, TrailingZeroInput, Zero};
+use sp_std::{vec, vec::Vec};
+use xcm::v3::{prelude::*, Xcm};
+
+/// Most items processed in one hook run that the benchmarks cover
+const MAX_ITEMS: u32 = 100;
+
+/// Destination of every benchmarked transfer
+const DEST_PARA_ID: u32 = 2000;
+
+/// Attributes written for one item: its metadata and its `Primary` URI
+const ITEM_ATTRIBUTES: u32 = 2;
+
+// A distinct collection or item id for each index
+fn id<Id: Decode>(index: u32) -> Id {
+	Id::decode(&mut TrailingZeroInput::new(&index.encode())).expect("input is infinite")
+}
+
+// Put `n` transfers to `DEST_PARA_ID` in flight, all sent at `sent_at` with the given status
+fn pending_transfers<T: Config<I>, I: 'static>(
+	n: u32,
+	sent_at: BlockNumberFor<T>,
+	status: TransferStatus<BlockNumberFor<T>>,
+) -> Vec<(T::CollectionId, T::ItemId, TransferId)> {
+	(0..n)
+		.map(|index| {
+			let (collection_id, item_id) = (id::<T::CollectionId>(0), id::<T::ItemId>(index));
+			let transfer_id = index as TransferId;
+			let transfer = TransferInfo {
+				id: transfer_id,
+				sender: account("sender", index, 0),
+				dest: MultiLocation::new(1, X1(Parachain(DEST_PARA_ID))),
+				sent_at,
+				status: status.clone(),
+				retry_count: 0,
+				valid_until: None,
+			};
+			PendingTransfers::<T, I>::insert(collection_id, item_id, transfer);
+			PendingByDestination::<T, I>::insert(DEST_PARA_ID, (collection_id, item_id), ());
+			TransferKeys::<T, I>::insert(transfer_id, (collection_id, item_id));
+			(collection_id, item_id, transfer_id)
+		})
+		.collect()
+}
+
+// Register `collection_id` as native to this chain, managed by `admin`
+fn register<T: Config<I>, I: 'static>(collection_id: T::CollectionId, admin: T::AccountId) {
+	let mode = CollectionMode::Native;
+	Collections::<T, I>::insert(
+		collection_id,
+		CollectionInfo { reserve: None, admin, mode, max_supply: None },
+	);
+}
+
+// An item's metadata of `m` bytes, with a `Primary` URI of the longest length if it is to have
+// `a` attributes
+fn item_metadata<T: Config<I>, I: 'static>(m: u32, a: u32) -> (Vec<u8>, Option<Vec<u8>>) {
+	(vec![1; m as usize], (a > 1).then(|| vec![1; T::MaxUriLen::get() as usize]))
+}
+
+benchmarks_instance_pallet! {
+	send_nft {
+		let m in 1 .. T::MaxMetadataLen::get();
+		let a in 1 .. ITEM_ATTRIBUTES;
+		let origin = T::SendOrigin::try_successful_origin()
+			.map_err(|_| "SendOrigin has no successful origin")?;
+		let sender = T::SendOrigin::ensure_origin(origin.clone())
+			.map_err(|_| "SendOrigin rejects its successful origin")?;
+		let (collection_id, item_id) = (id::<T::CollectionId>(0), id::<T::ItemId>(0));
+		register::<T, I>(collection_id, sender.clone());
+		NFTOwners::<T, I>::insert(collection_id, item_id, sender.clone());
+		T::Currency::set_balance(&sender, BalanceOf::<T, I>::max_value() / 2u32.into());
+		let (metadata, metadata_uri) = item_metadata::<T, I>(m, a);
+	}: _<T::RuntimeOrigin>(
+		origin,
+		collection_id,
+		item_id,
+		DEST_PARA_ID,
+		metadata,
+		metadata_uri,
+		None,
+		ExecutionLimit::Default,
+		false,
+		Vec::new(),
+		None
+	)
+	verify {
+		assert!(PendingTransfers::<T, I>::contains_key(collection_id, item_id));
+	}
+
+	receive_nft {
+		let m in 1 .. T::MaxMetadataLen::get();
+		let a in 1 .. ITEM_ATTRIBUTES;
+		let origin = T::ReceiveOrigin::try_successful_origin()
+			.map_err(|_| "ReceiveOrigin has no successful origin")?;
+		let location = T::ReceiveOrigin::ensure_origin(origin.clone())
+			.map_err(|_| "ReceiveOrigin rejects its successful origin")?;
+		let from_para_id =
+			NftBridge::<T, I>::sibling_para_id(&location).ok_or("ReceiveOrigin isn't a sibling")?;
+		let capabilities = LaneCapabilities { remote_calls: false, metadata_updates: false };
+		Lanes::<T, I>::insert(from_para_id, Lane { status: LaneStatus::Active, capabilities });
+		let (collection_id, item_id) = (id::<T::CollectionId>(0), id::<T::ItemId>(0));
+		let owner: T::AccountId = account("owner", 0, 0);
+		register::<T, I>(collection_id, owner.clone());
+		let (metadata, metadata_uri) = item_metadata::<T, I>(m, a);
+	}: _<T::RuntimeOrigin>(
+		origin,
+		collection_id,
+		item_id,
+		from_para_id,
+		owner.clone(),
+		metadata,
+		metadata_uri,
+		None,
+		None,
+		None,
+		Vec::new(),
+		None,
+		None,
+		None,
+		None
+	)
+	verify {
+		assert_eq!(NFTOwners::<T, I>::get(collection_id, item_id), Some(owner));
+	}
+
+	on_initialize_expiries {
+		let n in 0 .. MAX_ITEMS;
+		// In-flight transfers are never expired, so the sweep unlocks refused ones
+		let rejected = TransferStatus::Rejected { code: RejectionCode::Other };
+		pending_transfers::<T, I>(n, Zero::zero(), rejected);
+		let now = T::TransferTimeout::get();
+	}: {
+		NftBridge::<T, I>::expire_transfers(now, Weight::MAX);
+	}
+	verify {
+		assert_eq!(PendingTransfers::<T, I>::iter().count(), 0);
+	}
+
+	on_idle_queue_drain {
+		let n in 0 .. MAX_ITEMS;
+		let transfers = pending_transfers::<T, I>(n, Zero::zero(), TransferStatus::Queued);
+		let mut queue = BoundedVec::<TransferId, T::MaxOutboundQueue>::default();
+		for (collection_id, item_id, transfer_id) in transfers {
+			if queue.try_push(transfer_id).is_ok() {
+				let message = NftBridge::<T, I>::encode_stored(&Xcm(vec![ClearOrigin])).unwrap();
+				QueuedSends::<T, I>::insert(transfer_id, (collection_id, item_id, message));
+			}
+		}
+		OutboundQueue::<T, I>::insert(DEST_PARA_ID, queue);
+	}: {
+		NftBridge::<T, I>::drain_outbound_queues(Weight::MAX);
+	}
+	verify {
+		assert!(OutboundQueue::<T, I>::get(DEST_PARA_ID).is_empty());
+	}
+
+	on_idle_retries {
+		let n in 0 .. MAX_ITEMS;
+		let status = TransferStatus::SendFailed { next_attempt: Zero::zero() };
+		let transfers = pending_transfers::<T, I>(n, Zero::zero(), status);
+		for (collection_id, item_id, transfer_id) in transfers {
+			let message = NftBridge::<T, I>::encode_stored(&Xcm(vec![ClearOrigin])).unwrap();
+			FailedSends::<T, I>::insert(transfer_id, (collection_id, item_id, message));
+		}
+	}: {
+		NftBridge::<T, I>::retry_failed_sends(Zero::zero(), Weight::MAX);
+	}
+
+	on_idle_aging_scan {
+		let n in 0 .. MAX_ITEMS;
+		pending_transfers::<T, I>(n, Zero::zero(), TransferStatus::InFlight);
+		let now = T::WarnAfter::get();
+	}: {
+		NftBridge::<T, I>::warn_aging_transfers(now, Weight::MAX);
+	}
+	verify {
+		assert_eq!(AgingWarned::<T, I>::iter().count() as u32, n);
+	}
+}
@@ -0,0 +1,105 @@
+//! Chain-independent identifiers of bridged items
+//!
+//! An item's canonical id names it on the chain it was minted on, so every chain that hosts it
+//! over its life reports the same id to indexers. It reads
+//! `<network>:<para id>/<collection>/<item>`, CAIP style: the network is its lowercase name, the
+//! first 16 bytes of its genesis hash in hex, or `eip155-<chain id>`, and the collection and
+//! item are the hex of the first 8 and 16 bytes of their SCALE encodings, as in the asset wire
+//! format. The id is fixed in `CanonicalIds` the first time the item is sent or received.
+
+use crate::*;
+use codec::Encode;
+use frame_support::traits::Get;
+use sp_runtime::traits::Hash;
+use sp_std::vec::Vec;
+use xcm::v3::NetworkId;
+
+fn push_hex(out: &mut Vec<u8>, bytes: &[u8]) {
+	const DIGITS: &[u8; 16] = b"0123456789abcdef";
+	for byte in bytes {
+		out.push(DIGITS[(byte >> 4) as usize]);
+		out.push(DIGITS[(byte & 0xf) as usize]);
+	}
+}
+
+fn push_decimal(out: &mut Vec<u8>, mut value: u64) {
+	let start = out.len();
+	loop {
+		out.push(b'0' + (value % 10) as u8);
+		value /= 10;
+		if value == 0 {
+			break
+		}
+	}
+	out[start..].reverse();
+}
+
+fn push_network(out: &mut Vec<u8>, network: &NetworkId) {
+	match network {
+		NetworkId::ByGenesis(hash) | NetworkId::ByFork { block_hash: hash, .. } => {
+			push_hex(out, &hash[..16])
+		},
+		NetworkId::Ethereum { chain_id } => {
+			out.extend_from_slice(b"eip155-");
+			push_decimal(out, *chain_id);
+		},
+		NetworkId::Polkadot => out.extend_from_slice(b"polkadot"),
+		NetworkId::Kusama => out.extend_from_slice(b"kusama"),
+		NetworkId::Westend => out.extend_from_slice(b"westend"),
+		NetworkId::Rococo => out.extend_from_slice(b"rococo"),
+		NetworkId::Wococo => out.extend_from_slice(b"wococo"),
+		NetworkId::BitcoinCore => out.extend_from_slice(b"bitcoincore"),
+		NetworkId::BitcoinCash => out.extend_from_slice(b"bitcoincash"),
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The item's canonical id: the stored one, or the one it would be given now
+	pub fn canonical_id(collection_id: T::CollectionId, item_id: T::ItemId) -> CanonicalId {
+		CanonicalIds::<T, I>::get(collection_id, item_id)
+			.unwrap_or_else(|| Self::derive_canonical_id(collection_id, item_id))
+	}
+
+	/// Fix the item's canonical id if it has none yet, and return it
+	pub(crate) fn note_canonical_id(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> CanonicalId {
+		if let Some(id) = CanonicalIds::<T, I>::get(collection_id, item_id) {
+			return id
+		}
+		let id = Self::derive_canonical_id(collection_id, item_id);
+		CanonicalIdIndex::<T, I>::insert(T::Hashing::hash(&id), (collection_id, item_id));
+		CanonicalIds::<T, I>::insert(collection_id, item_id, &id);
+		id
+	}
+
+	/// Compose the id from the chain the item came from and its ids there, which are this
+	/// chain's own for native items
+	fn derive_canonical_id(collection_id: T::CollectionId, item_id: T::ItemId) -> CanonicalId {
+		let (para_id, remote_collection, remote_item) =
+			match ItemOrigin::<T, I>::get(collection_id, item_id) {
+				Some(para_id) => {
+					let remote_collection = WrappedCollectionSources::<T, I>::get(collection_id)
+						.filter(|(source, _)| *source == para_id)
+						.map_or(collection_id, |(_, remote)| remote);
+					let remote_item = ItemRemap::<T, I>::get(collection_id, item_id)
+						.filter(|(source, _)| *source == para_id)
+						.map_or(item_id, |(_, remote)| remote);
+					(para_id, remote_collection, remote_item)
+				},
+				None => (T::SelfParaId::get(), collection_id, item_id),
+			};
+
+		let mut id = Vec::new();
+		push_network(&mut id, &T::RelayNetwork::get());
+		id.push(b':');
+		push_decimal(&mut id, para_id.into());
+		id.push(b'/');
+		remote_collection.using_encoded(|bytes| push_hex(&mut id, &bytes[..bytes.len().min(8)]));
+		id.push(b'/');
+		remote_item.using_encoded(|bytes| push_hex(&mut id, &bytes[..bytes.len().min(16)]));
+		// At most 32 + 1 + 10 + 1 + 16 + 1 + 32 bytes, within the bound
+		CanonicalId::truncate_from(id)
+	}
+}
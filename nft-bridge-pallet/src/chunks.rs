@@ -0,0 +1,210 @@
+//! Chunked transfer of metadata too large for one message
+//!
+//! `send_chunked_nft` sends an item like a hash-only transfer and then streams its metadata to
+//! the destination: a `metadata_chunk_header` announcing the number of chunks and the hash of
+//! the whole blob, followed by one `metadata_chunk` per `ChunkSize` bytes. The receiver keeps
+//! chunks in `PendingChunks`, in any order, and writes `NFTMetadata` once all of them arrived and
+//! the assembled blob matches the announced hash. Assemblies still incomplete after
+//! `ReassemblyTimeout` blocks may be purged by anyone.
+
+use crate::*;
+use frame_support::{
+	traits::{fungible::Mutate as _, tokens::Preservation, Get},
+	BoundedVec,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{
+	traits::{Hash, Saturating},
+	DispatchError,
+};
+use sp_std::vec::Vec;
+
+/// A chunk as carried by `metadata_chunk`
+pub type MetadataChunkOf<T, I = ()> =
+	MetadataChunk<BoundedVec<u8, <T as Config<I>>::ChunkSize>, <T as frame_system::Config>::Hash>;
+
+/// An assembly as stored in `MetadataAssemblies`
+pub type MetadataAssemblyOf<T, I = ()> = MetadataAssembly<
+	<T as Config<I>>::CollectionId,
+	<T as Config<I>>::ItemId,
+	<T as frame_system::Config>::Hash,
+	BlockNumberFor<T>,
+>;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Send an NFT and stream its metadata to the destination in chunks
+	pub(crate) fn do_send_chunked_nft(
+		sender: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		dest_para_id: u32,
+		metadata: Vec<u8>,
+		valid_until: Option<BlockNumberFor<T>>,
+	) -> Result<(TransferId, u32), DispatchError> {
+		// Bill the metadata like an inline payload; the send itself carries none
+		let per_byte = T::FeePerMetadataByte::get().saturating_mul((metadata.len() as u32).into());
+		T::Currency::transfer(
+			&sender,
+			&T::FeeDestination::get(),
+			per_byte,
+			Preservation::Preserve,
+		)?;
+
+		let transfer_id = Self::do_xcm_transfer_nft(
+			sender,
+			collection_id,
+			item_id,
+			dest_para_id,
+			Vec::new(),
+			None,
+			None,
+			ExecutionLimit::Default,
+			false,
+			valid_until,
+		)?;
+		let chunks = Self::metadata_chunks(transfer_id, &metadata)?;
+		let total = chunks.len() as u32;
+		let metadata_hash = T::Hashing::hash(&metadata);
+		Self::set_item_metadata(collection_id, item_id, metadata, None)?;
+
+		let remote_item_id = Self::remote_item_id(collection_id, item_id, dest_para_id);
+		Self::send_bridge_call(
+			dest_para_id,
+			Call::<T, I>::metadata_chunk_header {
+				transfer_id,
+				collection_id: Self::remote_collection_id(collection_id, dest_para_id),
+				item_id: remote_item_id,
+				total,
+				metadata_hash,
+			},
+		)?;
+		for chunk in chunks {
+			Self::send_bridge_call(dest_para_id, Call::<T, I>::metadata_chunk { chunk })?;
+		}
+
+		Ok((transfer_id, total))
+	}
+
+	/// Split `metadata` into `ChunkSize` pieces, refusing blobs above `MaxMetadataChunks` of them
+	pub(crate) fn metadata_chunks(
+		transfer_id: TransferId,
+		metadata: &[u8],
+	) -> Result<Vec<MetadataChunkOf<T, I>>, DispatchError> {
+		let size = T::ChunkSize::get().max(1) as usize;
+		let total = ((metadata.len() + size - 1) / size) as u32;
+		ensure!(total > 0 && total <= T::MaxMetadataChunks::get(), Error::<T, I>::MetadataTooLong);
+
+		metadata
+			.chunks(size)
+			.enumerate()
+			.map(|(index, data)| {
+				let data: BoundedVec<u8, T::ChunkSize> =
+					data.to_vec().try_into().map_err(|_| Error::<T, I>::MetadataTooLong)?;
+				let chunk_hash = T::Hashing::hash(&data);
+				Ok(MetadataChunk { transfer_id, index: index as u32, total, data, chunk_hash })
+			})
+			.collect()
+	}
+
+	/// Open the assembly of a chunked blob announced by `from_para_id`
+	pub(crate) fn do_metadata_chunk_header(
+		from_para_id: u32,
+		transfer_id: TransferId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		total: u32,
+		metadata_hash: T::Hash,
+	) -> DispatchResult {
+		ensure!(total > 0 && total <= T::MaxMetadataChunks::get(), Error::<T, I>::InvalidChunk);
+		ensure!(
+			!MetadataAssemblies::<T, I>::contains_key(from_para_id, transfer_id),
+			Error::<T, I>::InvalidChunk
+		);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		MetadataAssemblies::<T, I>::insert(
+			from_para_id,
+			transfer_id,
+			MetadataAssembly {
+				collection_id,
+				item_id,
+				total,
+				received: 0,
+				metadata_hash,
+				expires_at: now.saturating_add(T::ReassemblyTimeout::get()),
+			},
+		);
+
+		Ok(())
+	}
+
+	/// Store one chunk and, once every chunk is in, assemble and verify the blob
+	pub(crate) fn do_metadata_chunk(
+		from_para_id: u32,
+		chunk: MetadataChunkOf<T, I>,
+	) -> DispatchResult {
+		let transfer_id = chunk.transfer_id;
+		let mut assembly = MetadataAssemblies::<T, I>::get(from_para_id, transfer_id)
+			.ok_or(Error::<T, I>::MetadataAssemblyNotFound)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(now < assembly.expires_at, Error::<T, I>::MetadataAssemblyNotFound);
+		ensure!(
+			chunk.total == assembly.total && chunk.index < assembly.total,
+			Error::<T, I>::InvalidChunk
+		);
+		ensure!(
+			T::Hashing::hash(&chunk.data) == chunk.chunk_hash,
+			Error::<T, I>::ChunkHashMismatch
+		);
+
+		let key = (from_para_id, transfer_id);
+		if !PendingChunks::<T, I>::contains_key(key, chunk.index) {
+			PendingChunks::<T, I>::insert(key, chunk.index, chunk.data);
+			assembly.received += 1;
+		}
+		if assembly.received < assembly.total {
+			MetadataAssemblies::<T, I>::insert(from_para_id, transfer_id, assembly);
+			return Ok(())
+		}
+
+		// Every chunk is in: the assembly ends here whether or not the blob checks out
+		MetadataAssemblies::<T, I>::remove(from_para_id, transfer_id);
+		let mut metadata = Vec::new();
+		for index in 0..assembly.total {
+			metadata.extend(PendingChunks::<T, I>::take(key, index).unwrap_or_default());
+		}
+
+		let collection_id = Self::local_collection_id(from_para_id, assembly.collection_id);
+		let item_id = RemoteItemRemap::<T, I>::get((from_para_id, collection_id), assembly.item_id)
+			.unwrap_or(assembly.item_id);
+		let valid = T::Hashing::hash(&metadata) == assembly.metadata_hash &&
+			ItemOrigin::<T, I>::get(collection_id, item_id) == Some(from_para_id);
+		if !valid {
+			Self::deposit_event(Event::MetadataAssemblyFailed { from_para_id, transfer_id });
+			return Ok(())
+		}
+
+		Self::set_item_metadata(collection_id, item_id, metadata, None)?;
+		DeferredMetadata::<T, I>::remove(collection_id, item_id);
+		Self::deposit_event(Event::MetadataAssembled { collection_id, item_id, from_para_id });
+
+		Ok(())
+	}
+
+	/// Drop an assembly that outlived `ReassemblyTimeout` along with its chunks
+	pub(crate) fn do_purge_metadata_assembly(
+		from_para_id: u32,
+		transfer_id: TransferId,
+	) -> DispatchResult {
+		let assembly = MetadataAssemblies::<T, I>::get(from_para_id, transfer_id)
+			.ok_or(Error::<T, I>::MetadataAssemblyNotFound)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(now >= assembly.expires_at, Error::<T, I>::AssemblyNotExpired);
+
+		MetadataAssemblies::<T, I>::remove(from_para_id, transfer_id);
+		let _ =
+			PendingChunks::<T, I>::clear_prefix((from_para_id, transfer_id), assembly.total, None);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,98 @@
+//! Escrow of NFTs for other pallets
+//!
+//! `NonfungibleEscrow` lends the bridge's locks to pallets that need temporary custody of an
+//! NFT. Like a transfer under `LockStrategy::Freeze`, an escrow leaves the item with its owner
+//! and only flags it: `EscrowedItems` makes `is_bridge_locked`, and with it `BridgeLockFilter`,
+//! refuse to move it. Escrows are tagged with the reason they were taken for and kept apart
+//! from the bridge's own records, so neither side can release or bridge the other's items.
+
+use crate::*;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	pub(crate) fn do_escrow(
+		who: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		reason: EscrowReason,
+	) -> Result<EscrowId, DispatchError> {
+		ensure!(reason != EscrowReason::Bridge, Error::<T, I>::ReservedEscrowReason);
+		ensure!(
+			Self::escrow_reason_of(collection_id, item_id).is_none(),
+			Error::<T, I>::EscrowedElsewhere
+		);
+		let owner = Self::owner(collection_id, item_id)
+			.ok_or_else(|| Self::missing_item_error(collection_id))?;
+		ensure!(owner == who, Error::<T, I>::NotOwner);
+
+		let escrow_id = NextEscrowId::<T, I>::mutate(|id| {
+			let current = *id;
+			*id = id.wrapping_add(1);
+			current
+		});
+		Escrows::<T, I>::insert(
+			escrow_id,
+			EscrowInfo { collection_id, item_id, owner: who.clone(), reason },
+		);
+		EscrowedItems::<T, I>::insert(collection_id, item_id, escrow_id);
+		Self::deposit_event(Event::ItemEscrowed {
+			escrow_id,
+			collection_id,
+			item_id,
+			owner: who,
+			reason,
+		});
+
+		Ok(escrow_id)
+	}
+
+	pub(crate) fn do_release(escrow_id: EscrowId, to: T::AccountId) -> DispatchResult {
+		let EscrowInfo { collection_id, item_id, owner, .. } =
+			Escrows::<T, I>::take(escrow_id).ok_or(Error::<T, I>::EscrowNotFound)?;
+		// Unflagged before moving, or the NFT backend would refuse to move it
+		EscrowedItems::<T, I>::remove(collection_id, item_id);
+
+		if to != owner {
+			Self::transactor_withdraw(collection_id, item_id, &owner)?;
+			Self::transactor_deposit(collection_id, item_id, &to)?;
+			NFTOwners::<T, I>::insert(collection_id, item_id, &to);
+		}
+		Self::deposit_event(Event::EscrowReleased { escrow_id, collection_id, item_id, to });
+
+		Ok(())
+	}
+
+	/// Why an NFT is held in escrow: the reason of an escrow taken through
+	/// `NonfungibleEscrow`, or `Bridge` for any other hold the bridge has on it
+	pub fn escrow_reason_of(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> Option<EscrowReason> {
+		match EscrowedItems::<T, I>::get(collection_id, item_id) {
+			Some(escrow_id) => Escrows::<T, I>::get(escrow_id).map(|escrow| escrow.reason),
+			None => {
+				Self::is_bridge_locked(&collection_id, &item_id).then_some(EscrowReason::Bridge)
+			},
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> NonfungibleEscrow<T::AccountId, T::CollectionId, T::ItemId>
+	for Pallet<T, I>
+{
+	fn escrow(
+		who: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		reason: EscrowReason,
+	) -> Result<EscrowId, DispatchError> {
+		Self::do_escrow(who, collection_id, item_id, reason)
+	}
+
+	fn release(escrow_id: EscrowId, to: T::AccountId) -> DispatchResult {
+		Self::do_release(escrow_id, to)
+	}
+
+	fn escrow_reason(collection_id: T::CollectionId, item_id: T::ItemId) -> Option<EscrowReason> {
+		Self::escrow_reason_of(collection_id, item_id)
+	}
+}
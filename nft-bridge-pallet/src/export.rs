@@ -0,0 +1,324 @@
+//! Migration of a whole collection to another chain
+//!
+//! `export_collection` marks a collection as migrating, which stops individual sends of its items.
+//! `on_idle` then sends its items to their owners on the destination, `batch_size` per block and
+//! within the destination's in-flight cap, until every item has been examined. `abort_export`
+//! stops an export and returns every item it sent that the destination has not confirmed yet.
+//!
+//! Once no item of a collection is owned or pending here, `clear_collection_data` removes what
+//! the bridge still keeps about it, `limit` entries per call. `ItemsAbroad` is kept so that items
+//! coming back are still recognised as returning.
+
+use crate::*;
+use codec::FullCodec;
+use frame_support::storage::IterableStorageDoubleMap;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::Zero;
+use sp_std::vec::Vec;
+
+/// An export as stored in `CollectionExportState`
+pub type CollectionExportOf<T> =
+	CollectionExport<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+/// Number of item-keyed maps `clear_collection_data` works through before the per-collection
+/// entries
+const CLEAR_ITEM_STAGES: u8 = 9;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Start migrating `collection_id` to `dest_para_id`
+	pub(crate) fn do_export_collection(
+		who: T::AccountId,
+		collection_id: T::CollectionId,
+		dest_para_id: u32,
+		batch_size: u32,
+	) -> DispatchResult {
+		ensure!(
+			Self::collection_admin(collection_id) == Some(who.clone()),
+			Error::<T, I>::NotCollectionAdmin
+		);
+		Self::ensure_transferable(collection_id)?;
+		ensure!(
+			!CollectionExportState::<T, I>::contains_key(collection_id),
+			Error::<T, I>::CollectionMigrating
+		);
+		ensure!(
+			batch_size > 0 && batch_size <= T::MaxExportBatchSize::get(),
+			Error::<T, I>::InvalidBatchSize
+		);
+
+		CollectionExportState::<T, I>::insert(
+			collection_id,
+			CollectionExport {
+				admin: who,
+				dest_para_id,
+				batch_size,
+				started_at: frame_system::Pallet::<T>::block_number(),
+				cursor: None,
+				exported: 0,
+				skipped: 0,
+			},
+		);
+
+		Self::deposit_event(Event::CollectionExportStarted { collection_id, dest_para_id });
+
+		Ok(())
+	}
+
+	/// Stop an export and return every item it sent whose message can't arrive any more to its
+	/// owner; items still in flight settle as usual.
+	/// `witness` must be at least the number of items the export has sent.
+	pub(crate) fn do_abort_export(
+		who: T::AccountId,
+		collection_id: T::CollectionId,
+		witness: u32,
+	) -> DispatchResult {
+		let export =
+			CollectionExportState::<T, I>::get(collection_id).ok_or(Error::<T, I>::ExportNotFound)?;
+		ensure!(
+			export.admin == who || Self::collection_admin(collection_id) == Some(who),
+			Error::<T, I>::NotCollectionAdmin
+		);
+		ensure!(export.exported <= witness, Error::<T, I>::BadWitness);
+
+		CollectionExportState::<T, I>::remove(collection_id);
+
+		// Individual sends are blocked while migrating, so these are the export's own transfers
+		let dest = Self::dest_location(export.dest_para_id);
+		let unconfirmed: Vec<_> = PendingTransfers::<T, I>::iter_prefix(collection_id)
+			.filter(|(_, transfer)| {
+				transfer.dest == dest &&
+					transfer.sent_at >= export.started_at &&
+					transfer.status.is_recallable()
+			})
+			.collect();
+		let mut restored = 0u32;
+		for (item_id, transfer) in unconfirmed {
+			if Self::unlock_nft(collection_id, item_id, &transfer.sender).is_ok() {
+				restored += 1;
+			}
+		}
+
+		Self::deposit_event(Event::CollectionExportAborted { collection_id, restored });
+
+		Ok(())
+	}
+
+	/// Advance running exports by one batch each, as far as `remaining_weight` allows
+	pub(crate) fn process_exports(remaining_weight: Weight) -> Weight {
+		let per_item = T::WeightInfo::export_item();
+		let mut budget = remaining_weight.checked_div_per_component(&per_item).unwrap_or(0);
+		let mut consumed = Weight::zero();
+
+		let exports: Vec<_> = CollectionExportState::<T, I>::iter().collect();
+		for (collection_id, mut export) in exports {
+			let dest_para_id = export.dest_para_id;
+			let max_in_flight = Self::destination_config(dest_para_id)
+				.and_then(|c| c.max_in_flight)
+				.unwrap_or(u32::MAX);
+			let room = max_in_flight.saturating_sub(InFlightCount::<T, I>::get(dest_para_id));
+			let limit = budget.min(export.batch_size.min(room) as u64);
+			if limit == 0 {
+				continue
+			}
+
+			let mut iter = match export.cursor.take() {
+				Some(key) => NFTOwners::<T, I>::iter_prefix_from(collection_id, key),
+				None => NFTOwners::<T, I>::iter_prefix(collection_id),
+			};
+			let mut batch = Vec::new();
+			let finished = loop {
+				if batch.len() as u64 >= limit {
+					break false
+				}
+				match iter.next() {
+					Some(entry) => batch.push(entry),
+					None => break true,
+				}
+			};
+			export.cursor = Some(iter.last_raw_key().to_vec());
+			budget -= batch.len() as u64;
+			consumed.saturating_accrue(per_item.saturating_mul(batch.len() as u64));
+
+			for (item_id, owner) in batch {
+				let sent = frame_support::storage::with_storage_layer(|| {
+					Self::export_item(&export.admin, &owner, collection_id, item_id, dest_para_id)
+				});
+				match sent {
+					Ok(()) => export.exported += 1,
+					Err(_) => export.skipped += 1,
+				}
+			}
+
+			if finished {
+				CollectionExportState::<T, I>::remove(collection_id);
+				Self::deposit_event(Event::CollectionExported {
+					collection_id,
+					dest_para_id,
+					exported: export.exported,
+					skipped: export.skipped,
+				});
+			} else {
+				Self::deposit_event(Event::CollectionExportProgress {
+					collection_id,
+					exported: export.exported,
+					skipped: export.skipped,
+				});
+				CollectionExportState::<T, I>::insert(collection_id, export);
+			}
+		}
+
+		consumed
+	}
+
+	/// Reject individual sends of items in a collection that is being exported
+	pub(crate) fn ensure_not_migrating(collection_id: T::CollectionId) -> DispatchResult {
+		ensure!(
+			!CollectionExportState::<T, I>::contains_key(collection_id),
+			Error::<T, I>::CollectionMigrating
+		);
+		Ok(())
+	}
+
+	/// Lock an item and send it to its owner on `dest_para_id`, with `payer` paying delivery.
+	/// A migration carries no bridging fee.
+	fn export_item(
+		payer: &T::AccountId,
+		owner: &T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		dest_para_id: u32,
+	) -> DispatchResult {
+		let metadata = NFTMetadata::<T, I>::get(collection_id, item_id).unwrap_or_default();
+		Self::ensure_metadata_fits_destination(dest_para_id, metadata.len())?;
+		let transfer_id = Self::begin_transfer(
+			owner,
+			collection_id,
+			item_id,
+			dest_para_id,
+			metadata,
+			None,
+			TransferStatus::InFlight,
+			None,
+		)?;
+		let message = Self::build_program(
+			owner,
+			&[(collection_id, item_id)],
+			&Self::dest_location(dest_para_id),
+			None,
+			ExecutionLimit::Default,
+		)?;
+		Self::ensure_message_fits(dest_para_id, &message)?;
+		let (message_hash, delivery_fee) = Self::send_paid(payer, dest_para_id, message)?;
+
+		Self::index_message(message_hash, transfer_id);
+		InFlightCount::<T, I>::mutate(dest_para_id, |count| *count = count.saturating_add(1));
+		Self::count_activity(|counters| &mut counters.sent, 1);
+		Self::deposit_event(Event::NFTSent {
+			collection_id,
+			item_id,
+			dest_para_id,
+			fee: Zero::zero(),
+			delivery_fee,
+			canonical_id: Self::note_canonical_id(collection_id, item_id),
+		});
+		T::OnNftSent::on_bridged(collection_id, item_id, dest_para_id, owner);
+
+		Ok(())
+	}
+
+	/// Remove up to `limit` residual entries of a collection with no item owned or pending here,
+	/// continuing from `CollectionClearCursor`. Returns how many entries were removed and whether
+	/// nothing is left.
+	pub(crate) fn do_clear_collection_data(
+		collection_id: T::CollectionId,
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError> {
+		ensure!(limit > 0, Error::<T, I>::InvalidBatchSize);
+		ensure!(
+			NFTOwners::<T, I>::iter_key_prefix(collection_id).next().is_none() &&
+				PendingTransfers::<T, I>::iter_key_prefix(collection_id).next().is_none(),
+			Error::<T, I>::CollectionInUse
+		);
+		ensure!(
+			!CollectionExportState::<T, I>::contains_key(collection_id),
+			Error::<T, I>::CollectionMigrating
+		);
+
+		let mut stage = CollectionClearCursor::<T, I>::get(collection_id);
+		let mut removed = 0u32;
+		while removed < limit && stage < CLEAR_ITEM_STAGES {
+			let budget = limit - removed;
+			let cleared = match stage {
+				0 => Self::clear_item_entries::<NFTMetadata<T, I>, _, _>(collection_id, budget),
+				1 => Self::clear_item_entries::<MetadataUris<T, I>, _, _>(collection_id, budget),
+				2 => Self::clear_item_entries::<ItemOrigin<T, I>, _, _>(collection_id, budget),
+				3 => Self::clear_item_remaps(collection_id, budget),
+				4 => Self::clear_item_entries::<DeferredMetadata<T, I>, _, _>(
+					collection_id,
+					budget,
+				),
+				5 => Self::clear_item_entries::<Royalties<T, I>, _, _>(collection_id, budget),
+				6 => Self::clear_item_entries::<MetadataSubscribers<T, I>, _, _>(
+					collection_id,
+					budget,
+				),
+				7 => Self::clear_item_entries::<LastMetadataSync<T, I>, _, _>(
+					collection_id,
+					budget,
+				),
+				_ => Self::clear_item_entries::<OrphanedItems<T, I>, _, _>(collection_id, budget),
+			};
+			removed += cleared;
+			// A map that gave fewer entries than asked for is empty
+			if cleared < budget {
+				stage += 1;
+			}
+		}
+
+		// The per-collection entries go last, together
+		if stage < CLEAR_ITEM_STAGES || removed >= limit {
+			CollectionClearCursor::<T, I>::insert(collection_id, stage);
+			return Ok((removed, false))
+		}
+		CollectionMetadataBytes::<T, I>::remove(collection_id);
+		CollectionMetadataQuota::<T, I>::remove(collection_id);
+		CollectionFeeOverride::<T, I>::remove(collection_id);
+		NonTransferableCollections::<T, I>::remove(collection_id);
+		KnownCollections::<T, I>::remove(collection_id);
+		Collections::<T, I>::remove(collection_id);
+		if let Some((para_id, remote)) = WrappedCollectionSources::<T, I>::take(collection_id) {
+			WrappedCollectionIds::<T, I>::remove(para_id, remote);
+		}
+		CollectionClearCursor::<T, I>::remove(collection_id);
+
+		Ok((removed + 1, true))
+	}
+
+	/// Remove up to `limit` entries of an item-keyed map under `collection_id`
+	fn clear_item_entries<M, K2, V>(collection_id: T::CollectionId, limit: u32) -> u32
+	where
+		M: IterableStorageDoubleMap<T::CollectionId, K2, V>,
+		K2: FullCodec,
+		V: FullCodec,
+	{
+		let keys: Vec<K2> = M::iter_key_prefix(collection_id).take(limit as usize).collect();
+		for key in &keys {
+			M::remove(collection_id, key);
+		}
+		keys.len() as u32
+	}
+
+	/// Remove up to `limit` item remaps under `collection_id` along with their reverse entries
+	fn clear_item_remaps(collection_id: T::CollectionId, limit: u32) -> u32 {
+		let remaps: Vec<_> =
+			ItemRemap::<T, I>::iter_prefix(collection_id).take(limit as usize).collect();
+		for (item_id, (para_id, remote_item_id)) in &remaps {
+			ItemRemap::<T, I>::remove(collection_id, item_id);
+			RemoteItemRemap::<T, I>::remove((*para_id, collection_id), remote_item_id);
+		}
+		remaps.len() as u32
+	}
+
+}
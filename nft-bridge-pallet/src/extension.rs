@@ -0,0 +1,135 @@
+//! Transaction pool check for bridge sends
+//!
+//! `CheckBridgeAvailable` runs the cheap checks a send would fail on-chain when the transaction
+//! is validated, so sends that can't succeed never reach a block or pay inclusion fees: the
+//! bridge being paused, a destination with neither a `DestinationConfig` nor an active lane, a
+//! collection that isn't registered, and a signer already at `MaxPendingPerAccount`. Every other
+//! call passes through untouched.
+
+use crate::*;
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchInfo, traits::IsSubType};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// `InvalidTransaction::Custom` code of a send made while the bridge is paused
+pub const BRIDGE_PAUSED: u8 = 1;
+/// `InvalidTransaction::Custom` code of a send of an item whose collection isn't registered
+pub const COLLECTION_NOT_REGISTERED: u8 = 2;
+/// `InvalidTransaction::Custom` code of a send by a signer at `MaxPendingPerAccount`
+pub const TOO_MANY_PENDING: u8 = 3;
+/// `InvalidTransaction::Custom` code of a send to a destination without a config or with a lane
+/// that isn't active
+pub const DESTINATION_NOT_REGISTERED: u8 = 4;
+
+/// Rejects bridge sends at pool validation that would fail on-chain
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T, I))]
+pub struct CheckBridgeAvailable<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T, I> CheckBridgeAvailable<T, I> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T, I> Default for CheckBridgeAvailable<T, I> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T, I> sp_std::fmt::Debug for CheckBridgeAvailable<T, I> {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckBridgeAvailable")
+	}
+}
+
+impl<T: Config<I>, I: 'static> CheckBridgeAvailable<T, I> {
+	/// The destination of a bridge send and the collections it moves items of, or `None` for
+	/// any other call
+	fn sent_collections(call: &Call<T, I>) -> Option<(u32, Vec<T::CollectionId>)> {
+		match call {
+			Call::send_nft { collection_id, dest_para_id, .. } |
+			Call::send_staged_nft { collection_id, dest_para_id, .. } |
+			Call::send_chunked_nft { collection_id, dest_para_id, .. } => {
+				Some((*dest_para_id, sp_std::vec![*collection_id]))
+			},
+			Call::send_nfts { items, dest_para_id, .. } => {
+				Some((*dest_para_id, items.iter().map(|item| item.collection_id).collect()))
+			},
+			_ => None,
+		}
+	}
+
+	/// Whether `dest_para_id` has a `DestinationConfig`, which opening a lane also sets, and no
+	/// lane that isn't active yet
+	fn destination_registered(dest_para_id: u32) -> bool {
+		DestinationConfigs::<T, I>::contains_key(dest_para_id) &&
+			Pallet::<T, I>::ensure_lane_active(dest_para_id).is_ok()
+	}
+}
+
+impl<T, I> SignedExtension for CheckBridgeAvailable<T, I>
+where
+	T: Config<I> + Send + Sync,
+	I: 'static + Send + Sync,
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo> + IsSubType<Call<T, I>>,
+{
+	const IDENTIFIER: &'static str = "CheckBridgeAvailable";
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		let Some((dest_para_id, collections)) = call.is_sub_type().and_then(Self::sent_collections)
+		else {
+			return Ok(ValidTransaction::default())
+		};
+
+		if BridgePaused::<T, I>::get() {
+			return Err(InvalidTransaction::Custom(BRIDGE_PAUSED).into())
+		}
+		if !Self::destination_registered(dest_para_id) {
+			return Err(InvalidTransaction::Custom(DESTINATION_NOT_REGISTERED).into())
+		}
+		if collections
+			.iter()
+			.any(|collection_id| Pallet::<T, I>::ensure_registered(*collection_id).is_err())
+		{
+			return Err(InvalidTransaction::Custom(COLLECTION_NOT_REGISTERED).into())
+		}
+		if Pallet::<T, I>::ensure_pending_capacity(who, collections.len() as u32).is_err() {
+			return Err(InvalidTransaction::Custom(TOO_MANY_PENDING).into())
+		}
+
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		self.validate(who, call, info, len).map(|_| ())
+	}
+}
@@ -0,0 +1,146 @@
+//! Provenance trails of bridged items
+//!
+//! Each item received here keeps in `ItemHistories` the chains it arrived from, oldest first.
+//! The trail travels with the item: relayers pass the one the source chain holds to
+//! `receive_nft`, which appends only the hop just made, so a trail going back and forth never
+//! lists a hop twice. A delivery without one extends the trail already stored here. The
+//! recipient holds `HistoryDepositPerHop` for each hop stored, if it can pay for them.
+//!
+//! A trail never outgrows `MaxHistoryHops`: once full, the oldest hop after the first makes room
+//! for the next. The owner may cut it down further with `compact_item_history`, to its first hop
+//! and the latest `KeepRecentHops`, getting the deposit of the dropped hops back. Dropped hops
+//! are counted in the trail's `elided`, which travels with it.
+
+use crate::*;
+use frame_support::{
+	traits::{fungible::MutateHold, tokens::Precision, Get, UnixTime},
+	BoundedVec,
+};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError,
+};
+
+/// A provenance trail as `receive_nft` carries it
+pub type ProvenanceTrailOf<T, I = ()> =
+	ProvenanceTrail<BoundedVec<ProvenanceHop, <T as Config<I>>::MaxHistoryHops>>;
+
+/// A trail as stored in `ItemHistories`
+pub type ItemHistoryOf<T, I = ()> = ItemHistory<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T, I>,
+	BoundedVec<ProvenanceHop, <T as Config<I>>::MaxHistoryHops>,
+>;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Record that an item arrived from `from_para_id` for `recipient`, extending `carried`,
+	/// the trail it had on the source, or else the one stored here
+	pub(crate) fn note_hop(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		from_para_id: u32,
+		recipient: &T::AccountId,
+		carried: Option<ProvenanceTrailOf<T, I>>,
+	) {
+		let previous = ItemHistories::<T, I>::take(collection_id, item_id);
+		let mut trail = match (carried, &previous) {
+			(Some(trail), _) => trail,
+			(None, Some(previous)) => previous.trail.clone(),
+			(None, None) => Default::default(),
+		};
+		if let Some(previous) = &previous {
+			Self::release_history_deposit(previous);
+		}
+		let hop = ProvenanceHop { from_para_id, received_at: T::UnixTime::now().as_secs() };
+		Self::push_hop(&mut trail, hop);
+
+		// The item is delivered all the same if its recipient can't pay for the trail
+		let deposit = Self::history_deposit(trail.hops.len());
+		let (depositor, deposit) =
+			match T::Currency::hold(&HoldReason::HistoryDeposit.into(), recipient, deposit) {
+				Ok(()) => (Some(recipient.clone()), deposit),
+				Err(_) => (None, Zero::zero()),
+			};
+		ItemHistories::<T, I>::insert(
+			collection_id,
+			item_id,
+			ItemHistory { trail, depositor, deposit },
+		);
+	}
+
+	/// Append `hop`, dropping the oldest hop after the first if the trail is full
+	fn push_hop(trail: &mut ProvenanceTrailOf<T, I>, hop: ProvenanceHop) {
+		if trail.hops.len() as u32 >= T::MaxHistoryHops::get() {
+			trail.elided = trail.elided.saturating_add(1);
+			// The first hop is kept whatever happens, so with room for one the new hop goes
+			if trail.hops.len() < 2 {
+				return
+			}
+			trail.hops.remove(1);
+		}
+		let _ = trail.hops.try_push(hop);
+	}
+
+	/// Cut the trail of an item `who` owns to its first hop and the latest `KeepRecentHops`.
+	/// Returns the trail's `elided` count and the deposit released.
+	pub(crate) fn do_compact_item_history(
+		who: &T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> Result<(u32, BalanceOf<T, I>), DispatchError> {
+		ensure!(Self::is_owner(collection_id, item_id, who), Error::<T, I>::NotOwner);
+		Self::ensure_not_bridge_locked(collection_id, item_id)?;
+		let mut history = ItemHistories::<T, I>::get(collection_id, item_id)
+			.ok_or(Error::<T, I>::NothingToCompact)?;
+		let keep_recent = T::KeepRecentHops::get() as usize;
+		let len = history.trail.hops.len();
+		ensure!(len > keep_recent.saturating_add(1), Error::<T, I>::NothingToCompact);
+
+		let mut hops = history.trail.hops.into_inner();
+		let recent = hops.split_off(len - keep_recent);
+		hops.truncate(1);
+		hops.extend(recent);
+		let dropped = (len - hops.len()) as u32;
+		history.trail.hops = BoundedVec::truncate_from(hops);
+		history.trail.elided = history.trail.elided.saturating_add(dropped);
+
+		let deposit = Self::history_deposit(history.trail.hops.len()).min(history.deposit);
+		let released = history.deposit.saturating_sub(deposit);
+		if let Some(depositor) = &history.depositor {
+			T::Currency::release(
+				&HoldReason::HistoryDeposit.into(),
+				depositor,
+				released,
+				Precision::Exact,
+			)?;
+		}
+		history.deposit = deposit;
+		let elided = history.trail.elided;
+		ItemHistories::<T, I>::insert(collection_id, item_id, history);
+
+		Ok((elided, released))
+	}
+
+	/// Drop the trail of an item that left or was burned, releasing its deposit
+	pub(crate) fn clear_item_history(collection_id: T::CollectionId, item_id: T::ItemId) {
+		if let Some(history) = ItemHistories::<T, I>::take(collection_id, item_id) {
+			Self::release_history_deposit(&history);
+		}
+	}
+
+	fn release_history_deposit(history: &ItemHistoryOf<T, I>) {
+		if let Some(depositor) = &history.depositor {
+			let _ = T::Currency::release(
+				&HoldReason::HistoryDeposit.into(),
+				depositor,
+				history.deposit,
+				Precision::BestEffort,
+			);
+		}
+	}
+
+	/// Deposit for a trail of `hops` hops
+	fn history_deposit(hops: usize) -> BalanceOf<T, I> {
+		T::HistoryDepositPerHop::get().saturating_mul((hops as u32).into())
+	}
+}
@@ -0,0 +1,81 @@
+//! Lanes to partner chains
+//!
+//! `open_lane` configures a partner in one call and sends it a `confirm_lane` handshake. A lane
+//! turns `Active` when the partner's own handshake arrives, which it sends when its governance
+//! opens the lane on its side; the side activated by it answers once, so both ends end up
+//! `Active` whichever opens first. Transfers to a chain with a lane need it `Active`, and an
+//! `Active` lane lets the partner deliver NFTs here even if it isn't in `TrustedSources`. Chains
+//! without a lane keep working as configured by hand.
+
+use crate::*;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	pub(crate) fn do_open_lane(para_id: u32, config: LaneConfig) -> DispatchResult {
+		ensure!(!Lanes::<T, I>::contains_key(para_id), Error::<T, I>::LaneAlreadyOpen);
+
+		DestinationConfigs::<T, I>::insert(para_id, config.destination);
+		PartnerProtocolVersion::<T, I>::set(para_id, config.protocol_version);
+		DestinationXcmVersion::<T, I>::set(para_id, config.xcm_version);
+		RouteTable::<T, I>::set(para_id, config.reserve);
+		let lane = Lane { status: LaneStatus::Pending, capabilities: config.capabilities };
+		Lanes::<T, I>::insert(para_id, lane);
+
+		Self::send_bridge_call(para_id, Call::confirm_lane { capabilities: config.capabilities })?;
+		Self::deposit_event(Event::LaneOpened { para_id });
+
+		Ok(())
+	}
+
+	pub(crate) fn do_confirm_lane(para_id: u32, remote: LaneCapabilities) -> DispatchResult {
+		let lane = Lanes::<T, I>::get(para_id).ok_or(Error::<T, I>::LaneNotFound)?;
+		// Already agreed; answering again would bounce handshakes between the chains forever
+		if lane.status == LaneStatus::Active {
+			return Ok(())
+		}
+
+		let capabilities = lane.capabilities.intersect(remote);
+		Lanes::<T, I>::insert(para_id, Lane { status: LaneStatus::Active, capabilities });
+		// The partner may have opened its side before ours existed and dropped our handshake
+		Self::send_bridge_call(para_id, Call::confirm_lane { capabilities: lane.capabilities })?;
+		Self::deposit_event(Event::LaneActivated { para_id, capabilities });
+
+		Ok(())
+	}
+
+	pub(crate) fn do_close_lane(para_id: u32) -> DispatchResult {
+		ensure!(Lanes::<T, I>::contains_key(para_id), Error::<T, I>::LaneNotFound);
+		ensure!(
+			PendingByDestination::<T, I>::iter_prefix(para_id).next().is_none() &&
+				OutboundQueue::<T, I>::get(para_id).is_empty(),
+			Error::<T, I>::LaneInUse
+		);
+
+		Lanes::<T, I>::remove(para_id);
+		DestinationConfigs::<T, I>::remove(para_id);
+		PartnerProtocolVersion::<T, I>::remove(para_id);
+		DestinationXcmVersion::<T, I>::remove(para_id);
+		RouteTable::<T, I>::remove(para_id);
+		Self::deposit_event(Event::LaneClosed { para_id });
+
+		Ok(())
+	}
+
+	/// Refuse transfers to a chain whose lane is still waiting for the partner's handshake
+	pub(crate) fn ensure_lane_active(dest_para_id: u32) -> DispatchResult {
+		ensure!(
+			Lanes::<T, I>::get(dest_para_id).map_or(true, |lane| lane.status == LaneStatus::Active),
+			Error::<T, I>::LaneNotActive
+		);
+		Ok(())
+	}
+
+	/// Whether the lane to `para_id` carries `capability`. Chains without a lane carry all.
+	pub(crate) fn lane_allows(para_id: u32, capability: fn(&LaneCapabilities) -> bool) -> bool {
+		Lanes::<T, I>::get(para_id).map_or(true, |lane| capability(&lane.capabilities))
+	}
+
+	/// Whether the partner has an `Active` lane to this chain
+	pub(crate) fn is_lane_active(para_id: u32) -> bool {
+		Lanes::<T, I>::get(para_id).map_or(false, |lane| lane.status == LaneStatus::Active)
+	}
+}
@@ -2,60 +2,409 @@
 
 /// A pallet to enable cross-chain NFT transfers using XCM
 pub use pallet::*;
+pub use assets::{NftAssetConverter, NftTransactor};
+pub use authorities::EnsureBridgeAuthority;
+pub use traits::*;
+pub use types::*;
+pub use chunks::{MetadataAssemblyOf, MetadataChunkOf};
+pub use export::CollectionExportOf;
+pub use extension::CheckBridgeAvailable;
+pub use history::{ItemHistoryOf, ProvenanceTrailOf};
+pub use ownership::{OwnershipQueryOf, RemoteOwnerOf};
+pub use snapshot::{BridgeStateEntryOf, MAX_EXPORT_STATE_PAGE};
+pub use staging::StagedMetadataOf;
+pub use swap::SwapOfferOf;
+pub use weights::WeightInfo;
 
+mod assets;
+mod authorities;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod canonical;
+mod chunks;
+mod escrow;
+mod export;
+mod extension;
+mod history;
+mod lanes;
+pub mod migrations;
+mod offchain;
+mod rejections;
+mod ownership;
+mod royalties;
+mod snapshot;
+mod staging;
+mod stats;
+mod supply;
+mod swap;
+pub mod traits;
+pub mod types;
+pub mod weights;
 pub mod xcm_handler;
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod network_tests;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::{
 		dispatch::DispatchResult,
 		pallet_prelude::*,
-		traits::{tokens::nonfungibles::Inspect, Get},
+		storage::unhashed,
+		traits::{
+			fungible::{self, Mutate as _},
+			tokens::{
+				nonfungibles::{Inspect, Mutate, Transfer},
+				Preservation,
+			},
+			Contains, Get, Incrementable, UnixTime,
+		},
 		PalletId,
 	};
-	use frame_system::pallet_prelude::*;
+	use frame_system::{offchain::SendTransactionTypes, pallet_prelude::*};
+	use sp_runtime::traits::{AccountIdConversion, Convert, Saturating, Zero};
 	use sp_std::vec::Vec;
-	use xcm::v3::{prelude::*, MultiLocation, SendXcm};
+	use xcm::{v3::{prelude::*, MultiLocation}, Version as XcmVersion};
 	use xcm_executor::traits::TransactAsset;
 
+	use crate::{traits::*, types::*, weights::WeightInfo, xcm_handler::MAX_MESSAGES_PER_TRANSFER};
+
+	/// Balance of the currency bridging fees are paid in
+	pub type BalanceOf<T, I = ()> = <<T as Config<I>>::Currency as fungible::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	/// An item's metadata URIs as stored in `MetadataUris`
+	pub type MetadataUrisOf<T, I = ()> = BoundedVec<
+		(MediaKind, BoundedVec<u8, <T as Config<I>>::MaxUriLen>),
+		<T as Config<I>>::MaxUrisPerItem,
+	>;
+
+	/// An outbound message as stored in `QueuedSends` and `FailedSends`: its SCALE encoding in
+	/// the latest XCM version
+	pub type EncodedXcmOf<T, I = ()> = BoundedVec<u8, <T as Config<I>>::MaxOutboundMessageSize>;
+
+	/// A failed inbound delivery as stored in `RecentInboundFailures`
+	pub type InboundFailureOf<T, I = ()> = InboundFailure<
+		<T as Config<I>>::CollectionId,
+		<T as Config<I>>::ItemId,
+		BlockNumberFor<T>,
+	>;
+
+	/// The in-code storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	pub struct Pallet<T>(_);
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T, I = ()>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
-		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	pub trait Config<I: 'static = ()>: frame_system::Config + SendTransactionTypes<Call<Self, I>> {
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// The NFT collection ID type
-		type CollectionId: Parameter + Member + Copy + MaybeSerializeDeserialize + Debug;
+		type CollectionId: Parameter
+			+ Member
+			+ Copy
+			+ MaybeSerializeDeserialize
+			+ Debug
+			+ PartialOrd
+			+ Incrementable;
 		/// The NFT ID type
 		type ItemId: Parameter + Member + Copy + MaybeSerializeDeserialize + Debug;
-		/// The origin that is allowed to send cross-chain messages
-		type SendOrigin: EnsureOrigin<Self::RuntimeOrigin>;
-		/// The XCM message sender
-		type XcmSender: SendXcm;
-		/// The asset transactor to handle NFT operations
+		/// The origin that may initiate outbound transfers, resolving to the sending account.
+		/// Usually `EnsureSigned`.
+		type SendOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+		/// The origin that delivers inbound bridge messages, resolving to the sending location.
+		/// Intended to be `EnsureXcm<IsSiblingParachain>`.
+		type ReceiveOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+		/// The origin allowed to perform administrative operations (pausing, whitelists and
+		/// forced recovery)
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// How NFTs are held while their outbound transfer is pending
+		#[pallet::constant]
+		type LockStrategy: Get<LockStrategy>;
+		/// Parachains this chain accepts inbound NFTs from
+		type TrustedSources: Contains<u32>;
+		/// The XCM message sender, given each message in the destination's XCM version. Wrap a
+		/// `SendXcm` router in `LatestXcmSender` if it negotiates versions itself.
+		type XcmSender: SendVersionedXcm;
+		/// Assembles the XCM program for outbound transfers, usually `ReserveTransferProgram`
+		type ProgramBuilder: BuildNftTransferProgram<Self, I>;
+		/// Where this chain's NFT assets are anchored, relative to this chain. Asset ids are
+		/// `prefix / GeneralIndex(collection)` and partner chains must match them with the same
+		/// prefix. Leave it `Here` to fall back to `PalletInstance(<this pallet's index>)`, which
+		/// changes if the runtime reorders its pallets.
+		type NftAssetPrefix: Get<InteriorMultiLocation>;
+		/// This chain's parachain id, the origin of the canonical ids of native items
+		#[pallet::constant]
+		type SelfParaId: Get<u32>;
+		/// The network this chain and its bridge partners belong to
+		#[pallet::constant]
+		type RelayNetwork: Get<NetworkId>;
+		/// Converts a local account into the beneficiary location on the destination
+		type AccountIdToLocation: Convert<Self::AccountId, MultiLocation>;
+		/// Resolves a location to its account here, sibling chains to their sovereign accounts
+		type LocationToAccountId: xcm_executor::traits::Convert<MultiLocation, Self::AccountId>;
+		/// Moves NFTs in the runtime's asset stack: locked items are withdrawn from their owner
+		/// and received or unlocked items deposited. Assets it doesn't handle stay in the pallet's
+		/// own bookkeeping only.
 		type AssetTransactor: TransactAsset;
+		/// The runtime's NFT pallet. Where it knows an item, its owner is authoritative and
+		/// `NFTOwners` must agree before the item can be sent. Received wrappers are minted into
+		/// it, unless `AssetTransactor` already deposited them. Use `NoNftBackend` if `NFTOwners`
+		/// is the only record.
+		type Nfts: Inspect<
+				Self::AccountId,
+				CollectionId = Self::CollectionId,
+				ItemId = Self::ItemId,
+			> + Mutate<Self::AccountId>
+			+ Transfer<Self::AccountId>;
 		/// The pallet ID for this pallet
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+		/// Whether senders may attach a call to be dispatched on the destination after delivery
+		#[pallet::constant]
+		type AllowRemoteCalls: Get<bool>;
+		/// Maximum encoded length of a call attached to a transfer
+		#[pallet::constant]
+		type MaxRemoteCallLen: Get<u32>;
+		/// Weight reserved on the destination for executing an attached call
+		#[pallet::constant]
+		type RemoteCallWeight: Get<Weight>;
+		/// Age in blocks after which a pending transfer is reported as stale
+		#[pallet::constant]
+		type StaleThreshold: Get<BlockNumberFor<Self>>;
+		/// Maximum number of pending transfers the offchain worker inspects per block
+		#[pallet::constant]
+		type MaxStaleScan: Get<u32>;
+		/// Priority of unsigned stale-transfer reports
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// Base delay in blocks between automatic re-sends; attempt `n` waits `n^2` times this
+		#[pallet::constant]
+		type RetryBackoff: Get<BlockNumberFor<Self>>;
+		/// Number of failed re-sends after which a transfer is abandoned and the NFT unlocked
+		#[pallet::constant]
+		type MaxRetries: Get<u32>;
+		/// Blocks after which an unsettled transfer whose message can't arrive any more is
+		/// unlocked back to its sender
+		#[pallet::constant]
+		type TransferTimeout: Get<BlockNumberFor<Self>>;
+		/// Blocks after which a still pending transfer is reported with `TransferAging`
+		#[pallet::constant]
+		type WarnAfter: Get<BlockNumberFor<Self>>;
+		/// Blocks an offer received from another chain stays open, and an accepted offer waits
+		/// for the proposing chain to settle before its escrow is released
+		#[pallet::constant]
+		type SwapAcceptTimeout: Get<BlockNumberFor<Self>>;
+		/// Maximum number of transfers waiting in a single destination's outbound queue
+		#[pallet::constant]
+		type MaxOutboundQueue: Get<u32>;
+		/// Maximum number of outbound transfers an account may have pending at once
+		#[pallet::constant]
+		type MaxPendingPerAccount: Get<u32>;
+		/// Number of recent delivery ids remembered per source chain to ignore retried deliveries
+		#[pallet::constant]
+		type DeliveryDedupWindow: Get<u32>;
+		/// Maximum number of accounts in `BridgeAuthorities`
+		#[pallet::constant]
+		type MaxAuthorities: Get<u32>;
+		/// Number of failed inbound deliveries remembered per source chain
+		#[pallet::constant]
+		type MaxFailureRecords: Get<u32>;
+		/// Maximum number of NFTs in a single batch send
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// Default cap on metadata and URI bytes stored for a single collection
+		#[pallet::constant]
+		type MaxCollectionMetadataBytes: Get<u64>;
+		/// Weight limit bought on destinations without their own configured limit
+		#[pallet::constant]
+		type DefaultWeightLimit: Get<Weight>;
+		/// Minimum number of blocks between two metadata syncs of the same item
+		#[pallet::constant]
+		type MetadataSyncPeriod: Get<BlockNumberFor<Self>>;
+		/// Maximum number of subscribed chains messaged directly by a metadata update. Further
+		/// chains are messaged from `on_idle`.
+		#[pallet::constant]
+		type MaxSubscribersPerUpdate: Get<u32>;
+		/// Whether collections that were never registered may use the bridge
+		#[pallet::constant]
+		type PermissionlessCollections: Get<bool>;
+		/// Whether the first item received for an unregistered collection registers it as a
+		/// wrapped collection instead of being refused
+		#[pallet::constant]
+		type AutoRegisterWrappedCollections: Get<bool>;
+		/// Inclusive range of local ids given to auto-registered wrapped collections, skipping
+		/// ids already used in `Nfts`. `None` keeps the source chain's collection id.
+		#[pallet::constant]
+		type WrappedCollectionRange: Get<Option<(Self::CollectionId, Self::CollectionId)>>;
+		/// Largest number of items a collection export may send per block
+		#[pallet::constant]
+		type MaxExportBatchSize: Get<u32>;
+		/// Blocks after which unsent staged metadata may be purged by anyone
+		#[pallet::constant]
+		type StagingTimeout: Get<BlockNumberFor<Self>>;
+		/// Deposit held per byte of staged metadata and URI until it is sent or purged
+		#[pallet::constant]
+		type StagingDepositPerByte: Get<BalanceOf<Self, I>>;
+		/// Blocks a metadata request waits for its response before it lapses
+		#[pallet::constant]
+		type MetadataRequestTimeout: Get<BlockNumberFor<Self>>;
+		/// Blocks an ownership query waits for its answer
+		#[pallet::constant]
+		type QueryTimeout: Get<BlockNumberFor<Self>>;
+		/// Deposit held from a signed account asking an ownership query until it is answered or
+		/// purged
+		#[pallet::constant]
+		type OwnershipQueryDeposit: Get<BalanceOf<Self, I>>;
+		/// Most ownership queries that may await an answer at once
+		#[pallet::constant]
+		type MaxOwnershipQueries: Get<u32>;
+		/// Most hops an item's provenance trail keeps. A full trail drops its oldest hop after
+		/// the first.
+		#[pallet::constant]
+		type MaxHistoryHops: Get<u32>;
+		/// Latest hops `compact_item_history` keeps besides an item's first
+		#[pallet::constant]
+		type KeepRecentHops: Get<u32>;
+		/// Deposit held per hop of an item's provenance trail from the account it was received
+		/// for
+		#[pallet::constant]
+		type HistoryDepositPerHop: Get<BalanceOf<Self, I>>;
+		/// Largest piece of metadata carried by one `metadata_chunk` message
+		#[pallet::constant]
+		type ChunkSize: Get<u32>;
+		/// Largest number of chunks a chunked metadata transfer may be split into
+		#[pallet::constant]
+		type MaxMetadataChunks: Get<u32>;
+		/// Blocks after which an incomplete metadata assembly may be purged
+		#[pallet::constant]
+		type ReassemblyTimeout: Get<BlockNumberFor<Self>>;
+		/// Most attributes written to the NFT backend for one item
+		#[pallet::constant]
+		type MaxAttributes: Get<u32>;
+		/// Longest attribute value written to the NFT backend. At least `MaxMetadataLen` and
+		/// `MaxUriLen`, since received metadata and URIs are written as attributes.
+		#[pallet::constant]
+		type MaxAttributeLen: Get<u32>;
+		/// Ceiling on the metadata length governance may allow, and its initial limit
+		#[pallet::constant]
+		type MaxMetadataLen: Get<u32>;
+		/// Ceiling on the metadata URI length governance may allow, and its initial limit
+		#[pallet::constant]
+		type MaxUriLen: Get<u32>;
+		/// Most metadata URIs stored for one item
+		#[pallet::constant]
+		type MaxUrisPerItem: Get<u32>;
+		/// Largest encoded outbound message, usually the HRMP channel's maximum message size
+		#[pallet::constant]
+		type MaxOutboundMessageSize: Get<u32>;
+		/// Amount of the relay chain's native token bought for execution on the destination
+		#[pallet::constant]
+		type RemoteExecutionFee: Get<u128>;
+		/// Most of the relay chain's native token a rejection report spends on execution at the
+		/// source chain. Zero sends reports unpaid.
+		#[pallet::constant]
+		type RejectionReportFee: Get<u128>;
+		/// Clock stamped on outbound payloads and checked against inbound ones
+		type UnixTime: UnixTime;
+		/// Oldest an inbound delivery may be, in seconds since its source sent it. Zero accepts
+		/// deliveries of any age.
+		#[pallet::constant]
+		type MaxInboundAge: Get<u64>;
+		/// Blocks between `PeriodStats` events. Zero emits none.
+		#[pallet::constant]
+		type StatsPeriod: Get<BlockNumberFor<Self>>;
+		/// Verifies storage proofs that inbound NFTs are locked on their source chain
+		type LockProofVerifier: VerifyLockProof<Self, I>;
+		/// Whether inbound NFTs must come with a lock proof
+		#[pallet::constant]
+		type RequireLockProof: Get<bool>;
+		/// Currency bridging fees are paid in and deposits are held in
+		type Currency: fungible::Mutate<Self::AccountId>
+			+ fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+		/// The overarching hold reason, which the bridge's `HoldReason` converts into
+		type RuntimeHoldReason: From<HoldReason>;
+		/// Fee charged for sending an NFT of a collection without its own fee policy
+		#[pallet::constant]
+		type BridgeFee: Get<BalanceOf<Self, I>>;
+		/// Extra fee charged for sending an NFT through the priority lane
+		#[pallet::constant]
+		type PriorityFee: Get<BalanceOf<Self, I>>;
+		/// In-flight slots above a destination's `max_in_flight` only priority transfers may use
+		#[pallet::constant]
+		type PriorityInFlightCap: Get<u32>;
+		/// Fee charged per byte of metadata and URI on top of the collection's base fee
+		#[pallet::constant]
+		type FeePerMetadataByte: Get<BalanceOf<Self, I>>;
+		/// Account bridging and delivery fees are paid to
+		type FeeDestination: Get<Self::AccountId>;
+		/// Converts the delivery fee the XCM transport asks for into `Currency`, or `None` if it
+		/// can't be paid in it
+		type FeeAssetConverter: Convert<MultiAssets, Option<BalanceOf<Self, I>>>;
+		/// Called after an NFT has been sent to another chain
+		type OnNftSent: OnNftBridged<Self, I>;
+		/// Runtime policy that may veto outbound transfers
+		type TransferFilter: EnsureNftTransfer<Self, I>;
+		/// Chooses the local id of foreign items wrapped here. `HashItemId` avoids collisions
+		/// with local items; `()` keeps the source chain's id.
+		type ItemIdGenerator: DeriveItemId<Self, I>;
+		/// Called after an NFT has been received from another chain
+		type OnNftReceived: OnNftBridged<Self, I>;
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Why the bridge holds funds on an account
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Deposit for metadata staged with `stage_metadata`
+		#[codec(index = 0)]
+		MetadataDeposit,
+		/// Deposit for an outbound transfer; not taken by any call yet
+		#[codec(index = 1)]
+		TransferDeposit,
+		/// Reward set aside for the relayer of a transfer; not taken by any call yet
+		#[codec(index = 2)]
+		RelayerReward,
+		/// Deposit for an ownership query asked with `query_remote_ownership`
+		#[codec(index = 3)]
+		OwnershipQueryDeposit,
+		/// Deposit for the provenance trail of a received item
+		#[codec(index = 4)]
+		HistoryDeposit,
 	}
 
+	/// Outbound transfers move `Owned -> Pending -> (Confirmed | Unlocked)`. `NFTSent` starts the
+	/// pending state once the message was handed to the transport; a transfer that is held back
+	/// starts it with `TransferQueued` or `TransferSendFailed` instead. Exactly one of
+	/// `TransferConfirmed`, `TransferCancelled`, `TransferBounced`, `TransferAbandoned` and
+	/// `TransferExpired` ends it, once per item, so indexers can treat any of them as the
+	/// transfer's final event.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// An NFT has been sent for cross-chain transfer
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// An NFT has been sent for cross-chain transfer. `Owned -> Pending`
 		NFTSent {
 			collection_id: T::CollectionId,
 			item_id: T::ItemId,
 			dest_para_id: u32,
+			fee: BalanceOf<T, I>,
+			/// Transport fee paid for the message
+			delivery_fee: BalanceOf<T, I>,
+			canonical_id: CanonicalId,
 		},
 		/// An NFT has been received from another chain
 		NFTReceived {
 			collection_id: T::CollectionId,
 			item_id: T::ItemId,
 			from_para_id: u32,
+			canonical_id: CanonicalId,
 		},
 		/// An NFT transfer has been completed
 		NFTTransferCompleted {
@@ -64,167 +413,3952 @@ pub mod pallet {
 			from_para_id: u32,
 			to_para_id: u32,
 		},
-	}
-
-	#[pallet::error]
-	pub enum Error<T> {
-		/// The NFT does not exist
-		NFTNotFound,
-		/// The NFT is not owned by the sender
-		NotOwner,
-		/// Failed to send XCM message
-		FailedToSendXCM,
-		/// Invalid destination parachain
-		InvalidDestination,
-		/// Metadata exceeds maximum length
-		MetadataTooLong,
-	}
-
-	#[pallet::storage]
-	#[pallet::getter(fn owner)]
-	/// Map of (collection_id, item_id) to owner
-	pub type NFTOwners<T: Config> = StorageDoubleMap<
-		_,
-		Blake2_128Concat,
-		T::CollectionId,
-		Blake2_128Concat,
-		T::ItemId,
-		T::AccountId,
-		OptionQuery,
-	>;
-
-	/// Storage to track pending cross-chain transfers
-	#[pallet::storage]
-	#[pallet::getter(fn pending_transfer)]
-	pub type PendingTransfers<T: Config> = StorageDoubleMap<
-		_,
-		Blake2_128Concat,
-		T::CollectionId,
-		Blake2_128Concat,
-		T::ItemId,
-		MultiLocation,
-		OptionQuery,
-	>;
-
-	/// Storage to preserve NFT metadata during transfers
-	#[pallet::storage]
-	#[pallet::getter(fn nft_metadata)]
-	pub type NFTMetadata<T: Config> = StorageDoubleMap<
-		_,
-		Blake2_128Concat,
-		T::CollectionId,
-		Blake2_128Concat,
-		T::ItemId,
-		Vec<u8>, // Raw metadata bytes
-		OptionQuery,
-	>;
-
-	/// Storage for NFT metadata URIs (for IPFS or other decentralized storage)
-	#[pallet::storage]
-	#[pallet::getter(fn nft_metadata_uri)]
-	pub type NFTMetadataUri<T: Config> = StorageDoubleMap<
-		_,
-		Blake2_128Concat,
-		T::CollectionId,
-		Blake2_128Concat,
-		T::ItemId,
-		Vec<u8>, // URI as bytes (e.g., IPFS hash)
-		OptionQuery,
-	>;
-
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Send an NFT to another parachain
-		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
-		pub fn send_nft(
-			origin: OriginFor<T>,
+		/// An NFT has been delivered by governance, bypassing inbound source checks
+		ForceReceived {
 			collection_id: T::CollectionId,
 			item_id: T::ItemId,
-			dest_para_id: u32,
-			metadata: Vec<u8>,
-			metadata_uri: Option<Vec<u8>>, // Optional URI for decentralized storage
-		) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
-			
-			// Call the XCM handler to process the transfer, with metadata preservation
-			Self::do_xcm_transfer_nft(sender, collection_id, item_id, dest_para_id, metadata, metadata_uri)
-		}
-
-		/// Receive an NFT from another parachain - typically called by XCM execution
-		#[pallet::call_index(1)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
-		pub fn receive_nft(
-			origin: OriginFor<T>,
+			from_para_id: u32,
+			owner: T::AccountId,
+			overwritten: bool,
+		},
+		/// An inbound NFT was refused and bounced back to its source chain
+		InboundRejected {
 			collection_id: T::CollectionId,
 			item_id: T::ItemId,
 			from_para_id: u32,
+			reason: RejectionReason,
+		},
+		/// A sent NFT was bounced back by the destination and unlocked to its owner.
+		/// `Pending -> Unlocked`
+		TransferBounced {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
 			owner: T::AccountId,
-			metadata: Vec<u8>,
-			metadata_uri: Option<Vec<u8>>, // Optional URI for decentralized storage
-		) -> DispatchResult {
-			// In a real implementation, this would likely be called by the XCM executor 
-			// with proper origin verification, or through a privileged function
-			T::SendOrigin::ensure_origin(origin)?;
-			
-			// Call internal function to process the receipt with metadata preservation
-			Self::do_receive_nft(collection_id, item_id, from_para_id, owner, metadata, metadata_uri)
-		}
-		
-		/// Lock an NFT for cross-chain transfer (internal function)
-		pub fn lock_nft(
+			reason: RejectionReason,
+		},
+		/// The destination refused a sent NFT and reported why; its sender may now cancel
+		NFTTransferRejected {
 			collection_id: T::CollectionId,
 			item_id: T::ItemId,
-			who: &T::AccountId,
-		) -> DispatchResult {
-			// Verify the sender owns the NFT
-			let owner = Self::owner(collection_id, item_id).ok_or(Error::<T>::NFTNotFound)?;
-			ensure!(&owner == who, Error::<T>::NotOwner);
-
-			// Lock the NFT by removing from active ownership but storing in pending transfers
-			NFTOwners::<T>::remove(collection_id, item_id);
-
-			// In a real implementation, we might store additional information about the lock
-			Ok(())
-		}
-		
-		/// Unlock an NFT after failed cross-chain transfer (internal function)
-		pub fn unlock_nft(
+			transfer_id: TransferId,
+			code: RejectionCode,
+		},
+		/// A refused inbound NFT was reported to the chain that sent it, under the id that chain
+		/// gave its transfer
+		RejectionReported {
 			collection_id: T::CollectionId,
 			item_id: T::ItemId,
-			recipient: &T::AccountId,
-		) -> DispatchResult {
-			// Check if this NFT is in pending transfer state
-			ensure!(PendingTransfers::<T>::contains_key(collection_id, item_id), Error::<T>::NFTNotFound);
-
-			// Restore ownership
-			NFTOwners::<T>::insert(collection_id, item_id, recipient.clone());
-
-			// Remove from pending transfers
-			PendingTransfers::<T>::remove(collection_id, item_id);
-
-			// Also clean up any associated metadata
-			NFTMetadata::<T>::remove(collection_id, item_id);
-			NFTMetadataUri::<T>::remove(collection_id, item_id);
-
-			Ok(())
-		}
-	}
-
-	// Implementation for handling NFT operations
-	impl<T: Config> Pallet<T> {
-		/// Check if an account owns a specific NFT
-		pub fn is_owner(collection_id: T::CollectionId, item_id: T::ItemId, who: &T::AccountId) -> bool {
-			if let Some(owner) = Self::owner(collection_id, item_id) {
-				&owner == who
-			} else {
-				false
-			}
-		}
-
-		/// Get the owner of an NFT
-		pub fn get_owner(collection_id: T::CollectionId, item_id: T::ItemId) -> Option<T::AccountId> {
-			Self::owner(collection_id, item_id)
-		}
+			from_para_id: u32,
+			transfer_id: TransferId,
+			code: RejectionCode,
+		},
+		/// Transfers sent, received, cancelled and expired during the `StatsPeriod` that just
+		/// ended, and those in flight at its end
+		PeriodStats { sent: u32, received: u32, cancelled: u32, expired: u32, pending: u32 },
+		/// A pending transfer was cancelled by its sender and the NFT returned.
+		/// `Pending -> Unlocked`
+		TransferCancelled {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+			owner: T::AccountId,
+		},
+		/// Sending a transfer's message failed at `stage`; it will be retried automatically
+		TransferSendFailed {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+			next_attempt: BlockNumberFor<T>,
+			stage: SendStage,
+		},
+		/// A previously failed transfer message was re-sent successfully
+		TransferResent {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+			retry_count: u32,
+		},
+		/// A transfer was given up after `MaxRetries` failed re-sends and the NFT unlocked.
+		/// `Pending -> Unlocked`
+		TransferAbandoned {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+		},
+		/// The destination's in-flight cap was reached and the transfer was queued
+		TransferQueued {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+			dest_para_id: u32,
+		},
+		/// A queued transfer was taken off its destination's queue and sent
+		TransferDequeued {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+			dest_para_id: u32,
+		},
+		/// A pending transfer has gone unacknowledged for at least `StaleThreshold` blocks. The
+		/// transfer stays pending.
+		TransferStale {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+			age: BlockNumberFor<T>,
+		},
+		/// A pending transfer reached `TransferTimeout` without being settled and the NFT was
+		/// returned to its sender by the expiry sweep. `Pending -> Unlocked`
+		TransferExpired {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+			sender: T::AccountId,
+		},
+		/// A transfer has been pending for `WarnAfter` blocks or more. Emitted once per transfer.
+		TransferAging {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			age: BlockNumberFor<T>,
+		},
+		/// A batch send finished; `skipped` lists the items best-effort mode left behind
+		BatchSendResult {
+			dest_para_id: u32,
+			sent: Vec<(T::CollectionId, T::ItemId)>,
+			skipped: Vec<((T::CollectionId, T::ItemId), DispatchError)>,
+		},
+		/// The configuration of a destination was changed
+		DestinationConfigSet {
+			dest_para_id: u32,
+			config: Option<DestinationConfig>,
+		},
+		/// The `BridgeMessage` version used for a partner chain was changed
+		PartnerVersionSet {
+			para_id: u32,
+			version: Option<u32>,
+		},
+		/// A collection admin set or removed an item's royalty
+		RoyaltySet {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			royalty: Option<RoyaltyInfo<T::AccountId>>,
+		},
+		/// An item changed hands on this chain, declaring `sale_price` if it was sold
+		Transferred {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			from: T::AccountId,
+			to: T::AccountId,
+			sale_price: Option<BalanceOf<T, I>>,
+		},
+		/// The royalty of a priced transfer was paid, or escrowed for a remote beneficiary
+		RoyaltyPaid {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			beneficiary: RoyaltyBeneficiary<T::AccountId>,
+			amount: BalanceOf<T, I>,
+		},
+		/// The NFT backend burned an item and the bridge dropped its records. `origin_para` is
+		/// the chain a wrapper came from, which was `notified` of the burn if the message went
+		/// out.
+		ItemBurned {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			origin_para: Option<u32>,
+			notified: bool,
+		},
+		/// The wrapper of an item that left here was burned on `para_id`; the item can't return
+		OriginalOrphaned { collection_id: T::CollectionId, item_id: T::ItemId, para_id: u32 },
+		/// `para_id` was asked who owns its item `collection_id`/`item_id`
+		OwnershipQueried {
+			query_id: QueryId,
+			para_id: u32,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		},
+		/// `para_id` reported who owns its item, `None` if it has no such item
+		RemoteOwnershipReported {
+			para_id: u32,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			owner: Option<T::AccountId>,
+		},
+		/// An ownership query lapsed without an answer and was removed
+		OwnershipQueryPurged { query_id: QueryId },
+		/// A remote beneficiary claimed its escrowed royalties
+		RoyaltiesClaimed { beneficiary: MultiLocation, to: T::AccountId, amount: BalanceOf<T, I> },
+		/// The metadata size limits were changed
+		MetadataLimitsUpdated { limits: MetadataSizeLimits },
+		/// Governance removed a pending transfer no settlement path could clear. `transfer_id`
+		/// is `None` if the record didn't decode. `Pending -> Unlocked` or dropped.
+		PendingKilled {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: Option<TransferId>,
+			refund_to: Option<T::AccountId>,
+			reason_hash: T::Hash,
+		},
+		/// The XCM version used for messages to a destination was changed
+		DestinationXcmVersionSet {
+			dest_para_id: u32,
+			version: Option<XcmVersion>,
+		},
+		/// An account opted in to or out of receiving bridged NFTs
+		InboundOptOutSet {
+			who: T::AccountId,
+			opted_out: bool,
+		},
+		/// The destination confirmed an outbound transfer. `Pending -> Confirmed`
+		TransferConfirmed {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+			dest_para_id: u32,
+		},
+		/// Metadata of items that no longer live on this chain was removed
+		MetadataPurged {
+			count: u32,
+		},
+		/// The audit records of settled transfers were removed
+		TransferRecordsPruned {
+			count: u32,
+		},
+		/// The intermediate hop used to reach a destination was set or cleared
+		RouteSet {
+			dest_para_id: u32,
+			via: Option<u32>,
+		},
+		/// The metadata byte quota of a collection was set or reset to the default
+		CollectionMetadataQuotaSet {
+			collection_id: T::CollectionId,
+			quota: Option<u64>,
+		},
+		/// An NFT was escrowed and offered in a swap to another chain
+		SwapProposed {
+			swap_id: SwapId,
+			proposer: T::AccountId,
+			their_para: u32,
+		},
+		/// Another chain offered a swap for an NFT on this chain
+		SwapOffered {
+			from_para_id: u32,
+			swap_id: SwapId,
+			their_owner: T::AccountId,
+		},
+		/// A swap offered by another chain was accepted and the requested NFT escrowed
+		SwapAccepted {
+			from_para_id: u32,
+			swap_id: SwapId,
+		},
+		/// A swap proposed here was accepted in time and the offered NFT sent to the counterpart
+		SwapCompleted {
+			swap_id: SwapId,
+		},
+		/// The proposing chain settled a swap and the requested NFT was sent to the proposer
+		SwapSettled {
+			from_para_id: u32,
+			swap_id: SwapId,
+		},
+		/// The fee policy of a collection was set. `FeePolicy::Default` removes the override.
+		CollectionFeePolicySet {
+			collection_id: T::CollectionId,
+			policy: FeePolicy<BalanceOf<T, I>>,
+		},
+		/// A collection was onboarded to the bridge
+		CollectionRegistered {
+			collection_id: T::CollectionId,
+			reserve: Option<u32>,
+			admin: T::AccountId,
+		},
+		/// A collection was registered on receipt of its first item from another chain
+		WrappedCollectionCreated {
+			local: T::CollectionId,
+			remote_para: u32,
+			remote_collection: T::CollectionId,
+		},
+		/// A wrapper was given a local id different from its id on the source chain
+		ItemRemapped {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			from_para_id: u32,
+			remote_item_id: T::ItemId,
+		},
+		/// A collection was removed from the bridge
+		CollectionDeregistered { collection_id: T::CollectionId },
+		/// An item held in a destination's escrow has neither a pending transfer to nor a
+		/// confirmed presence on that destination
+		EscrowDiscrepancy {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+		},
+		/// Metadata was staged for an item ahead of its transfer
+		MetadataStaged {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			metadata_hash: T::Hash,
+		},
+		/// An item was sent with staged metadata; the destination receives only its hash
+		StagedNftSent {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+			metadata_hash: T::Hash,
+		},
+		/// Expired staged metadata was removed and its deposit refunded
+		StagedMetadataPurged {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		},
+		/// A wrapper's metadata was requested from the chain it came from
+		MetadataRequested {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			from_para_id: u32,
+		},
+		/// An item's metadata was sent to a chain holding a wrapper of it
+		MetadataServed {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+		},
+		/// Requested metadata arrived, matched its hash and was stored
+		MetadataFetched {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		},
+		/// An item was sent with its metadata following in chunks
+		MetadataChunksSent {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+			total: u32,
+		},
+		/// Every chunk of a wrapper's metadata arrived and the assembled blob was stored
+		MetadataAssembled {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			from_para_id: u32,
+		},
+		/// A complete assembly did not match its announced hash or item and was discarded
+		MetadataAssemblyFailed {
+			from_para_id: u32,
+			transfer_id: TransferId,
+		},
+		/// An expired metadata assembly was removed with its chunks
+		MetadataAssemblyPurged {
+			from_para_id: u32,
+			transfer_id: TransferId,
+		},
+		/// The bridge's owner record was refreshed from the NFT backend
+		NftImported {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			owner: T::AccountId,
+		},
+		/// `NFTOwners` and the NFT backend name different owners for an item
+		OwnershipMismatch {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			bridge_owner: T::AccountId,
+			backend_owner: T::AccountId,
+		},
+		/// A consistency check over a collection finished. Pass `next` as `start_after` to
+		/// continue; `None` means the collection was covered.
+		ConsistencyChecked {
+			collection_id: T::CollectionId,
+			mismatches: u32,
+			next: Option<T::ItemId>,
+		},
+		/// A reconciliation pass over a destination's escrow finished
+		EscrowReconciled {
+			dest_para_id: u32,
+			discrepancies: u32,
+		},
+		/// The account managing a registered collection's bridge settings changed
+		CollectionAdminSet {
+			collection_id: T::CollectionId,
+			admin: T::AccountId,
+		},
+		/// A collection's reserve chain was changed by its admin or `AdminOrigin`
+		CollectionReserveSet {
+			collection_id: T::CollectionId,
+			reserve: Option<u32>,
+		},
+		/// An item's metadata was pushed to a chain holding a wrapper of it
+		MetadataSynced {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+		},
+		/// A metadata update was sent to a subscribed chain
+		MetadataSyncSent {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+		},
+		/// A metadata update reaches the remaining subscribed chains from `on_idle`
+		MetadataSyncQueued {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		},
+		/// A chain no longer receives a collection's metadata updates
+		MetadataSubscriberRemoved {
+			collection_id: T::CollectionId,
+			para_id: u32,
+		},
+		/// A wrapper's metadata was updated by the chain the item came from
+		MetadataUpdated {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			from_para_id: u32,
+		},
+		/// A collection was made non-transferable, or transferable again with `None`
+		CollectionTransferabilitySet {
+			collection_id: T::CollectionId,
+			restriction: Option<TransferRestriction>,
+		},
+		/// A collection started migrating to another chain
+		CollectionExportStarted {
+			collection_id: T::CollectionId,
+			dest_para_id: u32,
+		},
+		/// A batch of a collection export was processed
+		CollectionExportProgress {
+			collection_id: T::CollectionId,
+			exported: u32,
+			skipped: u32,
+		},
+		/// Every item of a migrating collection was examined and the export finished
+		CollectionExported {
+			collection_id: T::CollectionId,
+			dest_para_id: u32,
+			exported: u32,
+			skipped: u32,
+		},
+		/// A collection export was stopped and its unconfirmed items returned to their owners
+		CollectionExportAborted {
+			collection_id: T::CollectionId,
+			restored: u32,
+		},
+		/// A party withdrew from a swap. `from_para_id` is `None` for swaps proposed here.
+		SwapCancelled {
+			from_para_id: Option<u32>,
+			swap_id: SwapId,
+		},
+		/// A swap lapsed and its escrow was released. `from_para_id` is `None` for swaps proposed
+		/// here.
+		SwapExpired {
+			from_para_id: Option<u32>,
+			swap_id: SwapId,
+		},
+		/// Part of a collection's residual bridge data was removed; more remains
+		CollectionDataClearing {
+			collection_id: T::CollectionId,
+			removed: u32,
+		},
+		/// The last of a collection's residual bridge data was removed
+		CollectionDataCleared {
+			collection_id: T::CollectionId,
+			removed: u32,
+		},
+		/// Outbound sends were paused or resumed
+		BridgePauseSet { paused: bool },
+		/// An inbound NFT's beneficiary couldn't be resolved, so the NFT went to the sovereign
+		/// account of its source chain, or its collection had no supply left, so the NFT went
+		/// to the escrow account of its source chain
+		InboundOrphaned {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			from_para_id: u32,
+			beneficiary: MultiLocation,
+		},
+		/// A lane to a partner chain was opened and the handshake sent
+		LaneOpened { para_id: u32 },
+		/// The partner's handshake arrived and the lane may carry transfers
+		LaneActivated { para_id: u32, capabilities: LaneCapabilities },
+		/// A lane was closed and the partner's configuration removed
+		LaneClosed { para_id: u32 },
+		/// An orphaned NFT was handed to its rightful owner
+		OrphanReassigned {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			to: T::AccountId,
+		},
+		/// A collection's maximum wrapped supply was changed
+		CollectionMaxSupplySet { collection_id: T::CollectionId, max_supply: Option<u32> },
+		/// An account joined the bridge authorities
+		AuthorityAdded { who: T::AccountId },
+		/// An account left the bridge authorities
+		AuthorityRemoved { who: T::AccountId },
+		/// The failed inbound deliveries recorded for a source chain were cleared
+		InboundFailuresCleared { para_id: u32 },
+		/// Another pallet took an NFT into escrow
+		ItemEscrowed {
+			escrow_id: EscrowId,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			owner: T::AccountId,
+			reason: EscrowReason,
+		},
+		/// An escrow ended and its NFT went to `to`
+		EscrowReleased {
+			escrow_id: EscrowId,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			to: T::AccountId,
+		},
+		/// Delivery fee counters were reset, for one destination or all of them if `None`
+		DeliveryFeeSpendReset { dest_para_id: Option<u32> },
+		/// A delivery already processed under the same id arrived again and was skipped
+		DuplicateDeliveryIgnored {
+			from_para_id: u32,
+			delivery_id: [u8; 32],
+		},
+		/// An inbound delivery was refused and recorded in `RecentInboundFailures`. The ids are
+		/// the ones the source chain sent; for a bundle, those of the item that failed it.
+		InboundDeliveryFailed {
+			from_para_id: u32,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			reason: DispatchError,
+		},
+		/// An item's provenance trail was cut down to its first and latest hops, releasing
+		/// `released` of its deposit. `elided` counts every hop dropped from it so far.
+		HistoryCompacted {
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			elided: u32,
+			released: BalanceOf<T, I>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The NFT does not exist in a collection known here.
+		///
+		/// An item moves through `Owned -> Pending -> (Delivered | Unlocked)`: sending locks it
+		/// and removes its owner entry, so a pending item has no owner but is not missing. That
+		/// case is reported as `TransferAlreadyPending` instead.
+		NFTNotFound,
+		/// The NFT is not owned by the sender
+		NotOwner,
+		/// Failed to send XCM message
+		FailedToSendXCM,
+		/// The transport accepted the XCM message but failed to deliver it
+		DeliveryFailed,
+		/// Invalid destination parachain
+		InvalidDestination,
+		/// Metadata exceeds maximum length
+		MetadataTooLong,
+		/// An inbound NFT's (collection, item) already has an owner on this chain
+		NFTAlreadyExists,
+		/// The delivering origin is not the parachain the message claims to come from
+		OriginMismatch,
+		/// The NFT is locked in a pending cross-chain transfer and cannot be sent, approved or
+		/// transferred until the transfer is delivered or unlocked
+		TransferAlreadyPending,
+		/// The transfer's message may still be delivered, so the NFT can't be unlocked yet
+		TransferInFlight,
+		/// Attaching remote calls to transfers is disabled on this chain
+		RemoteCallsDisabled,
+		/// The attached remote call exceeds the maximum length
+		RemoteCallTooLong,
+		/// The configured program builder could not build the transfer message
+		ProgramBuildFailed,
+		/// The transfer message cannot be expressed in the XCM version the destination speaks
+		UnsupportedXcmVersion,
+		/// Unlimited execution was requested for a destination that is not trusted
+		UnlimitedWeightNotAllowed,
+		/// The configured asset prefix leaves no room for the collection junction
+		InvalidAssetPrefix,
+		/// The configured `AssetTransactor` refused to move the NFT
+		AssetTransactorFailed,
+		/// No item of a best-effort batch could be sent
+		NothingSent,
+		/// The transfer is not pending, not stale yet, or has already been reported
+		NotStale,
+		/// The inbound NFT comes from a parachain that is not in `TrustedSources`
+		UntrustedSourceChain,
+		/// The inbound nonce is not greater than the last nonce accepted from the source chain
+		InvalidNonce,
+		/// The delivery was sent longer than `MaxInboundAge` ago
+		MessageTooOld,
+		/// The hash of the inbound metadata differs from the hash supplied with the message
+		MetadataHashMismatch,
+		/// The inbound NFT is pending an outbound transfer to a different chain than the one
+		/// delivering it, so the sender is not the NFT's reserve
+		WrongReserve,
+		/// The inbound NFT's recipient has opted out of receiving bridged NFTs
+		RecipientOptedOut,
+		/// The destination's outbound queue is full
+		OutboundQueueFull,
+		/// A bundle would take the destination over its in-flight cap; bundles are never queued
+		DestinationAtCapacity,
+		/// The inbound NFT came without a lock proof although one is required, or its proof
+		/// failed `LockProofVerifier`
+		InvalidLockProof,
+		/// Storing the metadata would take the collection over its metadata byte quota
+		CollectionMetadataQuotaExceeded,
+		/// The encoded transfer message exceeds the destination's maximum message size
+		MessageTooLarge,
+		/// The item's metadata was synced too recently
+		SyncRateLimited,
+		/// The XCM transport asks for a delivery fee that can't be paid in `Currency`
+		UnsupportedFeeAsset,
+		/// No such swap is known on this chain
+		SwapNotFound,
+		/// The caller is not the party to the swap allowed to do this
+		NotSwapParty,
+		/// The swap's deadline has passed
+		SwapDeadlinePassed,
+		/// The swap cannot be cancelled before its deadline or accept timeout
+		SwapDeadlineNotReached,
+		/// The swap offer was already received or accepted
+		SwapAlreadyAccepted,
+		/// The NFT is escrowed in a swap and cannot be sent or transferred
+		ItemInSwap,
+		/// The collection is being exported and its items cannot be sent individually
+		CollectionMigrating,
+		/// The collection is not being exported
+		ExportNotFound,
+		/// The collection's items may not be bridged
+		CollectionNonTransferable,
+		/// No item of the collection has ever been seen on this chain
+		CollectionNotFound,
+		/// The collection has not been registered with the bridge
+		CollectionNotRegistered,
+		/// The collection is already registered
+		CollectionAlreadyRegistered,
+		/// The collection still has pending transfers or wrappers outstanding
+		CollectionInUse,
+		/// The export batch size is zero or above `MaxExportBatchSize`
+		InvalidBatchSize,
+		/// The witness is below the number of items the export has sent
+		BadWitness,
+		/// No metadata is staged for the item
+		StagedMetadataNotFound,
+		/// The staged metadata has not reached `StagingTimeout` yet
+		StagingNotExpired,
+		/// The item has no metadata waiting to be fetched from its origin chain
+		NoMetadataToFetch,
+		/// A request for the item's metadata is already outstanding
+		MetadataRequestPending,
+		/// No outstanding request matches the metadata response, or it lapsed
+		MetadataRequestNotFound,
+		/// No open assembly matches the chunk, or it expired
+		MetadataAssemblyNotFound,
+		/// The chunk or header does not fit the announced assembly
+		InvalidChunk,
+		/// A chunk's data does not match its hash
+		ChunkHashMismatch,
+		/// The assembly has not reached `ReassemblyTimeout` yet
+		AssemblyNotExpired,
+		/// `NFTOwners` disagrees with the NFT backend about who owns the item; `import_nft`
+		/// refreshes it
+		OwnershipOutOfSync,
+		/// The NFT backend refused to mint or burn a wrapper, e.g. its collection is missing or
+		/// the item already exists there
+		BackendMintFailed,
+		/// An item carries more than `MaxAttributes` attributes
+		TooManyAttributes,
+		/// An attribute value is longer than `MaxAttributeLen`
+		AttributeTooLong,
+		/// The partner's `BridgeMessage` version can't express this transfer
+		IncompatiblePartnerVersion,
+		/// A metadata limit above its `MaxMetadataLen` or `MaxUriLen` ceiling
+		MetadataLimitAboveCeiling,
+		/// Metadata exceeds the destination's `max_metadata_len`
+		MetadataTooLongForDestination,
+		/// More metadata URIs than `MaxUrisPerItem`
+		TooManyUris,
+		/// Two metadata URIs of the same `MediaKind`
+		DuplicateMediaKind,
+		/// No royalties are escrowed for the location
+		NoRoyaltiesToClaim,
+		/// Every id in `WrappedCollectionRange` is taken
+		CollectionIdExhausted,
+		/// No ownership query is outstanding under this id
+		UnknownQuery,
+		/// The ownership query lapsed before its answer arrived
+		QueryExpired,
+		/// The ownership query may still be answered
+		QueryNotExpired,
+		/// Only the collection's admin or `AdminOrigin` may do this
+		NotCollectionAdmin,
+		/// The transfer's `valid_until` block has passed
+		TransferDeadlineExpired,
+		/// Outbound sends are paused
+		BridgePaused,
+		/// The sender already has `MaxPendingPerAccount` transfers pending
+		TooManyPending,
+		/// The item isn't an orphaned inbound NFT
+		NotOrphaned,
+		/// The orphaned NFT has left the sovereign account that held it
+		OrphanNoLongerHeld,
+		/// A lane to the chain is already open
+		LaneAlreadyOpen,
+		/// No lane to the chain is open
+		LaneNotFound,
+		/// The lane to the destination is still waiting for the partner's handshake
+		LaneNotActive,
+		/// The lane still has transfers pending or queued
+		LaneInUse,
+		/// Receiving the item would exceed its collection's `max_supply`
+		CollectionSupplyExceeded,
+		/// The account is already a bridge authority
+		AlreadyAuthority,
+		/// The account is not a bridge authority
+		NotAuthority,
+		/// `BridgeAuthorities` already holds `MaxAuthorities` accounts
+		TooManyAuthorities,
+		/// The last bridge authority can't be removed
+		LastAuthority,
+		/// The NFT is already held in escrow, by the bridge or another pallet
+		EscrowedElsewhere,
+		/// No escrow has the given id
+		EscrowNotFound,
+		/// Only the bridge itself escrows NFTs under `EscrowReason::Bridge`
+		ReservedEscrowReason,
+		/// An inbound NFT carries neither metadata, a metadata URI nor a metadata hash, and has
+		/// no metadata here
+		MissingProvenance,
+		/// `MaxOwnershipQueries` ownership queries already await an answer
+		TooManyOwnershipQueries,
+		/// No transfer awaiting delivery has this id, so it can't be refused
+		TransferNotRejectable,
+		/// The item's provenance trail is no longer than `compact_item_history` would leave it
+		NothingToCompact,
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn owner)]
+	/// Map of (collection_id, item_id) to owner
+	pub type NFTOwners<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	/// Storage to track pending cross-chain transfers
+	#[pallet::storage]
+	#[pallet::getter(fn pending_transfer)]
+	pub type PendingTransfers<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		TransferInfo<T::AccountId, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Secondary index of pending transfers by destination para id, kept in step with
+	/// `PendingTransfers`
+	#[pallet::storage]
+	pub type PendingByDestination<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Blake2_128Concat,
+		(T::CollectionId, T::ItemId),
+		(),
+		OptionQuery,
+	>;
+
+	/// Hash of every outbound transfer message, mapped to the transfer it carried.
+	/// Entries are kept after the transfer settles so explorers can still resolve them, until
+	/// `prune_transfer_records` removes the transfer's records.
+	#[pallet::storage]
+	pub type MessageIndex<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, XcmHash, TransferId, OptionQuery>;
+
+	/// The NFT each transfer id refers to
+	#[pallet::storage]
+	pub type TransferKeys<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TransferId, (T::CollectionId, T::ItemId), OptionQuery>;
+
+	/// The `MessageIndex` hashes of each transfer's latest messages, so they can be pruned
+	/// with it
+	#[pallet::storage]
+	pub type TransferMessages<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TransferId,
+		BoundedVec<XcmHash, ConstU32<MAX_MESSAGES_PER_TRANSFER>>,
+		ValueQuery,
+	>;
+
+	/// Confirmed transfers, kept after the departed item's metadata has been removed. Together
+	/// with `TransferKeys` this is the audit record of NFTs that left the chain.
+	#[pallet::storage]
+	pub type Tombstones<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TransferId, TransferTombstone<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Messages of transfers whose send failed, kept for automatic re-sends
+	#[pallet::storage]
+	pub type FailedSends<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TransferId,
+		(T::CollectionId, T::ItemId, EncodedXcmOf<T, I>),
+		OptionQuery,
+	>;
+
+	/// Raw `FailedSends` key the last retry sweep stopped at
+	#[pallet::storage]
+	pub type RetryCursor<T: Config<I>, I: 'static = ()> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Number of delivered but unsettled transfers to each destination. Queued transfers and
+	/// transfers whose send failed don't count.
+	#[pallet::storage]
+	pub type InFlightCount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
+
+	/// Number of priority transfers at the head of each destination's `OutboundQueue`
+	#[pallet::storage]
+	pub type PriorityQueued<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
+
+	/// Transfers waiting for an in-flight slot at each destination, oldest first
+	#[pallet::storage]
+	pub type OutboundQueue<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<TransferId, T::MaxOutboundQueue>,
+		ValueQuery,
+	>;
+
+	/// Messages of queued transfers, sent once they leave the queue
+	#[pallet::storage]
+	pub type QueuedSends<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TransferId,
+		(T::CollectionId, T::ItemId, EncodedXcmOf<T, I>),
+		OptionQuery,
+	>;
+
+	/// Pending transfers that have already been reported as stale
+	#[pallet::storage]
+	pub type StaleReported<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TransferId, (), OptionQuery>;
+
+	/// Block in which each NFT was last locked outbound or minted inbound
+	#[pallet::storage]
+	pub type BridgedAt<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// NFTs frozen in place by a pending transfer under `LockStrategy::Freeze`. Transfer
+	/// implementations must refuse to move flagged items.
+	#[pallet::storage]
+	pub type BridgeLocked<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Whether governance stopped outbound sends
+	#[pallet::storage]
+	#[pallet::getter(fn bridge_paused)]
+	pub type BridgePaused<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+	/// Number of outbound transfers each account has pending
+	#[pallet::storage]
+	pub type PendingPerAccount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Item-keyed map `clear_collection_data` resumes from for each collection being cleared
+	#[pallet::storage]
+	pub type CollectionClearCursor<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, u8, ValueQuery>;
+
+	/// Ids of the latest deliveries processed from each source chain, oldest first
+	#[pallet::storage]
+	pub type ProcessedDeliveries<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<[u8; 32], T::DeliveryDedupWindow>,
+		ValueQuery,
+	>;
+
+	/// Bridge operators, whom `EnsureBridgeAuthority` lets through
+	#[pallet::storage]
+	#[pallet::getter(fn bridge_authorities)]
+	pub type BridgeAuthorities<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxAuthorities>, ValueQuery>;
+
+	/// Bridge activity in the current `StatsPeriod`, reset when its `PeriodStats` is emitted
+	#[pallet::storage]
+	#[pallet::getter(fn period_counters)]
+	pub type PeriodCounters<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BridgeCounters, ValueQuery>;
+
+	/// Bridge activity since genesis
+	#[pallet::storage]
+	#[pallet::getter(fn lifetime_counters)]
+	pub type LifetimeCounters<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BridgeCounters, ValueQuery>;
+
+	/// NFTs other pallets hold in escrow through `NonfungibleEscrow`
+	#[pallet::storage]
+	#[pallet::getter(fn escrows)]
+	pub type Escrows<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		EscrowId,
+		EscrowInfo<T::AccountId, T::CollectionId, T::ItemId>,
+		OptionQuery,
+	>;
+
+	/// The escrow holding each NFT in `Escrows`
+	#[pallet::storage]
+	pub type EscrowedItems<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		EscrowId,
+		OptionQuery,
+	>;
+
+	/// The id the next escrow will be assigned
+	#[pallet::storage]
+	pub type NextEscrowId<T: Config<I>, I: 'static = ()> = StorageValue<_, EscrowId, ValueQuery>;
+
+	/// Latest failed inbound deliveries from each source chain, oldest first
+	#[pallet::storage]
+	pub type RecentInboundFailures<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<InboundFailureOf<T, I>, T::MaxFailureRecords>,
+		ValueQuery,
+	>;
+
+	/// Delivery fees paid to the transport for messages to each destination since the last
+	/// reset, in the fee currency's smallest unit
+	#[pallet::storage]
+	#[pallet::getter(fn delivery_fee_spend)]
+	pub type DeliveryFeeSpend<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, u128, ValueQuery>;
+
+	/// Highest nonce accepted from each source chain
+	#[pallet::storage]
+	pub type InboundNonce<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, u64, ValueQuery>;
+
+	/// Accounts that refuse inbound bridged NFTs
+	#[pallet::storage]
+	pub type InboundOptOut<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Metadata and URI bytes currently stored for each collection
+	#[pallet::storage]
+	pub type CollectionMetadataBytes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, u64, ValueQuery>;
+
+	/// Raw `PendingTransfers` key the last expiry sweep stopped at
+	#[pallet::storage]
+	pub type ExpiryCursor<T: Config<I>, I: 'static = ()> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Raw `PendingTransfers` key the last aging scan stopped at
+	#[pallet::storage]
+	pub type AgingCursor<T: Config<I>, I: 'static = ()> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Pending transfers `TransferAging` was emitted for
+	#[pallet::storage]
+	pub type AgingWarned<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TransferId, (), OptionQuery>;
+
+	/// The id the next swap proposed on this chain will be assigned
+	#[pallet::storage]
+	pub type NextSwapId<T: Config<I>, I: 'static = ()> = StorageValue<_, SwapId, ValueQuery>;
+
+	/// Swaps proposed on this chain that the counterpart has not settled yet
+	#[pallet::storage]
+	#[pallet::getter(fn pending_swap)]
+	pub type PendingSwaps<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, SwapId, SwapOfferOf<T, I>, OptionQuery>;
+
+	/// Swaps offered by other chains, by proposing chain and that chain's swap id
+	#[pallet::storage]
+	#[pallet::getter(fn incoming_swap)]
+	pub type IncomingSwaps<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<
+			_,
+			Twox64Concat,
+			u32,
+			Twox64Concat,
+			SwapId,
+			SwapOfferOf<T, I>,
+			OptionQuery,
+		>;
+
+	/// Collections an item of which has been sent or received here, or that were given an admin.
+	/// Lets a mistyped collection be reported as `CollectionNotFound` rather than `NFTNotFound`.
+	#[pallet::storage]
+	pub type KnownCollections<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, (), OptionQuery>;
+
+	/// Next id to try for a wrapped collection, `None` for the start of `WrappedCollectionRange`
+	#[pallet::storage]
+	pub type NextWrappedCollectionId<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::CollectionId, OptionQuery>;
+
+	/// Local id of each wrapped collection given one from `WrappedCollectionRange`, by source
+	/// chain and that chain's collection id
+	#[pallet::storage]
+	#[pallet::getter(fn wrapped_collection)]
+	pub type WrappedCollectionIds<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Blake2_128Concat,
+		T::CollectionId,
+		T::CollectionId,
+		OptionQuery,
+	>;
+
+	/// Source chain and collection id of each entry in `WrappedCollectionIds`, by local id
+	#[pallet::storage]
+	pub type WrappedCollectionSources<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, (u32, T::CollectionId), OptionQuery>;
+
+	/// Collections whose items may not be bridged out, and possibly not in either
+	#[pallet::storage]
+	#[pallet::getter(fn transfer_restriction)]
+	pub type NonTransferableCollections<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, TransferRestriction, OptionQuery>;
+
+	/// Collections being migrated to another chain
+	#[pallet::storage]
+	#[pallet::getter(fn collection_export)]
+	pub type CollectionExportState<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, CollectionExportOf<T>, OptionQuery>;
+
+	/// NFTs held for a swap. They keep their owner but cannot be sent or transferred.
+	#[pallet::storage]
+	pub type SwapEscrow<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Raw `PendingSwaps` key the last swap expiry sweep stopped at
+	#[pallet::storage]
+	pub type SwapExpiryCursor<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Raw `IncomingSwaps` key the last swap expiry sweep stopped at
+	#[pallet::storage]
+	pub type IncomingSwapExpiryCursor<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Raw `NFTMetadata` key the last orphan purge stopped at
+	#[pallet::storage]
+	pub type MetadataPurgeCursor<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Hash of the metadata of wrappers that arrived without it, until it is fetched
+	#[pallet::storage]
+	pub type DeferredMetadata<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		T::Hash,
+		OptionQuery,
+	>;
+
+	/// The id the next ownership query will be sent with
+	#[pallet::storage]
+	pub type NextOwnershipQueryId<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, QueryId, ValueQuery>;
+
+	/// Ownership queries awaiting an answer
+	#[pallet::storage]
+	#[pallet::getter(fn pending_ownership_query)]
+	pub type OwnershipQueries<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, QueryId, OwnershipQueryOf<T, I>, OptionQuery>;
+
+	/// Number of entries in `OwnershipQueries`
+	#[pallet::storage]
+	pub type OwnershipQueryCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// Owners of items on other chains, by chain and collection as they were last reported
+	#[pallet::storage]
+	#[pallet::getter(fn remote_ownership)]
+	pub type RemoteOwnership<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(u32, T::CollectionId),
+		Blake2_128Concat,
+		T::ItemId,
+		RemoteOwnerOf<T>,
+		OptionQuery,
+	>;
+
+	/// Outstanding metadata requests and the block they lapse at
+	#[pallet::storage]
+	pub type MetadataRequests<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// Chunked metadata being received, by source chain and the source's transfer id
+	#[pallet::storage]
+	pub type MetadataAssemblies<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Twox64Concat,
+		TransferId,
+		MetadataAssemblyOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Chunks received so far for each open assembly, by index
+	#[pallet::storage]
+	pub type PendingChunks<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(u32, TransferId),
+		Twox64Concat,
+		u32,
+		BoundedVec<u8, T::ChunkSize>,
+		OptionQuery,
+	>;
+
+	/// Metadata uploaded ahead of a transfer by `stage_metadata`
+	#[pallet::storage]
+	#[pallet::getter(fn staged_metadata)]
+	pub type StagedMetadata<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		StagedMetadataOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Raw `NFTOwners` key the last escrow reconciliation of each destination stopped at
+	#[pallet::storage]
+	pub type EscrowReconcileCursor<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, Vec<u8>, OptionQuery>;
+
+	/// Collections whose metadata quota governance raised above `MaxCollectionMetadataBytes`
+	#[pallet::storage]
+	pub type CollectionMetadataQuota<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, u64, OptionQuery>;
+
+	/// Collections onboarded to the bridge
+	#[pallet::storage]
+	#[pallet::getter(fn collection_info)]
+	pub type Collections<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		CollectionInfo<T::AccountId>,
+		OptionQuery,
+	>;
+
+	/// Number of wrappers of each collection held on this chain, checked against its
+	/// `max_supply`. Items parked with a sovereign account by the orphan flow aren't counted.
+	#[pallet::storage]
+	#[pallet::getter(fn wrapped_supply)]
+	pub type WrappedSupply<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, u32, ValueQuery>;
+
+	/// Wrappers held under a different id than on their source chain: local id to the source
+	/// chain and its id there
+	#[pallet::storage]
+	pub type ItemRemap<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		(u32, T::ItemId),
+		OptionQuery,
+	>;
+
+	/// The reverse of `ItemRemap`: source chain and id there to the local id
+	#[pallet::storage]
+	pub type RemoteItemRemap<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(u32, T::CollectionId),
+		Blake2_128Concat,
+		T::ItemId,
+		T::ItemId,
+		OptionQuery,
+	>;
+
+	/// Chain each item that left here with a confirmed transfer now lives on
+	#[pallet::storage]
+	pub type ItemsAbroad<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		u32,
+		OptionQuery,
+	>;
+
+	/// Chain each wrapper received here came from. Items native to this chain have no entry.
+	#[pallet::storage]
+	#[pallet::getter(fn item_origin)]
+	pub type ItemOrigin<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		u32,
+		OptionQuery,
+	>;
+
+	/// Chains holding wrappers of a collection's items, which receive its metadata updates
+	#[pallet::storage]
+	pub type MetadataSubscribers<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Twox64Concat,
+		u32,
+		(),
+		OptionQuery,
+	>;
+
+	/// Items whose metadata update still has to reach some subscribed chains, with the raw
+	/// `MetadataSubscribers` key of the last chain messaged. `on_idle` continues after it.
+	#[pallet::storage]
+	pub type MetadataSyncCursors<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, (T::CollectionId, T::ItemId), Vec<u8>, OptionQuery>;
+
+	/// Block of each item's last metadata sync
+	#[pallet::storage]
+	pub type LastMetadataSync<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// Collections whose bridging fee differs from `BridgeFee`
+	#[pallet::storage]
+	#[pallet::getter(fn collection_fee_override)]
+	pub type CollectionFeeOverride<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, FeePolicy<BalanceOf<T, I>>, OptionQuery>;
+
+	/// Governance-managed settings for each destination
+	#[pallet::storage]
+	#[pallet::getter(fn destination_config)]
+	pub type DestinationConfigs<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, DestinationConfig, OptionQuery>;
+
+	/// Intermediate reserve chain through which each destination is reached. Destinations
+	/// without an entry are sent to directly.
+	#[pallet::storage]
+	#[pallet::getter(fn route)]
+	pub type RouteTable<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, u32, OptionQuery>;
+
+	/// XCM version each destination understands. Destinations without an entry get the latest.
+	#[pallet::storage]
+	#[pallet::getter(fn destination_xcm_version)]
+	pub type DestinationXcmVersion<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, XcmVersion, OptionQuery>;
+
+	/// `BridgeMessage` version each partner chain understands, set by governance or advertised
+	/// by the partner. Partners without an entry get `BRIDGE_MESSAGE_VERSION`.
+	#[pallet::storage]
+	#[pallet::getter(fn partner_protocol_version)]
+	pub type PartnerProtocolVersion<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, u32, OptionQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultMetadataLimits<T: Config<I>, I: 'static>() -> MetadataSizeLimits {
+		MetadataSizeLimits { max_metadata: T::MaxMetadataLen::get(), max_uri: T::MaxUriLen::get() }
+	}
+
+	/// Metadata size limits applied to transfers and metadata updates. Starts at the
+	/// `MaxMetadataLen` and `MaxUriLen` ceilings.
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_limits)]
+	pub type MetadataLimits<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, MetadataSizeLimits, ValueQuery, DefaultMetadataLimits<T, I>>;
+
+	/// The id the next outbound transfer will be assigned
+	#[pallet::storage]
+	pub type NextTransferId<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, TransferId, ValueQuery>;
+
+	/// Storage to preserve NFT metadata during transfers
+	#[pallet::storage]
+	#[pallet::getter(fn nft_metadata)]
+	pub type NFTMetadata<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		Vec<u8>, // Raw metadata bytes
+		OptionQuery,
+	>;
+
+	/// Metadata URIs of each item (IPFS or other decentralized storage), at most one per
+	/// `MediaKind`
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_uris)]
+	pub type MetadataUris<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		MetadataUrisOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Items whose wrapper was burned on the chain they went to, by that chain
+	#[pallet::storage]
+	#[pallet::getter(fn orphaned_item)]
+	pub type OrphanedItems<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		u32,
+		OptionQuery,
+	>;
+
+	/// Royalty owed on priced local transfers of each item
+	#[pallet::storage]
+	#[pallet::getter(fn royalty)]
+	pub type Royalties<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltyInfo<T::AccountId>,
+		OptionQuery,
+	>;
+
+	/// Royalties escrowed for remote beneficiaries until they claim them
+	#[pallet::storage]
+	#[pallet::getter(fn royalty_pot)]
+	pub type RoyaltyPots<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, MultiLocation, BalanceOf<T, I>, ValueQuery>;
+
+	/// Holding area for inbound NFTs not credited to their beneficiary, held by their source
+	/// chain's sovereign account because the beneficiary couldn't be resolved to a local account
+	/// or their collection had no supply left
+	#[pallet::storage]
+	#[pallet::getter(fn orphaned_inbound)]
+	pub type OrphanedInbound<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		OrphanedNft<T::AccountId>,
+		OptionQuery,
+	>;
+
+	/// Canonical id of each item, fixed the first time it was sent or received
+	#[pallet::storage]
+	#[pallet::getter(fn canonical_ids)]
+	pub type CanonicalIds<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		CanonicalId,
+		OptionQuery,
+	>;
+
+	/// Local item of each entry in `CanonicalIds`, by the hash of its canonical id
+	#[pallet::storage]
+	#[pallet::getter(fn item_by_canonical_id)]
+	pub type CanonicalIdIndex<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, T::Hash, (T::CollectionId, T::ItemId), OptionQuery>;
+
+	/// Lanes opened to partner chains with `open_lane`
+	#[pallet::storage]
+	#[pallet::getter(fn lane)]
+	pub type Lanes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, Lane, OptionQuery>;
+
+	/// Provenance trail of each item received here
+	#[pallet::storage]
+	#[pallet::getter(fn item_history)]
+	pub type ItemHistories<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		ItemHistoryOf<T, I>,
+		OptionQuery,
+	>;
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::emit_period_stats(now)
+		}
+
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut used = Self::retry_failed_sends(now, remaining_weight);
+			used.saturating_accrue(Self::drain_outbound_queues(remaining_weight.saturating_sub(used)));
+			used.saturating_accrue(Self::expire_transfers(now, remaining_weight.saturating_sub(used)));
+			used.saturating_accrue(Self::warn_aging_transfers(
+				now,
+				remaining_weight.saturating_sub(used),
+			));
+			used.saturating_accrue(Self::expire_swaps(now, remaining_weight.saturating_sub(used)));
+			used.saturating_accrue(Self::drain_metadata_syncs(
+				remaining_weight.saturating_sub(used),
+			));
+			used.saturating_add(Self::process_exports(remaining_weight.saturating_sub(used)))
+		}
+
+		fn offchain_worker(now: BlockNumberFor<T>) {
+			Self::scan_stale_transfers(now);
+		}
+
+		fn integrity_test() {
+			// Received metadata and URIs are written to the NFT backend as attributes, so any
+			// the metadata limits let through must fit one
+			assert!(
+				T::MaxAttributeLen::get() >= T::MaxMetadataLen::get().max(T::MaxUriLen::get()),
+				"MaxAttributeLen is below MaxMetadataLen or MaxUriLen"
+			);
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config<I>, I: 'static> ValidateUnsigned for Pallet<T, I> {
+		type Call = Call<T, I>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::report_stale { collection_id, item_id, transfer_id } = call else {
+				return InvalidTransaction::Call.into()
+			};
+			// The worker is not trusted: only accept reports that check out on-chain
+			Self::ensure_reportable(*collection_id, *item_id, *transfer_id)
+				.map_err(|_| InvalidTransaction::Stale)?;
+
+			ValidTransaction::with_tag_prefix("NftBridgeStale")
+				.priority(T::UnsignedPriority::get())
+				.and_provides(transfer_id)
+				.longevity(5)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Send an NFT to another parachain. A `priority` transfer pays `PriorityFee` on top of the
+		/// other fees and goes ahead of every non-priority transfer waiting for the destination.
+		#[pallet::call_index(0)]
+		#[pallet::weight({
+			let uri_len = metadata_uri.as_ref().map_or(0, |uri| uri.len() as u32);
+			let metadata_len = metadata.len() as u32;
+			T::WeightInfo::send_nft(metadata_len, attribute_count(metadata_len, uri_len))
+		})]
+		pub fn send_nft(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+			metadata: Vec<u8>,
+			metadata_uri: Option<Vec<u8>>, // Optional URI for decentralized storage
+			remote_call: Option<BoundedVec<u8, T::MaxRemoteCallLen>>, // Dispatched on arrival
+			execution_limit: ExecutionLimit,
+			priority: bool,
+			metadata_uris: ItemUris, // Further URIs; `metadata_uri` is the `Primary` one
+			valid_until: Option<BlockNumberFor<T>>, // Last block the transfer may execute in
+		) -> DispatchResult {
+			let sender = T::SendOrigin::ensure_origin(origin)?;
+			Self::ensure_before_deadline(valid_until)?;
+
+			// Call the XCM handler to process the transfer, with metadata preservation
+			Self::do_xcm_transfer_nft(
+				sender,
+				collection_id,
+				item_id,
+				dest_para_id,
+				metadata,
+				combine_uris(metadata_uri, metadata_uris),
+				remote_call.map(Into::into),
+				execution_limit,
+				priority,
+				valid_until,
+			)
+			.map(|_| ())
+		}
+
+		/// Receive an NFT from another parachain - typically called by XCM execution. A delivery
+		/// refused here doesn't fail the call: it is recorded in `RecentInboundFailures` and
+		/// announced with `InboundDeliveryFailed`. The item's provenance trail on the source, if
+		/// given, is kept with the hop just made added.
+		#[pallet::call_index(1)]
+		#[pallet::weight({
+			let uri_len = metadata_uri.as_ref().map_or(0, |uri| uri.len() as u32);
+			let metadata_len = metadata.len() as u32;
+			T::WeightInfo::receive_nft(metadata_len, attribute_count(metadata_len, uri_len))
+		})]
+		pub fn receive_nft(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			from_para_id: u32,
+			owner: T::AccountId,
+			metadata: Vec<u8>,
+			metadata_uri: Option<Vec<u8>>, // Optional URI for decentralized storage
+			nonce: Option<u64>,
+			metadata_hash: Option<T::Hash>,
+			lock_proof: Option<Vec<u8>>,
+			metadata_uris: ItemUris, // Further URIs; `metadata_uri` is the `Primary` one
+			delivery_id: Option<[u8; 32]>, // Repeated by relayers retrying the same delivery
+			sent_at: Option<u64>, // Unix time in seconds the source sent it, checked if given
+			transfer_id: Option<TransferId>, // The source's id for the transfer, named in reports
+			history: Option<ProvenanceTrailOf<T, I>>, // The item's trail on the source, if known
+		) -> DispatchResult {
+			// Only the chain the NFT claims to come from may deliver it
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			ensure!(Self::sibling_para_id(&location) == Some(from_para_id), Error::<T, I>::OriginMismatch);
+
+			// A retried delivery succeeds without doing anything
+			if let Some(delivery_id) = delivery_id {
+				if ProcessedDeliveries::<T, I>::get(from_para_id).contains(&delivery_id) {
+					Self::deposit_event(Event::DuplicateDeliveryIgnored {
+						from_para_id,
+						delivery_id,
+					});
+					return Ok(())
+				}
+			}
+
+			// A refused delivery is recorded rather than failed, so the record outlives the call
+			let received = Self::do_receive_nft_recording_failure(
+				collection_id,
+				item_id,
+				from_para_id,
+				owner,
+				Some(metadata),
+				combine_uris(metadata_uri, metadata_uris),
+				nonce,
+				metadata_hash,
+				lock_proof,
+				sent_at,
+				transfer_id,
+				history,
+			);
+			if !received {
+				return Ok(())
+			}
+			if let Some(delivery_id) = delivery_id {
+				Self::note_delivery(from_para_id, delivery_id);
+			}
+
+			// Sources without this pallet learn of the delivery through their own call
+			Self::send_transact_confirmation(from_para_id, collection_id, item_id)
+		}
+
+		/// Receive a bundle of NFTs, possibly from several collections, sent in one message.
+		/// Either every item is credited or none is; a refused bundle is recorded like a refused
+		/// `receive_nft`.
+		#[pallet::call_index(15)]
+		#[pallet::weight(Pallet::<T, I>::batch_weight(items, T::WeightInfo::receive_nft))]
+		pub fn receive_nfts(
+			origin: OriginFor<T>,
+			items: BoundedVec<BatchItem<T::CollectionId, T::ItemId>, T::MaxBatchSize>,
+			from_para_id: u32,
+			owner: T::AccountId,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			ensure!(Self::sibling_para_id(&location) == Some(from_para_id), Error::<T, I>::OriginMismatch);
+
+			Self::do_receive_nfts(items.into_inner(), from_para_id, owner);
+			Ok(())
+		}
+
+		/// Opt in to or out of receiving NFTs over the bridge
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::set_inbound_opt_out())]
+		pub fn set_inbound_opt_out(origin: OriginFor<T>, opted_out: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if opted_out {
+				InboundOptOut::<T, I>::insert(&who, ());
+			} else {
+				InboundOptOut::<T, I>::remove(&who);
+			}
+
+			Self::deposit_event(Event::InboundOptOutSet { who, opted_out });
+
+			Ok(())
+		}
+
+		/// Deliver an NFT whose inbound XCM failed after the source chain already locked it.
+		///
+		/// Bypasses the inbound source checks. An existing owner is only replaced when
+		/// `overwrite` is set.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::force_receive())]
+		pub fn force_receive(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			from_para_id: u32,
+			owner: T::AccountId,
+			metadata: Vec<u8>,
+			metadata_uri: Option<Vec<u8>>,
+			overwrite: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let overwritten = NFTOwners::<T, I>::get(collection_id, item_id)
+				.map_or(false, |owner| !Self::is_escrow_account(&owner));
+			ensure!(!overwritten || overwrite, Error::<T, I>::NFTAlreadyExists);
+
+			Self::store_received_nft(
+				collection_id,
+				item_id,
+				from_para_id,
+				owner.clone(),
+				Some(metadata),
+				combine_uris(metadata_uri, Vec::new()),
+			)?;
+
+			Self::deposit_event(Event::ForceReceived {
+				collection_id,
+				item_id,
+				from_para_id,
+				owner,
+				overwritten,
+			});
+
+			Ok(())
+		}
+
+		/// Refuse delivery of an NFT parked in `OrphanedInbound` and instruct the source chain
+		/// to unlock the original to its prior owner
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::reject_inbound())]
+		pub fn reject_inbound(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			from_para_id: u32,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let reason = RejectionReason::AdminRejected;
+			Self::do_reject_orphaned(collection_id, item_id, from_para_id, reason)?;
+
+			Self::deposit_event(Event::InboundRejected {
+				collection_id,
+				item_id,
+				from_para_id,
+				reason,
+			});
+
+			Ok(())
+		}
+
+		/// Unlock an NFT that the destination chain refused - called by the bounce XCM. The NFT
+		/// goes back to the transfer's sender, whoever the destination names.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::unlock_bounced())]
+		pub fn unlock_bounced(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			_owner: T::AccountId, // The recipient as the destination knew it; not trusted
+			reason: RejectionReason,
+		) -> DispatchResult {
+			// Only the chain the NFT was sent to may bounce it
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let (collection_id, item_id) = Self::local_ids(&location, collection_id, item_id);
+			let transfer = PendingTransfers::<T, I>::get(collection_id, item_id).ok_or(Error::<T, I>::NFTNotFound)?;
+			ensure!(location == transfer.dest, Error::<T, I>::OriginMismatch);
+
+			let owner = transfer.sender;
+			Self::unlock_nft(collection_id, item_id, &owner)?;
+
+			Self::deposit_event(Event::TransferBounced {
+				collection_id,
+				item_id,
+				owner,
+				reason,
+			});
+
+			Ok(())
+		}
+
+		/// Confirm that the destination received an NFT - called by the destination's bridge
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::confirm_transfer())]
+		pub fn confirm_transfer(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			// Only the chain the NFT was sent to may confirm it
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let (collection_id, item_id) = Self::local_ids(&location, collection_id, item_id);
+			let transfer = PendingTransfers::<T, I>::get(collection_id, item_id).ok_or(Error::<T, I>::NFTNotFound)?;
+			ensure!(location == transfer.dest, Error::<T, I>::OriginMismatch);
+
+			let dest_para_id = Self::sibling_para_id(&transfer.dest).unwrap_or_default();
+			Self::complete_outbound(collection_id, item_id);
+
+			// The canonical metadata now lives on the destination
+			Self::clear_item_metadata(collection_id, item_id);
+			Tombstones::<T, I>::insert(
+				transfer.id,
+				TransferTombstone {
+					dest_para_id,
+					confirmed_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::TransferConfirmed {
+				collection_id,
+				item_id,
+				transfer_id: transfer.id,
+				dest_para_id,
+			});
+
+			Ok(())
+		}
+
+		/// Send several NFTs to the same parachain.
+		///
+		/// In `Atomic` mode any failing item fails the whole call. In `BestEffort` mode failing
+		/// items are skipped and reported in `BatchSendResult`; the call only fails if nothing
+		/// could be sent. A passed `valid_until` fails the whole call in every mode.
+		#[pallet::call_index(8)]
+		#[pallet::weight(Pallet::<T, I>::batch_weight(items, T::WeightInfo::send_nft))]
+		pub fn send_nfts(
+			origin: OriginFor<T>,
+			items: BoundedVec<BatchItem<T::CollectionId, T::ItemId>, T::MaxBatchSize>,
+			dest_para_id: u32,
+			mode: BatchMode,
+			valid_until: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let sender = T::SendOrigin::ensure_origin(origin)?;
+			Self::ensure_before_deadline(valid_until)?;
+
+			let (sent, skipped) = match mode {
+				BatchMode::Bundle => {
+					let sent = items.iter().map(|item| (item.collection_id, item.item_id)).collect();
+					Self::do_send_bundle(sender, items.into_inner(), dest_para_id, valid_until)?;
+					(sent, Vec::new())
+				},
+				BatchMode::Atomic | BatchMode::BestEffort => {
+					let mut sent = Vec::new();
+					let mut skipped = Vec::new();
+					for item in items {
+						let keys = (item.collection_id, item.item_id);
+						let send = || {
+							Self::do_xcm_transfer_nft(
+								sender.clone(),
+								item.collection_id,
+								item.item_id,
+								dest_para_id,
+								item.metadata,
+								combine_uris(item.metadata_uri, Vec::new()),
+								None,
+								ExecutionLimit::Default,
+								false,
+								valid_until,
+							)
+						};
+						if mode == BatchMode::Atomic {
+							send()?;
+							sent.push(keys);
+						} else {
+							// Each item gets its own storage layer so a failure leaves no partial
+							// writes
+							match frame_support::storage::with_storage_layer(send) {
+								Ok(_) => sent.push(keys),
+								Err(e) => skipped.push((keys, e)),
+							}
+						}
+					}
+					ensure!(!sent.is_empty(), Error::<T, I>::NothingSent);
+					(sent, skipped)
+				},
+			};
+
+			Self::deposit_event(Event::BatchSendResult { dest_para_id, sent, skipped });
+
+			Ok(())
+		}
+
+		/// Flag a pending transfer as stale - submitted unsigned by the offchain worker
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::report_stale())]
+		pub fn report_stale(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let transfer = Self::ensure_reportable(collection_id, item_id, transfer_id)?;
+			StaleReported::<T, I>::insert(transfer_id, ());
+
+			let age = frame_system::Pallet::<T>::block_number().saturating_sub(transfer.sent_at);
+			Self::deposit_event(Event::TransferStale { collection_id, item_id, transfer_id, age });
+
+			Ok(())
+		}
+
+		/// Cancel a pending transfer and return the NFT to its sender, once its message
+		/// can no longer be delivered
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::cancel_transfer())]
+		pub fn cancel_transfer(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			let owner = T::SendOrigin::ensure_origin(origin)?;
+
+			Self::do_cancel_transfer(owner, collection_id, item_id)
+		}
+		
+		/// Set or clear the configuration of a destination
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_destination_config())]
+		pub fn set_destination_config(
+			origin: OriginFor<T>,
+			dest_para_id: u32,
+			config: Option<DestinationConfig>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			DestinationConfigs::<T, I>::set(dest_para_id, config.clone());
+
+			Self::deposit_event(Event::DestinationConfigSet { dest_para_id, config });
+
+			Ok(())
+		}
+
+		/// Remove metadata of items that have neither an owner, a pending transfer nor a wrapper
+		/// abroad. Anyone may call this; each call examines up to
+		/// `limit` metadata entries, continuing where the previous call stopped.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::purge_orphaned_metadata(*limit))]
+		pub fn purge_orphaned_metadata(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let count = Self::do_purge_orphaned_metadata(limit);
+			Self::deposit_event(Event::MetadataPurged { count });
+
+			Ok(())
+		}
+
+		/// Set a collection's metadata byte quota, or clear it to use `MaxCollectionMetadataBytes`
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_collection_metadata_quota())]
+		pub fn set_collection_metadata_quota(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			quota: Option<u64>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			CollectionMetadataQuota::<T, I>::set(collection_id, quota);
+
+			Self::deposit_event(Event::CollectionMetadataQuotaSet { collection_id, quota });
+
+			Ok(())
+		}
+
+		/// Route transfers to a destination through an intermediate reserve chain, or clear the
+		/// route to send to it directly
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::set_route())]
+		pub fn set_route(origin: OriginFor<T>, dest_para_id: u32, via: Option<u32>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(via != Some(dest_para_id), Error::<T, I>::InvalidDestination);
+
+			RouteTable::<T, I>::set(dest_para_id, via);
+
+			Self::deposit_event(Event::RouteSet { dest_para_id, via });
+
+			Ok(())
+		}
+
+		/// Escrow an NFT and offer it on `their_para` in exchange for `their_owner`'s NFT there.
+		/// The offer can be taken up until `deadline`; after that the proposer may cancel it.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::propose_swap())]
+		pub fn propose_swap(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			their_para: u32,
+			their_collection_id: T::CollectionId,
+			their_item_id: T::ItemId,
+			their_owner: T::AccountId,
+			deadline: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let proposer = T::SendOrigin::ensure_origin(origin)?;
+
+			Self::do_propose_swap(
+				proposer,
+				collection_id,
+				item_id,
+				their_para,
+				their_collection_id,
+				their_item_id,
+				their_owner,
+				deadline,
+			)
+			.map(|_| ())
+		}
+
+		/// Accept a swap offered by `from_para_id`, escrowing the requested NFT
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::accept_swap())]
+		pub fn accept_swap(origin: OriginFor<T>, from_para_id: u32, swap_id: SwapId) -> DispatchResult {
+			let who = T::SendOrigin::ensure_origin(origin)?;
+
+			Self::do_accept_swap(who, from_para_id, swap_id)
+		}
+
+		/// Withdraw from a swap proposed here (`from_para_id` is `None`) or offered by
+		/// `from_para_id`, recovering the caller's NFT
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::cancel_swap())]
+		pub fn cancel_swap(
+			origin: OriginFor<T>,
+			from_para_id: Option<u32>,
+			swap_id: SwapId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_cancel_swap(who, from_para_id, swap_id)
+		}
+
+		/// Record a swap offer - called by the proposing chain's bridge
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::swap_offered())]
+		pub fn swap_offered(
+			origin: OriginFor<T>,
+			swap_id: SwapId,
+			offer: SwapOfferOf<T, I>,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let from_para_id =
+				Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			Self::do_swap_offered(from_para_id, swap_id, offer)
+		}
+
+		/// Settle an accepted swap - called by the counterpart chain's bridge
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::swap_accepted())]
+		pub fn swap_accepted(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let from_para_id =
+				Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			Self::do_swap_accepted(from_para_id, swap_id)
+		}
+
+		/// Deliver the requested NFT of a settled swap - called by the proposing chain's bridge
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::swap_settled())]
+		pub fn swap_settled(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let from_para_id =
+				Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			Self::do_swap_settled(from_para_id, swap_id)
+		}
+
+		/// Waive or fix the bridging fee for a collection, or return it to `BridgeFee`. Callable
+		/// by the collection admin or `AdminOrigin`.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::set_collection_fee_policy())]
+		pub fn set_collection_fee_policy(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			policy: FeePolicy<BalanceOf<T, I>>,
+		) -> DispatchResult {
+			Self::ensure_collection_manager(origin, collection_id)?;
+
+			match policy {
+				FeePolicy::Default => CollectionFeeOverride::<T, I>::remove(collection_id),
+				_ => CollectionFeeOverride::<T, I>::insert(collection_id, policy),
+			}
+
+			Self::deposit_event(Event::CollectionFeePolicySet { collection_id, policy });
+
+			Ok(())
+		}
+
+		/// Hand a registered collection's bridge settings to another account, as its current admin
+		/// or `AdminOrigin`
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::set_collection_admin())]
+		pub fn set_collection_admin(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			admin: T::AccountId,
+		) -> DispatchResult {
+			Self::ensure_collection_manager(origin, collection_id)?;
+
+			Collections::<T, I>::try_mutate(collection_id, |info| {
+				let info = info.as_mut().ok_or(Error::<T, I>::CollectionNotRegistered)?;
+				info.admin = admin.clone();
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::CollectionAdminSet { collection_id, admin });
+
+			Ok(())
+		}
+
+		/// Change the chain holding a collection's original items, `None` making this chain the
+		/// reserve. Callable by the collection admin or `AdminOrigin`, and refused while any of
+		/// the collection's items is pending, lives on another chain, or is held as a wrapper.
+		#[pallet::call_index(59)]
+		#[pallet::weight(T::WeightInfo::set_collection_reserve())]
+		pub fn set_collection_reserve(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			reserve: Option<u32>,
+		) -> DispatchResult {
+			Self::ensure_collection_manager(origin, collection_id)?;
+			ensure!(
+				PendingTransfers::<T, I>::iter_key_prefix(collection_id).next().is_none() &&
+					ItemsAbroad::<T, I>::iter_key_prefix(collection_id).next().is_none() &&
+					ItemOrigin::<T, I>::iter_key_prefix(collection_id).next().is_none(),
+				Error::<T, I>::CollectionInUse
+			);
+
+			Collections::<T, I>::try_mutate(collection_id, |info| {
+				let info = info.as_mut().ok_or(Error::<T, I>::CollectionNotRegistered)?;
+				info.reserve = reserve;
+				info.mode = match reserve {
+					Some(_) => CollectionMode::Wrapped,
+					None => CollectionMode::Native,
+				};
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::CollectionReserveSet { collection_id, reserve });
+
+			Ok(())
+		}
+
+		/// Remove the bridge data left behind by a collection with no item owned or pending here:
+		/// metadata, origins, remaps, royalties, subscriptions and its registration. Each call
+		/// removes up to `limit` entries and emits `CollectionDataClearing` until the final call
+		/// emits `CollectionDataCleared`.
+		#[pallet::call_index(60)]
+		#[pallet::weight(T::WeightInfo::clear_collection_data(*limit))]
+		pub fn clear_collection_data(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			limit: u32,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let (removed, finished) = Self::do_clear_collection_data(collection_id, limit)?;
+			if finished {
+				Self::deposit_event(Event::CollectionDataCleared { collection_id, removed });
+			} else {
+				Self::deposit_event(Event::CollectionDataClearing { collection_id, removed });
+			}
+
+			Ok(())
+		}
+
+		/// Reset the delivery fee spent on `dest_para_id`, or on every destination if `None`, at
+		/// the end of an accounting period
+		#[pallet::call_index(61)]
+		#[pallet::weight(T::WeightInfo::reset_delivery_fee_spend())]
+		pub fn reset_delivery_fee_spend(
+			origin: OriginFor<T>,
+			dest_para_id: Option<u32>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			match dest_para_id {
+				Some(dest_para_id) => DeliveryFeeSpend::<T, I>::remove(dest_para_id),
+				None => {
+					let _ = DeliveryFeeSpend::<T, I>::clear(u32::MAX, None);
+				},
+			}
+
+			Self::deposit_event(Event::DeliveryFeeSpendReset { dest_para_id });
+
+			Ok(())
+		}
+
+		/// Stop or resume outbound sends. Transfers already pending settle as usual.
+		#[pallet::call_index(62)]
+		#[pallet::weight(T::WeightInfo::set_bridge_paused())]
+		pub fn set_bridge_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			BridgePaused::<T, I>::put(paused);
+			Self::deposit_event(Event::BridgePauseSet { paused });
+
+			Ok(())
+		}
+
+		/// Hand an orphaned inbound NFT from its source chain's sovereign account to `to`
+		#[pallet::call_index(63)]
+		#[pallet::weight(T::WeightInfo::reassign_orphaned())]
+		pub fn reassign_orphaned(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			to: T::AccountId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Self::do_reassign_orphaned(collection_id, item_id, to)
+		}
+
+		/// Set up a partner chain in one go: its destination config, protocol and XCM versions
+		/// and reserve route. The lane stays `Pending`, refusing transfers, until the partner
+		/// answers the handshake sent to it with `confirm_lane`.
+		#[pallet::call_index(64)]
+		#[pallet::weight(T::WeightInfo::open_lane())]
+		pub fn open_lane(origin: OriginFor<T>, para_id: u32, config: LaneConfig) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Self::do_open_lane(para_id, config)
+		}
+
+		/// Handshake from a partner chain that opened a lane to this one
+		#[pallet::call_index(65)]
+		#[pallet::weight(T::WeightInfo::confirm_lane())]
+		pub fn confirm_lane(
+			origin: OriginFor<T>,
+			capabilities: LaneCapabilities,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let para_id = Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			Self::do_confirm_lane(para_id, capabilities)
+		}
+
+		/// Close a lane and remove everything `open_lane` configured, once no transfer to the
+		/// partner is pending or queued
+		#[pallet::call_index(66)]
+		#[pallet::weight(T::WeightInfo::close_lane())]
+		pub fn close_lane(origin: OriginFor<T>, para_id: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Self::do_close_lane(para_id)
+		}
+
+		/// Forget the failed inbound deliveries recorded for a source chain
+		#[pallet::call_index(67)]
+		#[pallet::weight(T::WeightInfo::clear_inbound_failures())]
+		pub fn clear_inbound_failures(origin: OriginFor<T>, para_id: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			RecentInboundFailures::<T, I>::remove(para_id);
+			Self::deposit_event(Event::InboundFailuresCleared { para_id });
+
+			Ok(())
+		}
+
+		/// Add an account to the bridge authorities
+		#[pallet::call_index(68)]
+		#[pallet::weight(T::WeightInfo::add_authority())]
+		pub fn add_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Self::do_add_authority(who)
+		}
+
+		/// Remove an account from the bridge authorities, unless it is the last one
+		#[pallet::call_index(69)]
+		#[pallet::weight(T::WeightInfo::remove_authority())]
+		pub fn remove_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Self::do_remove_authority(who)
+		}
+
+		/// Limit how many wrappers of a collection this chain holds at once, as its admin or
+		/// `AdminOrigin`. A limit below the current supply only holds back further receives.
+		#[pallet::call_index(70)]
+		#[pallet::weight(T::WeightInfo::set_collection_max_supply())]
+		pub fn set_collection_max_supply(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			max_supply: Option<u32>,
+		) -> DispatchResult {
+			Self::ensure_collection_manager(origin, collection_id)?;
+
+			Collections::<T, I>::try_mutate(collection_id, |info| {
+				let info = info.as_mut().ok_or(Error::<T, I>::CollectionNotRegistered)?;
+				info.max_supply = max_supply;
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::CollectionMaxSupplySet { collection_id, max_supply });
+
+			Ok(())
+		}
+
+		/// Learn that the destination refused the NFT sent under `transfer_id` - called by the
+		/// destination's bridge. The transfer stays pending, marked `Rejected`, until its sender
+		/// cancels it.
+		#[pallet::call_index(71)]
+		#[pallet::weight(T::WeightInfo::report_rejection())]
+		pub fn report_rejection(
+			origin: OriginFor<T>,
+			transfer_id: TransferId,
+			code: RejectionCode,
+		) -> DispatchResult {
+			// Only the chain the NFT was sent to may refuse it
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+
+			Self::do_report_rejection(&location, transfer_id, code)
+		}
+
+		/// Remove the audit records of settled transfers, so their ids and message hashes no
+		/// longer resolve. Transfers still pending are skipped.
+		#[pallet::call_index(72)]
+		#[pallet::weight(T::WeightInfo::prune_transfer_records(transfer_ids.len() as u32))]
+		pub fn prune_transfer_records(
+			origin: OriginFor<T>,
+			transfer_ids: BoundedVec<TransferId, T::MaxBatchSize>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let count = Self::do_prune_transfer_records(&transfer_ids);
+			Self::deposit_event(Event::TransferRecordsPruned { count });
+
+			Ok(())
+		}
+
+		/// Cut the provenance trail of an item the caller owns down to its first hop and the
+		/// latest `KeepRecentHops`, releasing the deposit held for the hops dropped
+		#[pallet::call_index(73)]
+		#[pallet::weight(T::WeightInfo::compact_item_history())]
+		pub fn compact_item_history(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let (elided, released) = Self::do_compact_item_history(&who, collection_id, item_id)?;
+			Self::deposit_event(Event::HistoryCompacted {
+				collection_id,
+				item_id,
+				elided,
+				released,
+			});
+
+			Ok(())
+		}
+
+		/// Onboard a collection to the bridge. `reserve_para` is the chain holding the original
+		/// items, or `None` for a collection native to this chain.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::register_collection())]
+		pub fn register_collection(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			reserve_para: Option<u32>,
+			admin: T::AccountId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				!Collections::<T, I>::contains_key(collection_id),
+				Error::<T, I>::CollectionAlreadyRegistered
+			);
+
+			let mode =
+				if reserve_para.is_some() { CollectionMode::Wrapped } else { CollectionMode::Native };
+			Collections::<T, I>::insert(
+				collection_id,
+				CollectionInfo {
+					reserve: reserve_para,
+					admin: admin.clone(),
+					mode,
+					max_supply: None,
+				},
+			);
+			KnownCollections::<T, I>::insert(collection_id, ());
+
+			Self::deposit_event(Event::CollectionRegistered {
+				collection_id,
+				reserve: reserve_para,
+				admin,
+			});
+
+			Ok(())
+		}
+
+		/// Remove a collection from the bridge. Refused while any of its items is pending, lives
+		/// on another chain, or is held here as a wrapper.
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::deregister_collection())]
+		pub fn deregister_collection(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let info =
+				Collections::<T, I>::get(collection_id)
+					.ok_or(Error::<T, I>::CollectionNotRegistered)?;
+
+			let holds_wrappers = info.mode == CollectionMode::Wrapped &&
+				NFTOwners::<T, I>::iter_key_prefix(collection_id).next().is_some();
+			ensure!(
+				PendingTransfers::<T, I>::iter_key_prefix(collection_id).next().is_none() &&
+					ItemsAbroad::<T, I>::iter_key_prefix(collection_id).next().is_none() &&
+					!holds_wrappers,
+				Error::<T, I>::CollectionInUse
+			);
+
+			Collections::<T, I>::remove(collection_id);
+			if let Some((para_id, remote)) = WrappedCollectionSources::<T, I>::take(collection_id) {
+				WrappedCollectionIds::<T, I>::remove(para_id, remote);
+			}
+
+			Self::deposit_event(Event::CollectionDeregistered { collection_id });
+
+			Ok(())
+		}
+
+		/// Check that the items held in `dest_para_id`'s escrow account are pending to or
+		/// confirmed on that destination, emitting `EscrowDiscrepancy` for the rest. Anyone may
+		/// call this; each call examines up to `limit` owner entries, continuing where the
+		/// previous call for the destination stopped.
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::reconcile_escrow(*limit))]
+		pub fn reconcile_escrow(
+			origin: OriginFor<T>,
+			dest_para_id: u32,
+			limit: u32,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let discrepancies = Self::do_reconcile_escrow(dest_para_id, limit);
+			Self::deposit_event(Event::EscrowReconciled { dest_para_id, discrepancies });
+
+			Ok(())
+		}
+
+		/// Upload an item's metadata ahead of sending it with `send_staged_nft`. A deposit of
+		/// `StagingDepositPerByte` is held until the item is sent or the staging is purged.
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::stage_metadata())]
+		pub fn stage_metadata(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			metadata: Vec<u8>,
+			metadata_uri: Option<Vec<u8>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let metadata_hash =
+				Self::do_stage_metadata(who, collection_id, item_id, metadata, metadata_uri)?;
+			Self::deposit_event(Event::MetadataStaged { collection_id, item_id, metadata_hash });
+
+			Ok(())
+		}
+
+		/// Send an NFT whose metadata was staged. The transfer is billed without a metadata
+		/// payload and the destination is given only the metadata hash.
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::send_nft(0, 0))]
+		pub fn send_staged_nft(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+			valid_until: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_before_deadline(valid_until)?;
+
+			let metadata_hash = Self::do_send_staged_nft(
+				sender,
+				collection_id,
+				item_id,
+				dest_para_id,
+				valid_until,
+			)?;
+			Self::deposit_event(Event::StagedNftSent {
+				collection_id,
+				item_id,
+				dest_para_id,
+				metadata_hash,
+			});
+
+			Ok(())
+		}
+
+		/// Remove staged metadata that was not sent within `StagingTimeout` blocks, refunding
+		/// the deposit. Anyone may call this.
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::purge_staged_metadata())]
+		pub fn purge_staged_metadata(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::do_purge_staged_metadata(collection_id, item_id)?;
+			Self::deposit_event(Event::StagedMetadataPurged { collection_id, item_id });
+
+			Ok(())
+		}
+
+		/// Ask the chain a wrapper came from for the metadata it was sent without. Callable by
+		/// the wrapper's owner; the request lapses after `MetadataRequestTimeout` blocks.
+		#[pallet::call_index(37)]
+		#[pallet::weight(T::WeightInfo::request_metadata())]
+		pub fn request_metadata(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_request_metadata(who, collection_id, item_id)
+		}
+
+		/// Answer a metadata request from a chain holding a wrapper of one of our items - called
+		/// by the bridge of that chain
+		#[pallet::call_index(38)]
+		#[pallet::weight(T::WeightInfo::serve_metadata())]
+		pub fn serve_metadata(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let dest_para_id =
+				Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			Self::do_serve_metadata(dest_para_id, collection_id, item_id)
+		}
+
+		/// Deliver requested metadata - called by the bridge of the chain the item came from
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::metadata_served())]
+		pub fn metadata_served(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			metadata: Vec<u8>,
+			metadata_uri: Option<Vec<u8>>,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let from_para_id =
+				Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			Self::do_metadata_served(from_para_id, collection_id, item_id, metadata, metadata_uri)
+		}
+
+		/// Send an NFT whose metadata exceeds one message, streaming the metadata after it in
+		/// `ChunkSize` pieces. The per-byte fee applies to the whole blob.
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::send_chunked_nft(
+			metadata.len() as u32 / T::ChunkSize::get().max(1) + 1
+		))]
+		pub fn send_chunked_nft(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+			metadata: Vec<u8>,
+			valid_until: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_before_deadline(valid_until)?;
+
+			let (transfer_id, total) = Self::do_send_chunked_nft(
+				sender,
+				collection_id,
+				item_id,
+				dest_para_id,
+				metadata,
+				valid_until,
+			)?;
+			Self::deposit_event(Event::MetadataChunksSent {
+				collection_id,
+				item_id,
+				transfer_id,
+				total,
+			});
+
+			Ok(())
+		}
+
+		/// Announce chunked metadata for an item - called by the bridge of the chain it came from
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::metadata_chunk_header())]
+		pub fn metadata_chunk_header(
+			origin: OriginFor<T>,
+			transfer_id: TransferId,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			total: u32,
+			metadata_hash: T::Hash,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let from_para_id =
+				Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			Self::do_metadata_chunk_header(
+				from_para_id,
+				transfer_id,
+				collection_id,
+				item_id,
+				total,
+				metadata_hash,
+			)
+		}
+
+		/// Deliver one chunk of announced metadata - called by the bridge of the chain the item
+		/// came from
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::metadata_chunk(T::MaxMetadataChunks::get()))]
+		pub fn metadata_chunk(
+			origin: OriginFor<T>,
+			chunk: MetadataChunkOf<T, I>,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let from_para_id =
+				Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			Self::do_metadata_chunk(from_para_id, chunk)
+		}
+
+		/// Remove a metadata assembly still incomplete after `ReassemblyTimeout` blocks. Anyone
+		/// may call this.
+		#[pallet::call_index(43)]
+		#[pallet::weight(T::WeightInfo::purge_metadata_assembly(T::MaxMetadataChunks::get()))]
+		pub fn purge_metadata_assembly(
+			origin: OriginFor<T>,
+			from_para_id: u32,
+			transfer_id: TransferId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::do_purge_metadata_assembly(from_para_id, transfer_id)?;
+			Self::deposit_event(Event::MetadataAssemblyPurged { from_para_id, transfer_id });
+
+			Ok(())
+		}
+
+		/// Copy an item's owner from the NFT backend into `NFTOwners`, e.g. after it changed
+		/// hands outside the bridge. Anyone may call this.
+		#[pallet::call_index(44)]
+		#[pallet::weight(T::WeightInfo::import_nft())]
+		pub fn import_nft(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let owner = Self::do_import_nft(collection_id, item_id)?;
+			Self::deposit_event(Event::NftImported { collection_id, item_id, owner });
+
+			Ok(())
+		}
+
+		/// Compare up to `limit` owner entries of a collection, after `start_after`, with the NFT
+		/// backend, emitting `OwnershipMismatch` for each disagreement. Items in flight are
+		/// skipped. Anyone may call this; it changes no state.
+		#[pallet::call_index(45)]
+		#[pallet::weight(T::WeightInfo::check_consistency(*limit))]
+		pub fn check_consistency(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			start_after: Option<T::ItemId>,
+			limit: u32,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let (mismatches, next) = Self::do_check_consistency(collection_id, start_after, limit);
+			Self::deposit_event(Event::ConsistencyChecked { collection_id, mismatches, next });
+
+			Ok(())
+		}
+
+		/// Record the `BridgeMessage` version a partner chain understands, or clear it to use
+		/// the latest
+		#[pallet::call_index(46)]
+		#[pallet::weight(T::WeightInfo::set_partner_version())]
+		pub fn set_partner_version(
+			origin: OriginFor<T>,
+			para_id: u32,
+			version: Option<u32>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			PartnerProtocolVersion::<T, I>::set(para_id, version);
+
+			Self::deposit_event(Event::PartnerVersionSet { para_id, version });
+
+			Ok(())
+		}
+
+		/// Handshake from a partner chain announcing the `BridgeMessage` version it understands
+		#[pallet::call_index(47)]
+		#[pallet::weight(T::WeightInfo::advertise_version())]
+		pub fn advertise_version(origin: OriginFor<T>, version: u32) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let para_id = Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			PartnerProtocolVersion::<T, I>::insert(para_id, version);
+
+			Self::deposit_event(Event::PartnerVersionSet { para_id, version: Some(version) });
+
+			Ok(())
+		}
+
+		/// Remove a pending transfer, even one whose record no longer decodes, returning the NFT
+		/// to `refund_to` or dropping it. `reason_hash` identifies the incident write-up.
+		#[pallet::call_index(48)]
+		#[pallet::weight(T::WeightInfo::kill_pending())]
+		pub fn kill_pending(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			refund_to: Option<T::AccountId>,
+			reason_hash: T::Hash,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let transfer_id = Self::do_kill_pending(collection_id, item_id, refund_to.as_ref())?;
+			Self::deposit_event(Event::PendingKilled {
+				collection_id,
+				item_id,
+				transfer_id,
+				refund_to,
+				reason_hash,
+			});
+
+			Ok(())
+		}
+
+		/// Change the metadata size limits, within the `MaxMetadataLen` and `MaxUriLen` ceilings.
+		/// Metadata already stored is kept.
+		#[pallet::call_index(49)]
+		#[pallet::weight(T::WeightInfo::set_metadata_limits())]
+		pub fn set_metadata_limits(
+			origin: OriginFor<T>,
+			limits: MetadataSizeLimits,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				limits.max_metadata <= T::MaxMetadataLen::get() &&
+					limits.max_uri <= T::MaxUriLen::get(),
+				Error::<T, I>::MetadataLimitAboveCeiling
+			);
+
+			MetadataLimits::<T, I>::put(limits);
+
+			Self::deposit_event(Event::MetadataLimitsUpdated { limits });
+
+			Ok(())
+		}
+
+		/// Set or remove an item's royalty as its collection admin
+		#[pallet::call_index(50)]
+		#[pallet::weight(T::WeightInfo::set_royalty())]
+		pub fn set_royalty(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			royalty: Option<RoyaltyInfo<T::AccountId>>,
+		) -> DispatchResult {
+			Self::ensure_collection_manager(origin, collection_id)?;
+
+			Royalties::<T, I>::set(collection_id, item_id, royalty.clone());
+
+			Self::deposit_event(Event::RoyaltySet { collection_id, item_id, royalty });
+
+			Ok(())
+		}
+
+		/// Give an NFT to another account on this chain. A non-zero `sale_price` declares a sale,
+		/// and the caller pays the item's royalty on it before the NFT moves.
+		#[pallet::call_index(51)]
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			to: T::AccountId,
+			sale_price: Option<BalanceOf<T, I>>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+
+			Self::do_transfer(from, collection_id, item_id, to, sale_price)
+		}
+
+		/// Pay the royalties escrowed for the calling location out to `to`
+		#[pallet::call_index(52)]
+		#[pallet::weight(T::WeightInfo::claim_royalties())]
+		pub fn claim_royalties(origin: OriginFor<T>, to: T::AccountId) -> DispatchResult {
+			let beneficiary = T::ReceiveOrigin::ensure_origin(origin)?;
+
+			let amount = Self::do_claim_royalties(beneficiary, &to)?;
+			Self::deposit_event(Event::RoyaltiesClaimed { beneficiary, to, amount });
+
+			Ok(())
+		}
+
+		/// Notice from the chain an item was sent to that it burned the item's wrapper
+		#[pallet::call_index(53)]
+		#[pallet::weight(T::WeightInfo::wrapper_burned())]
+		pub fn wrapper_burned(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let para_id = Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+			let (collection_id, item_id) = Self::local_ids(&location, collection_id, item_id);
+			ensure!(
+				ItemsAbroad::<T, I>::get(collection_id, item_id) == Some(para_id),
+				Error::<T, I>::WrongReserve
+			);
+
+			ItemsAbroad::<T, I>::remove(collection_id, item_id);
+			OrphanedItems::<T, I>::insert(collection_id, item_id, para_id);
+
+			Self::deposit_event(Event::OriginalOrphaned { collection_id, item_id, para_id });
+
+			Ok(())
+		}
+
+		/// Ask `para_id` who currently owns its item `collection_id`/`item_id`. The answer is
+		/// stored in `RemoteOwnership` if it arrives within `QueryTimeout` blocks. Callable by
+		/// `AdminOrigin`, or by any signed account against `OwnershipQueryDeposit`, held until
+		/// the query is answered or purged.
+		#[pallet::call_index(54)]
+		#[pallet::weight(T::WeightInfo::query_remote_ownership())]
+		pub fn query_remote_ownership(
+			origin: OriginFor<T>,
+			para_id: u32,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			let depositor = match T::AdminOrigin::ensure_origin(origin.clone()) {
+				Ok(_) => None,
+				Err(_) => Some(ensure_signed(origin)?),
+			};
+
+			let query_id =
+				Self::do_query_remote_ownership(depositor, para_id, collection_id, item_id)?;
+			Self::deposit_event(Event::OwnershipQueried {
+				query_id,
+				para_id,
+				collection_id,
+				item_id,
+			});
+
+			Ok(())
+		}
+
+		/// Answer an ownership query - called by the bridge of the chain asking
+		#[pallet::call_index(55)]
+		#[pallet::weight(T::WeightInfo::ownership_query())]
+		pub fn ownership_query(
+			origin: OriginFor<T>,
+			query_id: QueryId,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+
+			Self::do_answer_ownership_query(&location, query_id, collection_id, item_id)
+		}
+
+		/// Answer to one of our ownership queries - called by the bridge of the chain asked
+		#[pallet::call_index(56)]
+		#[pallet::weight(T::WeightInfo::report_ownership())]
+		pub fn report_ownership(
+			origin: OriginFor<T>,
+			query_id: QueryId,
+			owner: Option<T::AccountId>,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let para_id = Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			let query = Self::do_report_ownership(para_id, query_id, owner.clone())?;
+			Self::deposit_event(Event::RemoteOwnershipReported {
+				para_id,
+				collection_id: query.collection_id,
+				item_id: query.item_id,
+				owner,
+			});
+
+			Ok(())
+		}
+
+		/// Remove an ownership query left unanswered past `QueryTimeout`. Anyone may call this.
+		#[pallet::call_index(57)]
+		#[pallet::weight(T::WeightInfo::purge_ownership_query())]
+		pub fn purge_ownership_query(origin: OriginFor<T>, query_id: QueryId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::do_purge_ownership_query(query_id)?;
+			Self::deposit_event(Event::OwnershipQueryPurged { query_id });
+
+			Ok(())
+		}
+
+		/// Push an item's current metadata to a chain holding a wrapper of it. Callable by the
+		/// item's owner, the collection admin or `AdminOrigin`, at most once per
+		/// `MetadataSyncPeriod`.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::sync_metadata())]
+		pub fn sync_metadata(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			dest_para_id: u32,
+		) -> DispatchResult {
+			let is_owner = ensure_signed(origin.clone())
+				.map_or(false, |who| Self::is_owner(collection_id, item_id, &who));
+			if !is_owner {
+				Self::ensure_collection_manager(origin, collection_id)?;
+			}
+
+			Self::do_sync_metadata(collection_id, item_id, dest_para_id)
+		}
+
+		/// Stop sending a collection's metadata updates to a chain. Callable by the collection
+		/// admin or `AdminOrigin`.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::remove_metadata_subscriber())]
+		pub fn remove_metadata_subscriber(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			para_id: u32,
+		) -> DispatchResult {
+			Self::ensure_collection_manager(origin, collection_id)?;
+
+			MetadataSubscribers::<T, I>::remove(collection_id, para_id);
+
+			Self::deposit_event(Event::MetadataSubscriberRemoved { collection_id, para_id });
+
+			Ok(())
+		}
+
+		/// Replace an item's metadata as its collection admin and push the update to every chain
+		/// holding wrappers of the collection
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::update_item_metadata(T::MaxSubscribersPerUpdate::get()))]
+		pub fn update_item_metadata(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			metadata: Vec<u8>,
+			metadata_uri: Option<Vec<u8>>,
+		) -> DispatchResult {
+			Self::ensure_collection_manager(origin, collection_id)?;
+
+			Self::do_update_item_metadata(collection_id, item_id, metadata, metadata_uri)
+		}
+
+		/// Migrate a whole collection to `dest_para_id` as its collection admin. Items are sent to
+		/// their owners from `on_idle`, at most `batch_size` per block, and can't be sent
+		/// individually until the export finishes or is aborted.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::export_collection())]
+		pub fn export_collection(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			dest_para_id: u32,
+			batch_size: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_export_collection(who, collection_id, dest_para_id, batch_size)
+		}
+
+		/// Stop a collection export and return the items it sent that the destination has not
+		/// confirmed. `witness` must be at least the number of items the export has sent.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::abort_export(*witness))]
+		pub fn abort_export(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			witness: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_abort_export(who, collection_id, witness)
+		}
+
+		/// Make a collection non-transferable over the bridge, or transferable again with `None`.
+		/// Callable by `AdminOrigin` or the collection admin.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::set_collection_transferability())]
+		pub fn set_collection_transferability(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			restriction: Option<TransferRestriction>,
+		) -> DispatchResult {
+			Self::ensure_collection_manager(origin, collection_id)?;
+
+			NonTransferableCollections::<T, I>::set(collection_id, restriction);
+
+			Self::deposit_event(Event::CollectionTransferabilitySet { collection_id, restriction });
+
+			Ok(())
+		}
+
+		/// Overwrite a wrapper's metadata - called by the bridge of the chain the item came from
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::update_metadata())]
+		pub fn update_metadata(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			metadata_hash: T::Hash,
+			metadata: Vec<u8>,
+			metadata_uri: Option<Vec<u8>>,
+		) -> DispatchResult {
+			let location = T::ReceiveOrigin::ensure_origin(origin)?;
+			let from_para_id =
+				Self::sibling_para_id(&location).ok_or(Error::<T, I>::OriginMismatch)?;
+
+			Self::do_update_metadata(
+				from_para_id,
+				collection_id,
+				item_id,
+				metadata_hash,
+				metadata,
+				metadata_uri,
+			)
+		}
+
+		/// Record the XCM version a destination speaks, or clear it to use the latest version
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_destination_xcm_version())]
+		pub fn set_destination_xcm_version(
+			origin: OriginFor<T>,
+			dest_para_id: u32,
+			version: Option<XcmVersion>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			DestinationXcmVersion::<T, I>::set(dest_para_id, version);
+
+			Self::deposit_event(Event::DestinationXcmVersionSet { dest_para_id, version });
+
+			Ok(())
+		}
+
+		/// Lock an NFT for cross-chain transfer (internal function)
+		pub fn lock_nft(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			who: &T::AccountId,
+			dest_para_id: u32,
+		) -> DispatchResult {
+			// Verify the sender owns the NFT
+			let owner = Self::owner(collection_id, item_id).ok_or(Error::<T, I>::NFTNotFound)?;
+			ensure!(&owner == who, Error::<T, I>::NotOwner);
+
+			match T::LockStrategy::get() {
+				// Hand the NFT to the destination's escrow; `PendingTransfers` marks it as locked
+				LockStrategy::Detach => {
+					Self::transactor_withdraw(collection_id, item_id, who)?;
+					NFTOwners::<T, I>::insert(
+						collection_id,
+						item_id,
+						Self::escrow_account_for(dest_para_id),
+					)
+				},
+				// Keep the owner visible and only flag the NFT as locked
+				LockStrategy::Freeze(_) => BridgeLocked::<T, I>::insert(collection_id, item_id, ()),
+			}
+			let now = frame_system::Pallet::<T>::block_number();
+			BridgedAt::<T, I>::insert(collection_id, item_id, now);
+
+			Ok(())
+		}
+		
+		/// Unlock an NFT after failed cross-chain transfer (internal function)
+		pub fn unlock_nft(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			recipient: &T::AccountId,
+		) -> DispatchResult {
+			// Check if this NFT is in pending transfer state
+			ensure!(PendingTransfers::<T, I>::contains_key(collection_id, item_id), Error::<T, I>::NFTNotFound);
+
+			// Restore ownership. A frozen NFT never left its owner.
+			if !BridgeLocked::<T, I>::contains_key(collection_id, item_id) {
+				Self::transactor_deposit(collection_id, item_id, recipient)?;
+			}
+			NFTOwners::<T, I>::insert(collection_id, item_id, recipient.clone());
+
+			// Remove from pending transfers. The metadata stays since the NFT is back.
+			Self::remove_pending(collection_id, item_id);
+
+			Ok(())
+		}
+	}
+
+	// Implementation for handling NFT operations
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Check if an account owns a specific NFT. Items held in escrow have no owner here.
+		pub fn is_owner(collection_id: T::CollectionId, item_id: T::ItemId, who: &T::AccountId) -> bool {
+			if Self::is_escrow_account(who) {
+				return false
+			}
+			if let Some(owner) = Self::owner(collection_id, item_id) {
+				&owner == who
+			} else {
+				false
+			}
+		}
+
+		/// Get the owner of an NFT
+		pub fn get_owner(collection_id: T::CollectionId, item_id: T::ItemId) -> Option<T::AccountId> {
+			Self::owner(collection_id, item_id)
+		}
+
+		/// Return a pending NFT to its sender once its message can no longer be delivered
+		pub fn do_cancel_transfer(
+			owner: T::AccountId,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			let transfer = PendingTransfers::<T, I>::get(collection_id, item_id)
+				.ok_or_else(|| Self::missing_item_error(collection_id))?;
+			ensure!(transfer.sender == owner, Error::<T, I>::NotOwner);
+
+			// A message that went out may still execute on the destination, so the NFT stays
+			// locked until it is confirmed, bounced or rejected
+			ensure!(transfer.status.is_recallable(), Error::<T, I>::TransferInFlight);
+
+			Self::unlock_nft(collection_id, item_id, &owner)?;
+			Self::count_activity(|counters| &mut counters.cancelled, 1);
+
+			Self::deposit_event(Event::TransferCancelled {
+				collection_id,
+				item_id,
+				transfer_id: transfer.id,
+				owner,
+			});
+
+			Ok(())
+		}
+
+		/// Unlock recallable transfers older than `TransferTimeout`, examining as many as
+		/// `remaining_weight` allows and continuing where the previous sweep stopped
+		pub(crate) fn expire_transfers(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let limit =
+				Self::hook_capacity(remaining_weight, T::WeightInfo::on_initialize_expiries);
+			if limit == 0 {
+				return Weight::zero()
+			}
+
+			let mut iter = match ExpiryCursor::<T, I>::get() {
+				Some(key) => PendingTransfers::<T, I>::iter_from(key),
+				None => PendingTransfers::<T, I>::iter(),
+			};
+			let mut expired = Vec::new();
+			let mut scanned = 0u32;
+			while scanned < limit {
+				let Some((collection_id, item_id, transfer)) = iter.next() else { break };
+				scanned += 1;
+				// In-flight messages may still arrive and are left to the destination to settle;
+				// the rest are also dropped once the sender's deadline passed
+				let timed_out = now.saturating_sub(transfer.sent_at) >= T::TransferTimeout::get();
+				let overdue = transfer.valid_until.map_or(false, |deadline| now > deadline);
+				if transfer.status.is_recallable() && (timed_out || overdue) {
+					expired.push((collection_id, item_id, transfer));
+				}
+			}
+
+			// Start over once the whole map has been covered
+			if scanned < limit {
+				ExpiryCursor::<T, I>::kill();
+			} else {
+				ExpiryCursor::<T, I>::put(iter.last_raw_key());
+			}
+
+			// One event per item, even when several expire in the same block
+			for (collection_id, item_id, transfer) in expired {
+				if Self::unlock_nft(collection_id, item_id, &transfer.sender).is_ok() {
+					Self::count_activity(|counters| &mut counters.expired, 1);
+					Self::deposit_event(Event::TransferExpired {
+						collection_id,
+						item_id,
+						dest_para_id: Self::sibling_para_id(&transfer.dest).unwrap_or_default(),
+						sender: transfer.sender,
+					});
+				}
+			}
+
+			T::WeightInfo::on_initialize_expiries(scanned)
+		}
+
+		/// Emit `TransferAging` for pending transfers older than `WarnAfter` not reported yet,
+		/// scanning as many as `remaining_weight` allows from where the last scan stopped
+		pub(crate) fn warn_aging_transfers(
+			now: BlockNumberFor<T>,
+			remaining_weight: Weight,
+		) -> Weight {
+			let limit = Self::hook_capacity(remaining_weight, T::WeightInfo::on_idle_aging_scan);
+			if limit == 0 {
+				return Weight::zero()
+			}
+
+			let mut iter = match AgingCursor::<T, I>::get() {
+				Some(key) => PendingTransfers::<T, I>::iter_from(key),
+				None => PendingTransfers::<T, I>::iter(),
+			};
+			let mut scanned = 0u32;
+			while scanned < limit {
+				let Some((collection_id, item_id, transfer)) = iter.next() else { break };
+				scanned += 1;
+				let age = now.saturating_sub(transfer.sent_at);
+				if age >= T::WarnAfter::get() && !AgingWarned::<T, I>::contains_key(transfer.id) {
+					AgingWarned::<T, I>::insert(transfer.id, ());
+					Self::deposit_event(Event::TransferAging { collection_id, item_id, age });
+				}
+			}
+
+			if scanned < limit {
+				AgingCursor::<T, I>::kill();
+			} else {
+				AgingCursor::<T, I>::put(iter.last_raw_key());
+			}
+
+			T::WeightInfo::on_idle_aging_scan(scanned)
+		}
+
+		/// Most items a block hook costing `weight(n)` for `n` items can handle in `remaining`
+		pub(crate) fn hook_capacity(remaining: Weight, weight: impl Fn(u32) -> Weight) -> u32 {
+			let base = weight(0);
+			let Some(available) = remaining.checked_sub(&base) else { return 0 };
+			let per_item = weight(1).saturating_sub(base);
+			available.checked_div_per_component(&per_item).unwrap_or(0).min(u32::MAX.into()) as u32
+		}
+
+		/// The pending transfer behind a stale report, if the report is valid
+		pub(crate) fn ensure_reportable(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+		) -> Result<TransferInfo<T::AccountId, BlockNumberFor<T>>, DispatchError> {
+			let transfer = PendingTransfers::<T, I>::get(collection_id, item_id)
+				.filter(|t| t.id == transfer_id)
+				.ok_or(Error::<T, I>::NotStale)?;
+			ensure!(!StaleReported::<T, I>::contains_key(transfer_id), Error::<T, I>::NotStale);
+			ensure!(
+				Self::is_stale(&transfer, frame_system::Pallet::<T>::block_number()),
+				Error::<T, I>::NotStale
+			);
+			Ok(transfer)
+		}
+
+		/// Remove the pending transfer of an item by its raw key, along with its staged metadata
+		/// and deposit. The item goes to `refund_to`, or is dropped with its metadata. Returns
+		/// the transfer's id, if the head of its record still decoded.
+		pub(crate) fn do_kill_pending(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			refund_to: Option<&T::AccountId>,
+		) -> Result<Option<TransferId>, DispatchError> {
+			let key = PendingTransfers::<T, I>::hashed_key_for(collection_id, item_id);
+			ensure!(unhashed::exists(&key), Error::<T, I>::NFTNotFound);
+
+			if let Some(staged) = StagedMetadata::<T, I>::take(collection_id, item_id) {
+				Self::release_staging_deposit(&staged)?;
+			}
+			match refund_to {
+				Some(recipient) => {
+					if !BridgeLocked::<T, I>::contains_key(collection_id, item_id) {
+						Self::transactor_deposit(collection_id, item_id, recipient)?;
+					}
+					NFTOwners::<T, I>::insert(collection_id, item_id, recipient.clone());
+				},
+				None => {
+					NFTOwners::<T, I>::remove(collection_id, item_id);
+					Self::clear_item_metadata(collection_id, item_id);
+				},
+			}
+
+			if PendingTransfers::<T, I>::try_get(collection_id, item_id).is_ok() {
+				return Ok(Self::remove_pending(collection_id, item_id).map(|transfer| transfer.id))
+			}
+			let raw = unhashed::get_raw(&key).unwrap_or_default();
+			unhashed::kill(&key);
+			// The leading fields often survive a broken record and are all the indexes need
+			let head = <(TransferId, T::AccountId, MultiLocation)>::decode(&mut &raw[..]).ok();
+			let Some((transfer_id, sender, dest)) = head else {
+				// Without them the destination is unknown, so every destination's index is searched
+				let indexed: Vec<_> = PendingByDestination::<T, I>::iter_keys()
+					.filter(|(_, key)| *key == (collection_id, item_id))
+					.collect();
+				for (dest_para_id, key) in indexed {
+					PendingByDestination::<T, I>::remove(dest_para_id, key);
+				}
+				BridgeLocked::<T, I>::remove(collection_id, item_id);
+				BridgedAt::<T, I>::remove(collection_id, item_id);
+				return Ok(None)
+			};
+			// The status didn't decode, so the queue tells whether the transfer was waiting in it
+			let queued = Self::sibling_para_id(&dest).map_or(false, |dest_para_id| {
+				OutboundQueue::<T, I>::get(dest_para_id).contains(&transfer_id)
+			});
+			let delivered = !queued && !FailedSends::<T, I>::contains_key(transfer_id);
+			Self::release_pending(
+				collection_id,
+				item_id,
+				transfer_id,
+				&sender,
+				&dest,
+				queued,
+				delivered,
+			);
+
+			Ok(Some(transfer_id))
+		}
+
+		/// Record a pending transfer and index it by destination
+		pub(crate) fn insert_pending(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer: TransferInfo<T::AccountId, BlockNumberFor<T>>,
+		) {
+			if let Some(dest_para_id) = Self::sibling_para_id(&transfer.dest) {
+				PendingByDestination::<T, I>::insert(dest_para_id, (collection_id, item_id), ());
+			}
+			PendingPerAccount::<T, I>::mutate(&transfer.sender, |count| {
+				*count = count.saturating_add(1)
+			});
+			PendingTransfers::<T, I>::insert(collection_id, item_id, transfer);
+		}
+
+		/// Remove a pending transfer and its destination index entry. Every settlement path
+		/// goes through here.
+		pub(crate) fn remove_pending(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> Option<TransferInfo<T::AccountId, BlockNumberFor<T>>> {
+			let transfer = PendingTransfers::<T, I>::take(collection_id, item_id)?;
+			let queued = transfer.status == TransferStatus::Queued;
+			Self::release_pending(
+				collection_id,
+				item_id,
+				transfer.id,
+				&transfer.sender,
+				&transfer.dest,
+				queued,
+				transfer.status.is_delivered(),
+			);
+			Some(transfer)
+		}
+
+		/// Drop the indexes and counters of a pending transfer whose record is already gone
+		fn release_pending(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			transfer_id: TransferId,
+			sender: &T::AccountId,
+			dest: &MultiLocation,
+			queued: bool,
+			delivered: bool,
+		) {
+			PendingPerAccount::<T, I>::mutate_exists(sender, |count| {
+				*count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+			});
+			if let Some(dest_para_id) = Self::sibling_para_id(dest) {
+				PendingByDestination::<T, I>::remove(dest_para_id, (collection_id, item_id));
+				if queued {
+					OutboundQueue::<T, I>::mutate(dest_para_id, |queue| {
+						let Some(pos) = queue.iter().position(|id| *id == transfer_id) else {
+							return
+						};
+						queue.remove(pos);
+						PriorityQueued::<T, I>::mutate(dest_para_id, |head| {
+							if (pos as u32) < *head {
+								*head -= 1;
+							}
+						});
+					});
+				} else if delivered {
+					InFlightCount::<T, I>::mutate(dest_para_id, |count| *count = count.saturating_sub(1));
+				}
+			}
+			QueuedSends::<T, I>::remove(transfer_id);
+			StaleReported::<T, I>::remove(transfer_id);
+			AgingWarned::<T, I>::remove(transfer_id);
+			FailedSends::<T, I>::remove(transfer_id);
+			BridgeLocked::<T, I>::remove(collection_id, item_id);
+			BridgedAt::<T, I>::remove(collection_id, item_id);
+		}
+
+		/// Settle a confirmed outbound transfer: the NFT now lives on the destination
+		pub(crate) fn complete_outbound(collection_id: T::CollectionId, item_id: T::ItemId) {
+			let Some(transfer) = Self::remove_pending(collection_id, item_id) else { return };
+			let dest_para_id = Self::sibling_para_id(&transfer.dest);
+			if let Some(dest_para_id) = dest_para_id {
+				ItemsAbroad::<T, I>::insert(collection_id, item_id, dest_para_id);
+			}
+			Self::note_wrapper_removed(collection_id, item_id);
+			Self::clear_item_history(collection_id, item_id);
+			match T::LockStrategy::get() {
+				// The NFT left escrow for the destination
+				LockStrategy::Detach => {
+					NFTOwners::<T, I>::remove(collection_id, item_id);
+					Self::backend_burn(collection_id, item_id);
+				},
+				LockStrategy::Freeze(CompletionMode::Escrow) => {
+					let escrow = Self::escrow_account_for(dest_para_id.unwrap_or_default());
+					NFTOwners::<T, I>::insert(collection_id, item_id, escrow)
+				},
+				LockStrategy::Freeze(CompletionMode::Burn) => {
+					NFTOwners::<T, I>::remove(collection_id, item_id)
+				},
+			}
+		}
+
+		/// Whether the bridge currently holds an NFT, so nothing else may move it: it is pending
+		/// or queued outbound, frozen in place, or in escrow for another pallet
+		pub fn is_bridge_locked(collection_id: &T::CollectionId, item_id: &T::ItemId) -> bool {
+			PendingTransfers::<T, I>::contains_key(collection_id, item_id) ||
+				BridgeLocked::<T, I>::contains_key(collection_id, item_id) ||
+				SwapEscrow::<T, I>::contains_key(collection_id, item_id) ||
+				EscrowedItems::<T, I>::contains_key(collection_id, item_id)
+		}
+
+		/// Refuse to move an NFT the bridge holds. Every path changing an owner here checks it.
+		pub(crate) fn ensure_not_bridge_locked(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> DispatchResult {
+			ensure!(
+				!Self::is_bridge_locked(&collection_id, &item_id),
+				Error::<T, I>::TransferAlreadyPending
+			);
+			Ok(())
+		}
+
+		/// `NFTNotFound` for a collection known here, `CollectionNotFound` otherwise. Items written
+		/// to `NFTOwners` by other pallets also make their collection known.
+		pub(crate) fn missing_item_error(collection_id: T::CollectionId) -> Error<T, I> {
+			let known = KnownCollections::<T, I>::contains_key(collection_id) ||
+				NFTOwners::<T, I>::iter_key_prefix(collection_id).next().is_some();
+			if known {
+				Error::<T, I>::NFTNotFound
+			} else {
+				Error::<T, I>::CollectionNotFound
+			}
+		}
+
+		/// The account managing a registered collection's bridge settings
+		pub fn collection_admin(collection_id: T::CollectionId) -> Option<T::AccountId> {
+			Collections::<T, I>::get(collection_id).map(|info| info.admin)
+		}
+
+		/// Remember a processed delivery id, forgetting the oldest once `DeliveryDedupWindow` ids
+		/// are kept for the source chain
+		pub(crate) fn note_delivery(from_para_id: u32, delivery_id: [u8; 32]) {
+			ProcessedDeliveries::<T, I>::mutate(from_para_id, |seen| {
+				if !seen.is_empty() && seen.len() as u32 >= T::DeliveryDedupWindow::get() {
+					seen.remove(0);
+				}
+				let _ = seen.try_push(delivery_id);
+			});
+		}
+
+		/// Record a failed inbound delivery, forgetting the oldest once `MaxFailureRecords` are
+		/// kept for the source chain
+		pub(crate) fn note_inbound_failure(
+			from_para_id: u32,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			reason: DispatchError,
+		) {
+			let at = frame_system::Pallet::<T>::block_number();
+			RecentInboundFailures::<T, I>::mutate(from_para_id, |failures| {
+				if !failures.is_empty() && failures.len() as u32 >= T::MaxFailureRecords::get() {
+					failures.remove(0);
+				}
+				let _ = failures.try_push(InboundFailure { collection_id, item_id, reason, at });
+			});
+		}
+
+		/// Record and announce an inbound delivery that was refused
+		pub(crate) fn note_refused_delivery(
+			from_para_id: u32,
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			reason: DispatchError,
+		) {
+			Self::note_inbound_failure(from_para_id, collection_id, item_id, reason);
+			Self::deposit_event(Event::InboundDeliveryFailed {
+				from_para_id,
+				collection_id,
+				item_id,
+				reason,
+			});
+		}
+
+		/// Failed inbound deliveries recorded for `para_id`, oldest first
+		pub fn inbound_failures(para_id: u32) -> Vec<InboundFailureOf<T, I>> {
+			RecentInboundFailures::<T, I>::get(para_id).into_inner()
+		}
+
+		/// Reject sends while governance has the bridge paused
+		pub(crate) fn ensure_not_paused() -> DispatchResult {
+			ensure!(!BridgePaused::<T, I>::get(), Error::<T, I>::BridgePaused);
+			Ok(())
+		}
+
+		/// Fail if `new` more pending transfers would put `who` above `MaxPendingPerAccount`
+		pub(crate) fn ensure_pending_capacity(who: &T::AccountId, new: u32) -> DispatchResult {
+			let pending = PendingPerAccount::<T, I>::get(who).saturating_add(new);
+			ensure!(pending <= T::MaxPendingPerAccount::get(), Error::<T, I>::TooManyPending);
+			Ok(())
+		}
+
+		/// Reject a send signed with a `valid_until` block that has already passed
+		pub(crate) fn ensure_before_deadline(
+			valid_until: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				valid_until.map_or(true, |deadline| now <= deadline),
+				Error::<T, I>::TransferDeadlineExpired
+			);
+			Ok(())
+		}
+
+		/// Reject collections that were not onboarded, unless `PermissionlessCollections` is set
+		pub(crate) fn ensure_registered(collection_id: T::CollectionId) -> DispatchResult {
+			ensure!(
+				T::PermissionlessCollections::get() || Collections::<T, I>::contains_key(collection_id),
+				Error::<T, I>::CollectionNotRegistered
+			);
+			Ok(())
+		}
+
+		/// Ensure `origin` is `AdminOrigin` or signed by the collection's admin
+		pub(crate) fn ensure_collection_manager(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+		) -> DispatchResult {
+			if T::AdminOrigin::ensure_origin(origin.clone()).is_ok() {
+				return Ok(())
+			}
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::collection_admin(collection_id) == Some(who),
+				Error::<T, I>::NotCollectionAdmin
+			);
+			Ok(())
+		}
+
+		/// The pallet's own account, admin of collections it registers on its own
+		pub fn escrow_account() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// The account holding NFTs pending to `dest_para_id`, and those that left for it under
+		/// `CompletionMode::Escrow`
+		pub fn escrow_account_for(dest_para_id: u32) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(dest_para_id)
+		}
+
+		/// Whether `who` is the pallet's account or one of its per-destination escrow accounts
+		pub fn is_escrow_account(who: &T::AccountId) -> bool {
+			*who == Self::escrow_account() ||
+				PalletId::try_from_sub_account::<u32>(who)
+					.map_or(false, |(id, _)| id == T::PalletId::get())
+		}
+
+		/// Whether an item held by `escrow` is accounted for: pending to or confirmed on the
+		/// destination that account escrows for
+		pub(crate) fn escrow_accounted_for(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			escrow: &T::AccountId,
+		) -> bool {
+			PendingTransfers::<T, I>::get(collection_id, item_id)
+				.and_then(|transfer| Self::sibling_para_id(&transfer.dest))
+				.or_else(|| ItemsAbroad::<T, I>::get(collection_id, item_id))
+				.map_or(false, |dest_para_id| *escrow == Self::escrow_account_for(dest_para_id))
+		}
+
+		/// Up to `limit` items held in `dest_para_id`'s escrow account
+		pub fn escrowed_for(dest_para_id: u32, limit: u32) -> Vec<(T::CollectionId, T::ItemId)> {
+			let escrow = Self::escrow_account_for(dest_para_id);
+			NFTOwners::<T, I>::iter()
+				.filter(|(_, _, owner)| *owner == escrow)
+				.map(|(collection_id, item_id, _)| (collection_id, item_id))
+				.take(limit as usize)
+				.collect()
+		}
+
+		/// Up to `limit` items sitting in escrow for an outbound transfer, with the destination
+		/// they are escrowed for. Pages through `PendingByDestination`, resuming after
+		/// `start_after`, the last entry of the previous page.
+		pub fn escrowed_items(
+			start_after: Option<(T::CollectionId, T::ItemId, u32)>,
+			limit: u32,
+		) -> Vec<(T::CollectionId, T::ItemId, u32)> {
+			let iter = match start_after {
+				Some((collection_id, item_id, dest_para_id)) => {
+					let key = PendingByDestination::<T, I>::hashed_key_for(
+						dest_para_id,
+						(collection_id, item_id),
+					);
+					PendingByDestination::<T, I>::iter_from(key)
+				},
+				None => PendingByDestination::<T, I>::iter(),
+			};
+			iter.filter_map(|(dest_para_id, (collection_id, item_id), ())| {
+				let escrow = Self::escrow_account_for(dest_para_id);
+				(NFTOwners::<T, I>::get(collection_id, item_id) == Some(escrow))
+					.then_some((collection_id, item_id, dest_para_id))
+			})
+			.take(limit as usize)
+			.collect()
+		}
+
+		/// Examine up to `limit` owner entries from the destination's reconciliation cursor,
+		/// reporting escrowed items that are not accounted for. Returns how many were reported.
+		pub(crate) fn do_reconcile_escrow(dest_para_id: u32, limit: u32) -> u32 {
+			let escrow = Self::escrow_account_for(dest_para_id);
+			let mut iter = match EscrowReconcileCursor::<T, I>::get(dest_para_id) {
+				Some(key) => NFTOwners::<T, I>::iter_from(key),
+				None => NFTOwners::<T, I>::iter(),
+			};
+
+			let mut discrepancies = 0u32;
+			let mut scanned = 0u32;
+			while scanned < limit {
+				let Some((collection_id, item_id, owner)) = iter.next() else { break };
+				scanned += 1;
+
+				if owner == escrow && !Self::escrow_accounted_for(collection_id, item_id, &owner) {
+					discrepancies += 1;
+					Self::deposit_event(Event::EscrowDiscrepancy {
+						collection_id,
+						item_id,
+						dest_para_id,
+					});
+				}
+			}
+
+			// Start over once the whole map has been covered
+			if scanned < limit {
+				EscrowReconcileCursor::<T, I>::remove(dest_para_id);
+			} else {
+				EscrowReconcileCursor::<T, I>::insert(dest_para_id, iter.last_raw_key());
+			}
+			discrepancies
+		}
+
+		/// Check up to `limit` items of a collection against the NFT backend. Returns the number
+		/// of mismatches and the last item checked if the collection may have more.
+		pub(crate) fn do_check_consistency(
+			collection_id: T::CollectionId,
+			start_after: Option<T::ItemId>,
+			limit: u32,
+		) -> (u32, Option<T::ItemId>) {
+			let mut iter = match start_after {
+				Some(item_id) => NFTOwners::<T, I>::iter_prefix_from(
+					collection_id,
+					NFTOwners::<T, I>::hashed_key_for(collection_id, item_id),
+				),
+				None => NFTOwners::<T, I>::iter_prefix(collection_id),
+			};
+
+			let mut mismatches = 0u32;
+			let mut scanned = 0u32;
+			let mut last = None;
+			while scanned < limit {
+				let Some((item_id, bridge_owner)) = iter.next() else {
+					return (mismatches, None)
+				};
+				scanned += 1;
+				last = Some(item_id);
+
+				if PendingTransfers::<T, I>::contains_key(collection_id, item_id) {
+					continue
+				}
+				match T::Nfts::owner(&collection_id, &item_id) {
+					Some(backend_owner) if backend_owner != bridge_owner => {
+						mismatches += 1;
+						Self::deposit_event(Event::OwnershipMismatch {
+							collection_id,
+							item_id,
+							bridge_owner,
+							backend_owner,
+						});
+					},
+					_ => {},
+				}
+			}
+			(mismatches, last)
+		}
+
+		/// Store an item's metadata and URIs, charging the bytes to its collection's quota
+		pub(crate) fn set_item_metadata(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+			metadata: Vec<u8>,
+			metadata_uris: Option<ItemUris>,
+		) -> DispatchResult {
+			let old = Self::item_metadata_bytes(collection_id, item_id);
+			// Without new URIs the stored ones are kept
+			let metadata_uris = metadata_uris.map(Self::bound_uris).transpose()?;
+			let uri_len = match &metadata_uris {
+				Some(uris) => uris.iter().map(|(_, uri)| uri.len()).sum(),
+				None => Self::stored_uri_bytes(collection_id, item_id),
+			};
+			let new = (metadata.len() + uri_len) as u64;
+			let used = CollectionMetadataBytes::<T, I>::get(collection_id).saturating_sub(old);
+			let total = used.saturating_add(new);
+
+			// Shrinking is always allowed, even for collections already over a lowered quota
+			if new > old {
+				ensure!(
+					total <= Self::collection_metadata_quota(collection_id),
+					Error::<T, I>::CollectionMetadataQuotaExceeded
+				);
+			}
+
+			NFTMetadata::<T, I>::insert(collection_id, item_id, metadata);
+			if let Some(uris) = metadata_uris {
+				MetadataUris::<T, I>::insert(collection_id, item_id, uris);
+			}
+			CollectionMetadataBytes::<T, I>::insert(collection_id, total);
+
+			Ok(())
+		}
+
+		/// Remove an item's metadata and URIs and release their bytes from the collection quota
+		pub(crate) fn clear_item_metadata(collection_id: T::CollectionId, item_id: T::ItemId) {
+			let bytes = Self::item_metadata_bytes(collection_id, item_id);
+			NFTMetadata::<T, I>::remove(collection_id, item_id);
+			MetadataUris::<T, I>::remove(collection_id, item_id);
+			CollectionMetadataBytes::<T, I>::mutate_exists(collection_id, |used| {
+				*used = used.map(|used| used.saturating_sub(bytes)).filter(|used| *used > 0);
+			});
+		}
+
+		/// Examine up to `limit` metadata entries from the purge cursor and clear the orphaned
+		/// ones, returning how many were removed
+		pub(crate) fn do_purge_orphaned_metadata(limit: u32) -> u32 {
+			let mut iter = match MetadataPurgeCursor::<T, I>::get() {
+				Some(key) => NFTMetadata::<T, I>::iter_keys_from(key),
+				None => NFTMetadata::<T, I>::iter_keys(),
+			};
+
+			let mut orphans = Vec::new();
+			let mut scanned = 0u32;
+			while scanned < limit {
+				let Some((collection_id, item_id)) = iter.next() else { break };
+				scanned += 1;
+
+				// An item abroad keeps its metadata for updates to its wrapper and its return
+				let orphaned = !NFTOwners::<T, I>::contains_key(collection_id, item_id) &&
+					!PendingTransfers::<T, I>::contains_key(collection_id, item_id) &&
+					!ItemsAbroad::<T, I>::contains_key(collection_id, item_id);
+				if orphaned {
+					orphans.push((collection_id, item_id));
+				}
+			}
+
+			// Start over once the whole map has been covered
+			if scanned < limit {
+				MetadataPurgeCursor::<T, I>::kill();
+			} else {
+				MetadataPurgeCursor::<T, I>::put(iter.last_raw_key());
+			}
+
+			for (collection_id, item_id) in &orphans {
+				Self::clear_item_metadata(*collection_id, *item_id);
+			}
+			orphans.len() as u32
+		}
+
+		/// Bytes of metadata and URIs stored for an item
+		fn item_metadata_bytes(collection_id: T::CollectionId, item_id: T::ItemId) -> u64 {
+			let metadata = NFTMetadata::<T, I>::decode_len(collection_id, item_id).unwrap_or(0);
+			(metadata + Self::stored_uri_bytes(collection_id, item_id)) as u64
+		}
+
+		/// Bytes of the URIs stored for an item
+		fn stored_uri_bytes(collection_id: T::CollectionId, item_id: T::ItemId) -> usize {
+			MetadataUris::<T, I>::get(collection_id, item_id)
+				.map_or(0, |uris| uris.iter().map(|(_, uri)| uri.len()).sum())
+		}
+
+		/// Bound a URI list for storage, refusing more than `MaxUrisPerItem` entries, two of
+		/// one kind or one longer than `MaxUriLen`
+		pub(crate) fn bound_uris(uris: ItemUris) -> Result<MetadataUrisOf<T, I>, DispatchError> {
+			let mut bounded = MetadataUrisOf::<T, I>::default();
+			for (kind, uri) in uris {
+				ensure!(bounded.iter().all(|(k, _)| *k != kind), Error::<T, I>::DuplicateMediaKind);
+				let uri = uri.try_into().map_err(|_| Error::<T, I>::MetadataTooLong)?;
+				bounded.try_push((kind, uri)).map_err(|_| Error::<T, I>::TooManyUris)?;
+			}
+			Ok(bounded)
+		}
+
+		/// Longest and total length of a URI list
+		pub(crate) fn uri_lengths(uris: &Option<ItemUris>) -> (usize, usize) {
+			uris.iter().flatten().fold((0, 0), |(longest, total), (_, uri)| {
+				(longest.max(uri.len()), total + uri.len())
+			})
+		}
+
+		/// Weight of sending or receiving each item of a batch, `weight` taking an item's metadata
+		/// length and attribute count
+		pub(crate) fn batch_weight(
+			items: &[BatchItem<T::CollectionId, T::ItemId>],
+			weight: impl Fn(u32, u32) -> Weight,
+		) -> Weight {
+			items.iter().fold(Weight::zero(), |total, item| {
+				let metadata_len = item.metadata.len() as u32;
+				let uri_len = item.metadata_uri.as_ref().map_or(0, |uri| uri.len() as u32);
+				total.saturating_add(weight(metadata_len, attribute_count(metadata_len, uri_len)))
+			})
+		}
+
+		/// An item's `Primary` URI, the single URI of earlier versions
+		pub fn nft_metadata_uri(
+			collection_id: T::CollectionId,
+			item_id: T::ItemId,
+		) -> Option<Vec<u8>> {
+			MetadataUris::<T, I>::get(collection_id, item_id)?
+				.into_iter()
+				.find(|(kind, _)| *kind == MediaKind::Primary)
+				.map(|(_, uri)| uri.into_inner())
+		}
+
+		/// The fee for bridging an item of `collection_id` carrying `payload_len` bytes of metadata
+		/// and URI, or `None` if the collection's fee is waived
+		pub fn bridge_fee(
+			collection_id: T::CollectionId,
+			payload_len: u32,
+		) -> Option<BalanceOf<T, I>> {
+			let base = match CollectionFeeOverride::<T, I>::get(collection_id).unwrap_or_default() {
+				FeePolicy::Waived => return None,
+				FeePolicy::Fixed(fee) => fee,
+				FeePolicy::Default => T::BridgeFee::get(),
+			};
+			let per_byte = T::FeePerMetadataByte::get().saturating_mul(payload_len.into());
+			Some(base.saturating_add(per_byte))
+		}
+
+		/// Charge `who` the bridging fee for an item of `collection_id`, returning the amount
+		/// charged. Waived fees touch no balances at all.
+		pub(crate) fn charge_bridge_fee(
+			who: &T::AccountId,
+			collection_id: T::CollectionId,
+			payload_len: u32,
+		) -> Result<BalanceOf<T, I>, DispatchError> {
+			let Some(fee) = Self::bridge_fee(collection_id, payload_len) else {
+				return Ok(Zero::zero())
+			};
+			let destination = T::FeeDestination::get();
+			T::Currency::transfer(who, &destination, fee, Preservation::Preserve)?;
+			Ok(fee)
+		}
+
+		/// The metadata byte quota in force for a collection
+		pub fn collection_metadata_quota(collection_id: T::CollectionId) -> u64 {
+			CollectionMetadataQuota::<T, I>::get(collection_id)
+				.unwrap_or_else(T::MaxCollectionMetadataBytes::get)
+		}
+
+		/// Up to `limit` items currently in flight to `dest_para_id`
+		pub fn pending_to(dest_para_id: u32, limit: u32) -> Vec<(T::CollectionId, T::ItemId)> {
+			PendingByDestination::<T, I>::iter_key_prefix(dest_para_id)
+				.take(limit as usize)
+				.collect()
+		}
+
+		/// Check that `PendingTransfers` and `PendingByDestination` agree, that everything in
+		/// escrow is pending or abroad, and that escrow holds exactly the items pending under
+		/// `LockStrategy::Detach`
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), &'static str> {
+			let mut escrowed = 0u32;
+			for (collection_id, item_id, owner) in NFTOwners::<T, I>::iter() {
+				if !Self::is_escrow_account(&owner) {
+					continue
+				}
+				ensure!(
+					Self::escrow_accounted_for(collection_id, item_id, &owner),
+					"escrowed item without a pending transfer"
+				);
+				if !ItemsAbroad::<T, I>::contains_key(collection_id, item_id) {
+					escrowed += 1;
+				}
+			}
+			let escrowing = match T::LockStrategy::get() {
+				LockStrategy::Detach => PendingTransfers::<T, I>::iter_keys().count() as u32,
+				LockStrategy::Freeze(_) => 0,
+			};
+			ensure!(escrowed == escrowing, "escrow does not match the pending transfers");
+			for (collection_id, item_id, transfer) in PendingTransfers::<T, I>::iter() {
+				let dest_para_id =
+					Self::sibling_para_id(&transfer.dest).ok_or("pending transfer to a non-sibling")?;
+				ensure!(
+					PendingByDestination::<T, I>::contains_key(
+						dest_para_id,
+						(collection_id, item_id)
+					),
+					"pending transfer missing from the destination index"
+				);
+			}
+			for (dest_para_id, (collection_id, item_id), ()) in
+				PendingByDestination::<T, I>::iter()
+			{
+				let transfer = PendingTransfers::<T, I>::get(collection_id, item_id)
+					.ok_or("destination index entry without a pending transfer")?;
+				ensure!(
+					Self::sibling_para_id(&transfer.dest) == Some(dest_para_id),
+					"destination index entry points at the wrong destination"
+				);
+			}
+			Ok(())
+		}
+
+		/// Extract the para id from a sibling parachain location
+		pub fn sibling_para_id(location: &MultiLocation) -> Option<u32> {
+			match location {
+				MultiLocation { parents: 1, interior: X1(Parachain(id)) } => Some(*id),
+				_ => None,
+			}
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> NftBridging<T::AccountId, T::CollectionId, T::ItemId>
+	for Pallet<T, I>
+{
+	fn send(
+		owner: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		dest_para_id: u32,
+		metadata: sp_std::vec::Vec<u8>,
+		metadata_uri: Option<sp_std::vec::Vec<u8>>,
+	) -> Result<TransferId, sp_runtime::DispatchError> {
+		Self::do_xcm_transfer_nft(
+			owner,
+			collection_id,
+			item_id,
+			dest_para_id,
+			metadata,
+			combine_uris(metadata_uri, Vec::new()),
+			None,
+			ExecutionLimit::Default,
+			false,
+			None,
+		)
+	}
+
+	fn cancel(
+		owner: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> sp_runtime::DispatchResult {
+		Self::do_cancel_transfer(owner, collection_id, item_id)
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnNftBurned<T::CollectionId, T::ItemId> for Pallet<T, I> {
+	fn on_nft_burned(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> sp_runtime::DispatchResult {
+		Self::on_item_burned(collection_id, item_id)
+	}
+}
+
+/// Matches NFTs the bridge currently holds. Plug it into an NFT pallet's locker or a runtime
+/// call filter to keep other pallets from moving bridged items.
+pub struct BridgeLockFilter<T, I = ()>(sp_std::marker::PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> frame_support::traits::Contains<(T::CollectionId, T::ItemId)>
+	for BridgeLockFilter<T, I>
+{
+	fn contains((collection_id, item_id): &(T::CollectionId, T::ItemId)) -> bool {
+		Pallet::<T, I>::is_bridge_locked(collection_id, item_id)
 	}
-}
\ No newline at end of file
+}
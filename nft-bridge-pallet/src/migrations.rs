@@ -0,0 +1,407 @@
+//! Storage migrations of the NFT bridge pallet
+
+use crate::*;
+use codec::{Decode, Encode};
+use frame_support::{
+	storage::unhashed,
+	storage_alias,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+	Blake2_128Concat,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+/// Version 1: `PendingTransfers` destinations move from XCM v2 to v3 `MultiLocation`s
+pub mod v1 {
+	use super::*;
+
+	/// `TransferInfo` as stored before version 1
+	#[derive(Decode, Encode)]
+	pub(crate) struct OldTransferInfo<AccountId, BlockNumber> {
+		pub id: TransferId,
+		pub sender: AccountId,
+		pub dest: xcm::v2::MultiLocation,
+		pub sent_at: BlockNumber,
+		pub status: TransferStatus<BlockNumber>,
+		pub retry_count: u32,
+	}
+
+	type OldTransferInfoOf<T> =
+		OldTransferInfo<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+	/// Re-encode every pending transfer's destination as an XCM v3 location. Entries that don't
+	/// decode in the old format or have no v3 equivalent are logged and left as they are, so
+	/// governance can clear them.
+	pub struct MigrateToV1<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV1<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() >= 1 {
+				log::info!(target: "runtime::nft-bridge", "MigrateToV1 already applied");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let keys: Vec<_> = PendingTransfers::<T, I>::iter_keys().collect();
+			let mut migrated = 0u64;
+			for (collection_id, item_id) in keys.iter().copied() {
+				let key = PendingTransfers::<T, I>::hashed_key_for(collection_id, item_id);
+				let new = unhashed::get_raw(&key)
+					.and_then(|raw| OldTransferInfoOf::<T>::decode(&mut &raw[..]).ok())
+					.and_then(|old| {
+						Some(TransferInfo {
+							id: old.id,
+							sender: old.sender,
+							dest: old.dest.try_into().ok()?,
+							sent_at: old.sent_at,
+							status: old.status,
+							retry_count: old.retry_count,
+							valid_until: None,
+						})
+					});
+				match new {
+					Some(transfer) => {
+						PendingTransfers::<T, I>::insert(collection_id, item_id, transfer);
+						migrated += 1;
+					},
+					None => log::warn!(
+						target: "runtime::nft-bridge",
+						"MigrateToV1: skipping unconvertible pending transfer {:?}/{:?}",
+						collection_id,
+						item_id,
+					),
+				}
+			}
+
+			StorageVersion::new(1).put::<Pallet<T, I>>();
+			log::info!(
+				target: "runtime::nft-bridge",
+				"MigrateToV1: migrated {} of {} pending transfers",
+				migrated,
+				keys.len(),
+			);
+			T::DbWeight::get().reads_writes(1 + 2 * keys.len() as u64, 1 + migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok((PendingTransfers::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			frame_support::ensure!(
+				PendingTransfers::<T, I>::iter_keys().count() as u32 == before,
+				"pending transfers were lost"
+			);
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 1,
+				"storage version not updated"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Version 2: the single `NFTMetadataUri` of each item becomes its `Primary` entry in
+/// `MetadataUris`
+pub mod v2 {
+	use super::*;
+
+	/// URIs as stored before version 2
+	#[storage_alias]
+	pub(crate) type NFTMetadataUri<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as Config<I>>::CollectionId,
+		Blake2_128Concat,
+		<T as Config<I>>::ItemId,
+		Vec<u8>,
+	>;
+
+	/// Move every item's URI into `MetadataUris`. A URI longer than `MaxUriLen` is logged and
+	/// dropped.
+	pub struct MigrateToV2<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV2<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 1 {
+				log::info!(target: "runtime::nft-bridge", "MigrateToV2 not applicable");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut moved = 0u64;
+			let mut dropped = 0u64;
+			for (collection_id, item_id, uri) in NFTMetadataUri::<T, I>::drain() {
+				match Pallet::<T, I>::bound_uris(vec![(MediaKind::Primary, uri)]) {
+					Ok(uris) => {
+						MetadataUris::<T, I>::insert(collection_id, item_id, uris);
+						moved += 1;
+					},
+					Err(_) => {
+						log::warn!(
+							target: "runtime::nft-bridge",
+							"MigrateToV2: dropping overlong URI of {:?}/{:?}",
+							collection_id,
+							item_id,
+						);
+						dropped += 1;
+					},
+				}
+			}
+
+			StorageVersion::new(2).put::<Pallet<T, I>>();
+			log::info!(
+				target: "runtime::nft-bridge",
+				"MigrateToV2: moved {} URIs, dropped {}",
+				moved,
+				dropped,
+			);
+			let entries = moved + dropped;
+			T::DbWeight::get().reads_writes(1 + entries, 1 + entries + moved)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok((NFTMetadataUri::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			frame_support::ensure!(
+				NFTMetadataUri::<T, I>::iter_keys().next().is_none(),
+				"legacy URIs left behind"
+			);
+			frame_support::ensure!(
+				MetadataUris::<T, I>::iter_keys().count() as u32 <= before,
+				"more URI lists than legacy URIs"
+			);
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 2,
+				"storage version not updated"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Version 3: staging deposits move from the escrow account to a `MetadataDeposit` hold on
+/// their depositor
+pub mod v3 {
+	use super::*;
+	use frame_support::traits::{
+		fungible::{Mutate as _, MutateHold as _},
+		tokens::Preservation,
+	};
+
+	/// Pay every staging deposit back out of the escrow account and hold it on its depositor.
+	/// A deposit that can't be moved is logged and left in escrow with its staged metadata, so
+	/// governance can settle them.
+	pub struct MigrateToV3<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV3<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 2 {
+				log::info!(target: "runtime::nft-bridge", "MigrateToV3 not applicable");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let escrow = Pallet::<T, I>::escrow_account();
+			let mut entries = 0u64;
+			let mut held = 0u64;
+			for (collection_id, item_id, staged) in StagedMetadata::<T, I>::iter() {
+				entries += 1;
+				let moved = frame_support::storage::with_storage_layer(|| {
+					T::Currency::transfer(
+						&escrow,
+						&staged.depositor,
+						staged.deposit,
+						Preservation::Expendable,
+					)?;
+					T::Currency::hold(
+						&HoldReason::MetadataDeposit.into(),
+						&staged.depositor,
+						staged.deposit,
+					)
+				});
+				match moved {
+					Ok(()) => held += 1,
+					Err(e) => log::warn!(
+						target: "runtime::nft-bridge",
+						"MigrateToV3: deposit of {:?}/{:?} left in escrow: {:?}",
+						collection_id,
+						item_id,
+						e,
+					),
+				}
+			}
+
+			StorageVersion::new(3).put::<Pallet<T, I>>();
+			log::info!(
+				target: "runtime::nft-bridge",
+				"MigrateToV3: moved {} of {} staging deposits to holds",
+				held,
+				entries,
+			);
+			T::DbWeight::get().reads_writes(1 + 4 * entries, 1 + 4 * held)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok((StagedMetadata::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			frame_support::ensure!(
+				StagedMetadata::<T, I>::iter_keys().count() as u32 == before,
+				"staged metadata was lost"
+			);
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 3,
+				"storage version not updated"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Version 4: pending transfers gain a `valid_until` deadline
+pub mod v4 {
+	use super::*;
+
+	/// `TransferInfo` as stored before version 4
+	#[derive(Decode, Encode)]
+	pub(crate) struct OldTransferInfo<AccountId, BlockNumber> {
+		pub id: TransferId,
+		pub sender: AccountId,
+		pub dest: xcm::v3::MultiLocation,
+		pub sent_at: BlockNumber,
+		pub status: TransferStatus<BlockNumber>,
+		pub retry_count: u32,
+	}
+
+	type OldTransferInfoOf<T> =
+		OldTransferInfo<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+	/// Re-encode every pending transfer without a deadline. `translate` drops entries that
+	/// don't decode in the old format.
+	pub struct MigrateToV4<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV4<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 3 {
+				log::info!(target: "runtime::nft-bridge", "MigrateToV4 not applicable");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut entries = 0u64;
+			PendingTransfers::<T, I>::translate::<OldTransferInfoOf<T>, _>(|_, _, old| {
+				entries += 1;
+				Some(TransferInfo {
+					id: old.id,
+					sender: old.sender,
+					dest: old.dest,
+					sent_at: old.sent_at,
+					status: old.status,
+					retry_count: old.retry_count,
+					valid_until: None,
+				})
+			});
+
+			StorageVersion::new(4).put::<Pallet<T, I>>();
+			log::info!(
+				target: "runtime::nft-bridge",
+				"MigrateToV4: migrated {} pending transfers",
+				entries,
+			);
+			T::DbWeight::get().reads_writes(1 + entries, 1 + entries)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok((PendingTransfers::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			frame_support::ensure!(
+				PendingTransfers::<T, I>::iter_values().count() as u32 == before,
+				"pending transfers were lost"
+			);
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 4,
+				"storage version not updated"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Version 5: registered collections gain an optional `max_supply`
+pub mod v5 {
+	use super::*;
+
+	/// `CollectionInfo` as stored before version 5
+	#[derive(Decode, Encode)]
+	pub(crate) struct OldCollectionInfo<AccountId> {
+		pub reserve: Option<u32>,
+		pub admin: AccountId,
+		pub mode: CollectionMode,
+	}
+
+	/// Re-encode every registered collection without a supply limit. `translate` drops entries
+	/// that don't decode in the old format.
+	pub struct MigrateToV5<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV5<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 4 {
+				log::info!(target: "runtime::nft-bridge", "MigrateToV5 not applicable");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut entries = 0u64;
+			Collections::<T, I>::translate::<OldCollectionInfo<T::AccountId>, _>(|_, old| {
+				entries += 1;
+				Some(CollectionInfo {
+					reserve: old.reserve,
+					admin: old.admin,
+					mode: old.mode,
+					max_supply: None,
+				})
+			});
+
+			StorageVersion::new(5).put::<Pallet<T, I>>();
+			log::info!(
+				target: "runtime::nft-bridge",
+				"MigrateToV5: migrated {} collections",
+				entries,
+			);
+			T::DbWeight::get().reads_writes(1 + entries, 1 + entries)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok((Collections::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			frame_support::ensure!(
+				Collections::<T, I>::iter_values().count() as u32 == before,
+				"registered collections were lost"
+			);
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 5,
+				"storage version not updated"
+			);
+			Ok(())
+		}
+	}
+}
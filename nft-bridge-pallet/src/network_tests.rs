@@ -0,0 +1,717 @@
+// Network tests for the NFT Bridge pallet
+// Parachains A (1) and B (2) both run the bridge and exchange real XCM through xcm-simulator,
+// so the programs the bridge sends are executed on the other side rather than inspected
+
+use crate::*;
+use frame_support::assert_ok;
+use xcm::{v3::prelude::*, VersionedMultiAssets};
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
+
+const ALICE: parachain::AccountId = parachain::AccountId::new([1u8; 32]);
+
+// Minimal XCMP and DMP queue: executes each message as soon as it arrives
+#[frame_support::pallet]
+pub mod mock_msg_queue {
+    use codec::{Decode, Encode};
+    use frame_support::pallet_prelude::*;
+    use sp_std::prelude::*;
+    use xcm::{v3::prelude::*, VersionedXcm};
+    use xcm_simulator::{
+        DmpMessageHandlerT as DmpMessageHandler, ParaId, RelayBlockNumber, XcmpMessageFormat,
+        XcmpMessageHandlerT as XcmpMessageHandler,
+    };
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type XcmExecutor: ExecuteXcm<Self::RuntimeCall>;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::storage]
+    pub(super) type ParachainId<T: Config> = StorageValue<_, ParaId, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A message from `origin` was executed
+        Executed { origin: MultiLocation, outcome: Outcome },
+        /// A message from `origin` could not be decoded
+        BadFormat { origin: MultiLocation },
+    }
+
+    impl<T: Config> Get<ParaId> for Pallet<T> {
+        fn get() -> ParaId {
+            ParachainId::<T>::get()
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        pub fn set_para_id(para_id: ParaId) {
+            ParachainId::<T>::put(para_id);
+        }
+
+        pub fn parachain_id() -> ParaId {
+            ParachainId::<T>::get()
+        }
+
+        fn execute(origin: MultiLocation, data: &mut &[u8], max_weight: Weight) -> bool {
+            let message = VersionedXcm::<T::RuntimeCall>::decode(data)
+                .ok()
+                .and_then(|message| Xcm::<T::RuntimeCall>::try_from(message).ok());
+            let Some(message) = message else {
+                Self::deposit_event(Event::BadFormat { origin });
+                return false
+            };
+            let hash = message.using_encoded(sp_io::hashing::blake2_256);
+            let outcome = T::XcmExecutor::execute_xcm(origin, message, hash, max_weight);
+            Self::deposit_event(Event::Executed { origin, outcome });
+            true
+        }
+    }
+
+    impl<T: Config> XcmpMessageHandler for Pallet<T> {
+        fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
+            iter: I,
+            max_weight: Weight,
+        ) -> Weight {
+            for (sender, _, mut data) in iter {
+                let origin = MultiLocation::new(1, X1(Parachain(sender.into())));
+                XcmpMessageFormat::decode(&mut data).expect("the simulator sends versioned XCM");
+                while !data.is_empty() && Self::execute(origin, &mut data, max_weight) {}
+            }
+            max_weight
+        }
+    }
+
+    impl<T: Config> DmpMessageHandler for Pallet<T> {
+        fn handle_dmp_messages(
+            iter: impl Iterator<Item = (RelayBlockNumber, Vec<u8>)>,
+            limit: Weight,
+        ) -> Weight {
+            for (_, data) in iter {
+                Self::execute(MultiLocation::parent(), &mut &data[..], limit);
+            }
+            limit
+        }
+    }
+}
+
+// The runtime of both parachains. Execution is priced by `ExecutionPrice`, which lives in each
+// chain's own storage, so B can be made more expensive than A.
+pub mod parachain {
+    use super::mock_msg_queue;
+    use crate as pallet_nft_bridge;
+    use crate::*;
+    use codec::Decode;
+    use frame_support::{
+        construct_runtime, parameter_types,
+        traits::{ConstBool, ConstU128, ConstU32, ConstU64, ContainsPair, Everything, Nothing},
+        PalletId,
+    };
+    use polkadot_parachain::primitives::Sibling;
+    use sp_core::H256;
+    use sp_runtime::{
+        testing::Header,
+        traits::{AccountIdConversion, BlakeTwo256, Convert, IdentityLookup},
+    };
+    use xcm::v3::prelude::*;
+    use xcm_builder::{
+        AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowTopLevelPaidExecutionFrom,
+        EnsureXcmOrigin, FixedRateOfFungible, FixedWeightBounds, ParentIsPreset,
+        SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
+        SovereignSignedViaLocation, TakeWeightCredit,
+    };
+    use xcm_executor::XcmExecutor;
+
+    pub type AccountId = sp_runtime::AccountId32;
+    pub type Balance = u128;
+
+    type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+    type Block = frame_system::mocking::MockBlock<Runtime>;
+
+    construct_runtime!(
+        pub enum Runtime where
+            Block = Block,
+            NodeBlock = Block,
+            UncheckedExtrinsic = UncheckedExtrinsic,
+        {
+            System: frame_system,
+            Balances: pallet_balances,
+            MsgQueue: mock_msg_queue,
+            PolkadotXcm: pallet_xcm,
+            NftBridge: pallet_nft_bridge,
+        }
+    );
+
+    impl frame_system::Config for Runtime {
+        type BaseCallFilter = Everything;
+        type BlockWeights = ();
+        type BlockLength = ();
+        type RuntimeOrigin = RuntimeOrigin;
+        type RuntimeCall = RuntimeCall;
+        type Index = u64;
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = AccountId;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type RuntimeEvent = RuntimeEvent;
+        type BlockHashCount = ConstU64<250>;
+        type DbWeight = ();
+        type Version = ();
+        type PalletInfo = PalletInfo;
+        type AccountData = pallet_balances::AccountData<Balance>;
+        type OnNewAccount = ();
+        type OnKilledAccount = ();
+        type SystemWeightInfo = ();
+        type SS58Prefix = ();
+        type OnSetCode = ();
+        type MaxConsumers = ConstU32<16>;
+    }
+
+    impl pallet_balances::Config for Runtime {
+        type Balance = Balance;
+        type DustRemoval = ();
+        type RuntimeEvent = RuntimeEvent;
+        type ExistentialDeposit = ConstU128<1>;
+        type AccountStore = System;
+        type WeightInfo = ();
+        type MaxLocks = ();
+        type MaxReserves = ();
+        type ReserveIdentifier = [u8; 8];
+        type FreezeIdentifier = ();
+        type MaxFreezes = ();
+        type HoldIdentifier = RuntimeHoldReason;
+        type MaxHolds = ConstU32<3>;
+    }
+
+    impl mock_msg_queue::Config for Runtime {
+        type RuntimeEvent = RuntimeEvent;
+        type XcmExecutor = XcmExecutor<XcmConfig>;
+    }
+
+    parameter_types! {
+        pub const RelayNetwork: NetworkId = NetworkId::Kusama;
+        pub UniversalLocation: InteriorMultiLocation =
+            X2(GlobalConsensus(RelayNetwork::get()), Parachain(MsgQueue::parachain_id().into()));
+        pub const UnitWeightCost: Weight = Weight::from_parts(10, 10);
+        pub const MaxInstructions: u32 = 100;
+        pub const MaxAssetsIntoHolding: u32 = 64;
+        // Relay token charged per second and per megabyte of execution
+        pub storage ExecutionPrice: (AssetId, u128, u128) =
+            (Concrete(MultiLocation::parent()), 1_000, 1_000);
+        pub ReachableDest: Option<MultiLocation> = Some(MultiLocation::parent());
+    }
+
+    pub type LocationToAccountId = (
+        ParentIsPreset<AccountId>,
+        SiblingParachainConvertsVia<Sibling, AccountId>,
+        AccountId32Aliases<RelayNetwork, AccountId>,
+    );
+
+    pub type XcmOriginToCallOrigin = (
+        SovereignSignedViaLocation<LocationToAccountId, RuntimeOrigin>,
+        SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>,
+    );
+
+    pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
+
+    pub type XcmRouter = super::ParachainXcmRouter<MsgQueue>;
+
+    pub type Barrier = (
+        TakeWeightCredit,
+        AllowTopLevelPaidExecutionFrom<Everything>,
+        AllowExplicitUnpaidExecutionFrom<Everything>,
+    );
+
+    // Every sibling is the reserve of the NFTs it sends
+    pub struct SiblingReserves;
+    impl ContainsPair<MultiAsset, MultiLocation> for SiblingReserves {
+        fn contains(_: &MultiAsset, origin: &MultiLocation) -> bool {
+            matches!(origin, MultiLocation { parents: 1, interior: X1(Parachain(_)) })
+        }
+    }
+
+    pub struct XcmConfig;
+    impl xcm_executor::Config for XcmConfig {
+        type RuntimeCall = RuntimeCall;
+        type XcmSender = XcmRouter;
+        type AssetTransactor = ();
+        type OriginConverter = XcmOriginToCallOrigin;
+        type IsReserve = SiblingReserves;
+        type IsTeleporter = ();
+        type UniversalLocation = UniversalLocation;
+        type Barrier = Barrier;
+        type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+        type Trader = FixedRateOfFungible<ExecutionPrice, ()>;
+        type ResponseHandler = PolkadotXcm;
+        type AssetTrap = PolkadotXcm;
+        type AssetLocker = ();
+        type AssetExchanger = ();
+        type AssetClaims = PolkadotXcm;
+        type SubscriptionService = PolkadotXcm;
+        type PalletInstancesInfo = AllPalletsWithSystem;
+        type FeeManager = ();
+        type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+        type MessageExporter = ();
+        type UniversalAliases = Nothing;
+        type CallDispatcher = RuntimeCall;
+        type SafeCallFilter = Everything;
+    }
+
+    impl pallet_xcm::Config for Runtime {
+        type RuntimeEvent = RuntimeEvent;
+        type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+        type XcmRouter = XcmRouter;
+        type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+        type XcmExecuteFilter = Everything;
+        type XcmExecutor = XcmExecutor<XcmConfig>;
+        type XcmTeleportFilter = Nothing;
+        type XcmReserveTransferFilter = Everything;
+        type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+        type UniversalLocation = UniversalLocation;
+        type RuntimeOrigin = RuntimeOrigin;
+        type RuntimeCall = RuntimeCall;
+        const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+        type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+        type Currency = Balances;
+        type CurrencyMatcher = ();
+        type TrustedLockers = ();
+        type SovereignAccountOf = LocationToAccountId;
+        type MaxLockers = ConstU32<8>;
+        type MaxRemoteLockConsumers = ConstU32<0>;
+        type RemoteLockConsumerIdentifier = ();
+        type WeightInfo = pallet_xcm::TestWeightInfo;
+        #[cfg(feature = "runtime-benchmarks")]
+        type ReachableDest = ReachableDest;
+    }
+
+    // Bridge callbacks arrive as `Transact`s dispatched by the sending sibling's sovereign account
+    pub struct EnsureSiblingSovereign;
+    impl EnsureOrigin<RuntimeOrigin> for EnsureSiblingSovereign {
+        type Success = MultiLocation;
+        fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+            o.into().and_then(|o| match o {
+                frame_system::RawOrigin::Signed(who) => {
+                    let raw: &[u8; 32] = who.as_ref();
+                    let para_id = u32::decode(&mut &raw[4..8]).unwrap_or_default();
+                    let sovereign: AccountId = Sibling(para_id.into()).into_account_truncating();
+                    if sovereign == who {
+                        Ok(MultiLocation::new(1, X1(Parachain(para_id))))
+                    } else {
+                        Err(RuntimeOrigin::from(frame_system::RawOrigin::Signed(who)))
+                    }
+                },
+                r => Err(RuntimeOrigin::from(r)),
+            })
+        }
+        #[cfg(feature = "runtime-benchmarks")]
+        fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+            Ok(RuntimeOrigin::signed(Sibling(2.into()).into_account_truncating()))
+        }
+    }
+
+    // Beneficiaries are addressed by their 32-byte account id
+    pub struct AccountToLocation;
+    impl Convert<AccountId, MultiLocation> for AccountToLocation {
+        fn convert(who: AccountId) -> MultiLocation {
+            X1(Junction::AccountId32 { network: None, id: who.into() }).into()
+        }
+    }
+
+    // Delivery fees are paid 1:1 in the relay token; nothing else is accepted
+    pub struct RelayTokenToBalance;
+    impl Convert<MultiAssets, Option<Balance>> for RelayTokenToBalance {
+        fn convert(assets: MultiAssets) -> Option<Balance> {
+            match assets.into_inner().as_slice() {
+                [MultiAsset { id: Concrete(location), fun: Fungible(amount) }]
+                    if *location == MultiLocation::parent() =>
+                    Some(*amount),
+                _ => None,
+            }
+        }
+    }
+
+    pub struct FixedTime;
+    impl frame_support::traits::UnixTime for FixedTime {
+        fn now() -> core::time::Duration {
+            core::time::Duration::from_secs(1_700_000_000)
+        }
+    }
+
+    parameter_types! {
+        pub const NftBridgePalletId: PalletId = PalletId(*b"nftbridg");
+        pub const BridgeLockStrategy: LockStrategy = LockStrategy::Detach;
+        pub const NftAssetPrefix: InteriorMultiLocation = Here;
+        pub SelfParaId: u32 = MsgQueue::parachain_id().into();
+        pub const RemoteExecutionFee: u128 = 1_000;
+        pub const RejectionReportFee: u128 = 1_000;
+        pub const WrappedRange: Option<(u32, u32)> = None;
+        pub const RemoteCallWeight: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+        pub const DefaultWeightLimit: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+        pub FeeAccount: AccountId = AccountId::new([99u8; 32]);
+    }
+
+    impl Config for Runtime {
+        type RuntimeEvent = RuntimeEvent;
+        type CollectionId = u32;
+        type ItemId = u32;
+        type SendOrigin = frame_system::EnsureSigned<Self::AccountId>;
+        type ReceiveOrigin = EnsureSiblingSovereign;
+        type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+        type LockStrategy = BridgeLockStrategy;
+        type TrustedSources = Everything;
+        type LockProofVerifier = ();
+        type RequireLockProof = ConstBool<false>;
+        type XcmSender = LatestXcmSender<XcmRouter>;
+        type ProgramBuilder = xcm_handler::ReserveTransferProgram;
+        type NftAssetPrefix = NftAssetPrefix;
+        type SelfParaId = SelfParaId;
+        type RelayNetwork = RelayNetwork;
+        type AccountIdToLocation = AccountToLocation;
+        type LocationToAccountId = LocationToAccountId;
+        type AssetTransactor = ();
+        type Nfts = NoNftBackend<u32, u32>;
+        type PalletId = NftBridgePalletId;
+        type AllowRemoteCalls = ConstBool<true>;
+        type MaxRemoteCallLen = ConstU32<256>;
+        type RemoteCallWeight = RemoteCallWeight;
+        type DefaultWeightLimit = DefaultWeightLimit;
+        type MaxBatchSize = ConstU32<8>;
+        type MaxOutboundQueue = ConstU32<4>;
+        type MaxPendingPerAccount = ConstU32<64>;
+        type DeliveryDedupWindow = ConstU32<2>;
+        type MaxFailureRecords = ConstU32<2>;
+        type MaxAuthorities = ConstU32<3>;
+        type RejectionReportFee = RejectionReportFee;
+        type UnixTime = FixedTime;
+        type MaxInboundAge = ConstU64<3_600>;
+        type StatsPeriod = ConstU64<10>;
+        type TransferTimeout = ConstU64<1000>;
+        type WarnAfter = ConstU64<500>;
+        type SwapAcceptTimeout = ConstU64<20>;
+        type MetadataSyncPeriod = ConstU64<10>;
+        type MaxSubscribersPerUpdate = ConstU32<2>;
+        type MaxExportBatchSize = ConstU32<10>;
+        type StagingTimeout = ConstU64<20>;
+        type StagingDepositPerByte = ConstU128<1>;
+        type MetadataRequestTimeout = ConstU64<10>;
+        type QueryTimeout = ConstU64<10>;
+        type OwnershipQueryDeposit = ConstU128<5>;
+        type MaxOwnershipQueries = ConstU32<2>;
+        type MaxHistoryHops = ConstU32<6>;
+        type KeepRecentHops = ConstU32<2>;
+        type HistoryDepositPerHop = ConstU128<0>;
+        type ChunkSize = ConstU32<4>;
+        type MaxMetadataChunks = ConstU32<8>;
+        type ReassemblyTimeout = ConstU64<10>;
+        type MaxAttributes = ConstU32<2>;
+        type MaxAttributeLen = ConstU32<1024>;
+        type PermissionlessCollections = ConstBool<true>;
+        type AutoRegisterWrappedCollections = ConstBool<false>;
+        type WrappedCollectionRange = WrappedRange;
+        type MaxCollectionMetadataBytes = ConstU64<4096>;
+        type RetryBackoff = ConstU64<10>;
+        type MaxRetries = ConstU32<3>;
+        type StaleThreshold = ConstU64<100>;
+        type MaxStaleScan = ConstU32<16>;
+        type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+        type RemoteExecutionFee = RemoteExecutionFee;
+        type MaxOutboundMessageSize = ConstU32<{ 64 * 1024 }>;
+        type MaxMetadataLen = ConstU32<1024>;
+        type MaxUriLen = ConstU32<256>;
+        type MaxUrisPerItem = ConstU32<4>;
+        type Currency = Balances;
+        type RuntimeHoldReason = RuntimeHoldReason;
+        type BridgeFee = ConstU128<0>;
+        type PriorityFee = ConstU128<5>;
+        type PriorityInFlightCap = ConstU32<1>;
+        type FeePerMetadataByte = ConstU128<0>;
+        type FeeDestination = FeeAccount;
+        type FeeAssetConverter = RelayTokenToBalance;
+        type OnNftSent = ();
+        type TransferFilter = ();
+        type ItemIdGenerator = ();
+        type OnNftReceived = ();
+        type WeightInfo = ();
+    }
+
+    pub type Extrinsic = sp_runtime::testing::TestXt<RuntimeCall, ()>;
+
+    impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+    where
+        RuntimeCall: From<C>,
+    {
+        type OverarchingCall = RuntimeCall;
+        type Extrinsic = Extrinsic;
+    }
+}
+
+// A relay chain that only passes messages along
+pub mod relay_chain {
+    use frame_support::{
+        construct_runtime, parameter_types,
+        traits::{ConstU32, ConstU64, Everything, Nothing, ProcessMessage, ProcessMessageError},
+        weights::{Weight, WeightMeter},
+    };
+    use polkadot_runtime_parachains::inclusion::{AggregateMessageOrigin, UmpQueueId};
+    use sp_core::H256;
+    use sp_runtime::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+    };
+    use xcm::v3::prelude::*;
+    use xcm_builder::{FixedWeightBounds, ProcessXcmMessage};
+    use xcm_executor::XcmExecutor;
+
+    type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+    type Block = frame_system::mocking::MockBlock<Runtime>;
+
+    construct_runtime!(
+        pub enum Runtime where
+            Block = Block,
+            NodeBlock = Block,
+            UncheckedExtrinsic = UncheckedExtrinsic,
+        {
+            System: frame_system,
+            MessageQueue: pallet_message_queue,
+        }
+    );
+
+    impl frame_system::Config for Runtime {
+        type BaseCallFilter = Everything;
+        type BlockWeights = ();
+        type BlockLength = ();
+        type RuntimeOrigin = RuntimeOrigin;
+        type RuntimeCall = RuntimeCall;
+        type Index = u64;
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = sp_runtime::AccountId32;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type RuntimeEvent = RuntimeEvent;
+        type BlockHashCount = ConstU64<250>;
+        type DbWeight = ();
+        type Version = ();
+        type PalletInfo = PalletInfo;
+        type AccountData = ();
+        type OnNewAccount = ();
+        type OnKilledAccount = ();
+        type SystemWeightInfo = ();
+        type SS58Prefix = ();
+        type OnSetCode = ();
+        type MaxConsumers = ConstU32<16>;
+    }
+
+    parameter_types! {
+        pub const RelayNetwork: NetworkId = NetworkId::Kusama;
+        pub UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(RelayNetwork::get()));
+        pub const UnitWeightCost: Weight = Weight::from_parts(10, 10);
+        pub const MaxInstructions: u32 = 100;
+        pub const MaxAssetsIntoHolding: u32 = 64;
+        pub const MessageQueueHeapSize: u32 = 65_536;
+        pub const MessageQueueMaxStale: u32 = 16;
+        pub MessageQueueServiceWeight: Option<Weight> =
+            Some(Weight::from_parts(1_000_000_000, 1_000_000));
+    }
+
+    pub struct XcmConfig;
+    impl xcm_executor::Config for XcmConfig {
+        type RuntimeCall = RuntimeCall;
+        type XcmSender = super::RelayChainXcmRouter;
+        type AssetTransactor = ();
+        type OriginConverter = ();
+        type IsReserve = ();
+        type IsTeleporter = ();
+        type UniversalLocation = UniversalLocation;
+        type Barrier = ();
+        type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+        type Trader = ();
+        type ResponseHandler = ();
+        type AssetTrap = ();
+        type AssetLocker = ();
+        type AssetExchanger = ();
+        type AssetClaims = ();
+        type SubscriptionService = ();
+        type PalletInstancesInfo = AllPalletsWithSystem;
+        type FeeManager = ();
+        type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+        type MessageExporter = ();
+        type UniversalAliases = Nothing;
+        type CallDispatcher = RuntimeCall;
+        type SafeCallFilter = Everything;
+    }
+
+    // Upward messages are executed as coming from the parachain that sent them
+    pub struct MessageProcessor;
+    impl ProcessMessage for MessageProcessor {
+        type Origin = AggregateMessageOrigin;
+
+        fn process_message(
+            message: &[u8],
+            origin: Self::Origin,
+            meter: &mut WeightMeter,
+        ) -> Result<bool, ProcessMessageError> {
+            let AggregateMessageOrigin::Ump(UmpQueueId::Para(para)) = origin;
+            ProcessXcmMessage::<Junction, XcmExecutor<XcmConfig>, RuntimeCall>::process_message(
+                message,
+                Junction::Parachain(para.into()),
+                meter,
+            )
+        }
+    }
+
+    impl pallet_message_queue::Config for Runtime {
+        type RuntimeEvent = RuntimeEvent;
+        type Size = u32;
+        type HeapSize = MessageQueueHeapSize;
+        type MaxStale = MessageQueueMaxStale;
+        type ServiceWeight = MessageQueueServiceWeight;
+        type MessageProcessor = MessageProcessor;
+        type QueueChangeHandler = ();
+        type WeightInfo = ();
+    }
+}
+
+decl_test_parachain! {
+    pub struct ParaA {
+        Runtime = parachain::Runtime,
+        XcmpMessageHandler = parachain::MsgQueue,
+        DmpMessageHandler = parachain::MsgQueue,
+        new_ext = para_ext(1),
+    }
+}
+
+decl_test_parachain! {
+    pub struct ParaB {
+        Runtime = parachain::Runtime,
+        XcmpMessageHandler = parachain::MsgQueue,
+        DmpMessageHandler = parachain::MsgQueue,
+        new_ext = para_ext(2),
+    }
+}
+
+decl_test_relay_chain! {
+    pub struct Relay {
+        Runtime = relay_chain::Runtime,
+        RuntimeCall = relay_chain::RuntimeCall,
+        RuntimeEvent = relay_chain::RuntimeEvent,
+        XcmConfig = relay_chain::XcmConfig,
+        MessageQueue = relay_chain::MessageQueue,
+        System = relay_chain::System,
+        new_ext = relay_ext(),
+    }
+}
+
+decl_test_network! {
+    pub struct MockNet {
+        relay_chain = Relay,
+        parachains = vec![
+            (1, ParaA),
+            (2, ParaB),
+        ],
+    }
+}
+
+pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::default()
+        .build_storage::<parachain::Runtime>()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        parachain::System::set_block_number(1);
+        parachain::MsgQueue::set_para_id(para_id.into());
+    });
+    ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::default()
+        .build_storage::<relay_chain::Runtime>()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| relay_chain::System::set_block_number(1));
+    ext
+}
+
+/// Make execution on B cost more than any transfer buys there, then send item `(1, 1)` from A
+/// to B. Returns the NFT as the transfer program carries it.
+fn send_underpaid_transfer() -> MultiAsset {
+    ParaB::execute_with(|| {
+        parachain::ExecutionPrice::set(&(Concrete(MultiLocation::parent()), u128::MAX, u128::MAX));
+    });
+
+    ParaA::execute_with(|| {
+        use parachain::{NftBridge, RuntimeOrigin};
+        NFTOwners::<parachain::Runtime>::insert(1, 1, ALICE);
+        assert_ok!(NftBridge::send_nft(
+            RuntimeOrigin::signed(ALICE),
+            1,
+            1,
+            2,
+            b"test_metadata".to_vec(),
+            None,
+            None,
+            ExecutionLimit::Default,
+            false,
+            vec![],
+            None
+        ));
+        NftBridge::nft_asset(1, 1).unwrap()
+    })
+}
+
+#[test]
+fn underpaid_transfers_are_trapped_on_the_destination() {
+    MockNet::reset();
+    let nft = send_underpaid_transfer();
+
+    // B ran the program up to `BuyExecution`, which failed, and trapped the NFT it held
+    ParaB::execute_with(|| {
+        use parachain::{RuntimeEvent, System};
+        let sender = MultiLocation::new(1, X1(Parachain(1)));
+        let trapped = System::events().into_iter().any(|record| match record.event {
+            RuntimeEvent::PolkadotXcm(pallet_xcm::Event::AssetsTrapped(
+                _,
+                origin,
+                VersionedMultiAssets::V3(assets),
+            )) => origin == sender && assets.contains(&nft),
+            _ => false,
+        });
+        assert!(trapped);
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::NftBridge(crate::Event::NFTReceived { .. })
+        )));
+    });
+
+    // Without a report from B, A still thinks the NFT is on its way
+    ParaA::execute_with(|| {
+        let transfer = parachain::NftBridge::pending_transfer(1, 1).unwrap();
+        assert_eq!(transfer.status, TransferStatus::InFlight);
+    });
+}
+
+/// Acceptance test for error handling on the destination: B's error handler reports the trap to
+/// A, and the report hands the NFT back to its owner without them having to cancel.
+#[test]
+#[ignore = "synth-198: transfer programs set no error handler, so B never reports the trap"]
+fn trapped_transfers_are_reported_and_unlocked() {
+    MockNet::reset();
+    send_underpaid_transfer();
+
+    ParaA::execute_with(|| {
+        use parachain::NftBridge;
+        assert!(NftBridge::pending_transfer(1, 1).is_none());
+        assert_eq!(NftBridge::owner(1, 1), Some(ALICE));
+        assert!(!NftBridge::is_bridge_locked(&1, &1));
+        assert_ok!(NftBridge::do_try_state());
+    });
+}
@@ -0,0 +1,67 @@
+//! Offchain worker watching for stale pending transfers
+
+use crate::*;
+use frame_support::traits::Get;
+use frame_system::{
+	offchain::SubmitTransaction,
+	pallet_prelude::BlockNumberFor,
+};
+use sp_runtime::{offchain::storage::StorageValueRef, traits::Saturating};
+use sp_std::vec::Vec;
+
+/// Offchain storage key of the raw `PendingTransfers` key the last scan stopped at
+const STALE_SCAN_CURSOR: &[u8] = b"nft-bridge::stale-scan-cursor";
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Scan up to `MaxStaleScan` pending transfers, continuing where the previous run stopped,
+	/// and report those older than `StaleThreshold` that haven't been reported yet
+	pub(crate) fn scan_stale_transfers(now: BlockNumberFor<T>) {
+		let cursor = StorageValueRef::persistent(STALE_SCAN_CURSOR);
+		let start = cursor.get::<Vec<u8>>().ok().flatten();
+
+		let mut iter = match start {
+			Some(key) => PendingTransfers::<T, I>::iter_from(key),
+			None => PendingTransfers::<T, I>::iter(),
+		};
+		let mut scanned = 0u32;
+		while scanned < T::MaxStaleScan::get() {
+			let Some((collection_id, item_id, transfer)) = iter.next() else { break };
+			scanned += 1;
+
+			if !Self::is_stale(&transfer, now) || StaleReported::<T, I>::contains_key(transfer.id) {
+				continue
+			}
+			log::warn!(
+				target: "runtime::nft-bridge",
+				"transfer {} of {:?}/{:?} to {:?} pending since block {:?}",
+				transfer.id,
+				collection_id,
+				item_id,
+				transfer.dest,
+				transfer.sent_at,
+			);
+
+			let call = Call::report_stale { collection_id, item_id, transfer_id: transfer.id };
+			let submitted =
+				SubmitTransaction::<T, Call<T, I>>::submit_unsigned_transaction(call.into());
+			if submitted.is_err() {
+				log::error!(target: "runtime::nft-bridge", "failed to submit stale report");
+			}
+		}
+
+		// Start over once the whole map has been covered
+		if scanned < T::MaxStaleScan::get() {
+			cursor.clear();
+		} else {
+			cursor.set(&iter.last_raw_key());
+		}
+	}
+
+	/// Whether a pending transfer has been waiting for at least `StaleThreshold` blocks
+	pub(crate) fn is_stale(
+		transfer: &TransferInfo<T::AccountId, BlockNumberFor<T>>,
+		now: BlockNumberFor<T>,
+	) -> bool {
+		now.saturating_sub(transfer.sent_at) >= T::StaleThreshold::get()
+	}
+}
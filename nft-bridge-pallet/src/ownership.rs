@@ -0,0 +1,132 @@
+//! Ownership queries to other chains
+//!
+//! `query_remote_ownership` asks another chain running this pallet who owns one of its items
+//! (`ownership_query`). That chain answers with `report_ownership`, which is accepted only from
+//! the chain asked and within `QueryTimeout` blocks, and stored in `RemoteOwnership`. Queries
+//! left unanswered may be purged by anyone once they lapse.
+//!
+//! A signed account asking holds `OwnershipQueryDeposit` until its query is answered or purged,
+//! and at most `MaxOwnershipQueries` queries are outstanding at once.
+
+use crate::*;
+use frame_support::traits::{fungible::MutateHold, tokens::Precision, Get};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError,
+};
+use xcm::v3::{MultiLocation, QueryId};
+
+/// A query as stored in `OwnershipQueries`
+pub type OwnershipQueryOf<T, I = ()> = OwnershipQuery<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T, I>,
+	<T as Config<I>>::CollectionId,
+	<T as Config<I>>::ItemId,
+	BlockNumberFor<T>,
+>;
+
+/// A report as stored in `RemoteOwnership`
+pub type RemoteOwnerOf<T> =
+	RemoteOwner<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Ask `para_id` who owns its item `collection_id`/`item_id`, holding the deposit from
+	/// `depositor` if there is one
+	pub(crate) fn do_query_remote_ownership(
+		depositor: Option<T::AccountId>,
+		para_id: u32,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> Result<QueryId, DispatchError> {
+		let outstanding = OwnershipQueryCount::<T, I>::get();
+		ensure!(outstanding < T::MaxOwnershipQueries::get(), Error::<T, I>::TooManyOwnershipQueries);
+		let deposit = match &depositor {
+			Some(who) => {
+				let deposit = T::OwnershipQueryDeposit::get();
+				T::Currency::hold(&HoldReason::OwnershipQueryDeposit.into(), who, deposit)?;
+				deposit
+			},
+			None => Zero::zero(),
+		};
+
+		let query_id = NextOwnershipQueryId::<T, I>::mutate(|id| {
+			let current = *id;
+			*id = id.wrapping_add(1);
+			current
+		});
+		let expires_at =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::QueryTimeout::get());
+		OwnershipQueries::<T, I>::insert(
+			query_id,
+			OwnershipQuery { para_id, collection_id, item_id, expires_at, depositor, deposit },
+		);
+		OwnershipQueryCount::<T, I>::put(outstanding.saturating_add(1));
+
+		Self::send_bridge_call(
+			para_id,
+			Call::<T, I>::ownership_query { query_id, collection_id, item_id },
+		)?;
+
+		Ok(query_id)
+	}
+
+	/// Tell the chain at `location` who owns the item it asked about
+	pub(crate) fn do_answer_ownership_query(
+		location: &MultiLocation,
+		query_id: QueryId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> DispatchResult {
+		let para_id = Self::sibling_para_id(location).ok_or(Error::<T, I>::OriginMismatch)?;
+		let (collection_id, item_id) = Self::local_ids(location, collection_id, item_id);
+		let owner = Self::owner(collection_id, item_id);
+
+		Self::send_bridge_call(para_id, Call::<T, I>::report_ownership { query_id, owner })
+	}
+
+	/// Record the answer `from_para_id` gave to one of our queries
+	pub(crate) fn do_report_ownership(
+		from_para_id: u32,
+		query_id: QueryId,
+		owner: Option<T::AccountId>,
+	) -> Result<OwnershipQueryOf<T, I>, DispatchError> {
+		let query = OwnershipQueries::<T, I>::get(query_id).ok_or(Error::<T, I>::UnknownQuery)?;
+		ensure!(query.para_id == from_para_id, Error::<T, I>::OriginMismatch);
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(now <= query.expires_at, Error::<T, I>::QueryExpired);
+
+		Self::remove_ownership_query(query_id, &query)?;
+		RemoteOwnership::<T, I>::insert(
+			(from_para_id, query.collection_id),
+			query.item_id,
+			RemoteOwner { owner, reported_at: now },
+		);
+
+		Ok(query)
+	}
+
+	/// Drop a query that lapsed without an answer
+	pub(crate) fn do_purge_ownership_query(query_id: QueryId) -> DispatchResult {
+		let query = OwnershipQueries::<T, I>::get(query_id).ok_or(Error::<T, I>::UnknownQuery)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(now > query.expires_at, Error::<T, I>::QueryNotExpired);
+
+		Self::remove_ownership_query(query_id, &query)
+	}
+
+	/// Drop a settled query and give its asker back the deposit
+	fn remove_ownership_query(query_id: QueryId, query: &OwnershipQueryOf<T, I>) -> DispatchResult {
+		OwnershipQueries::<T, I>::remove(query_id);
+		OwnershipQueryCount::<T, I>::mutate(|count| *count = count.saturating_sub(1));
+		if let Some(depositor) = &query.depositor {
+			T::Currency::release(
+				&HoldReason::OwnershipQueryDeposit.into(),
+				depositor,
+				query.deposit,
+				Precision::Exact,
+			)?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,146 @@
+//! Reporting refused deliveries to the chain that sent them
+//!
+//! When `receive_nft` refuses an NFT, the call still succeeds, so the source would see nothing
+//! amiss. The destination sends it a best-effort `report_rejection` with a `RejectionCode`, buying
+//! at most `RejectionReportFee` of execution there. The source marks the pending transfer named
+//! in it `Rejected`, if that is still awaiting delivery, after which its sender may cancel it.
+//! Deliveries that don't name their transfer aren't reported, nor are replays: an NFT refused as
+//! already received or with a spent nonce got here before, and must not be handed back too.
+//! Partners on the `Transact` protocol don't run this pallet and get no report.
+
+use crate::*;
+use frame_support::traits::Get;
+use xcm::v3::prelude::*;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The code reported for a `do_receive_nft` error
+	pub(crate) fn rejection_code(error: DispatchError) -> RejectionCode {
+		let codes: [(Error<T, I>, RejectionCode); 16] = [
+			(Error::<T, I>::UntrustedSourceChain, RejectionCode::UntrustedSource),
+			(Error::<T, I>::InvalidNonce, RejectionCode::InvalidNonce),
+			(Error::<T, I>::MessageTooOld, RejectionCode::MessageTooOld),
+			(Error::<T, I>::WrongReserve, RejectionCode::WrongReserve),
+			(Error::<T, I>::CollectionNotRegistered, RejectionCode::CollectionNotRegistered),
+			(Error::<T, I>::CollectionNonTransferable, RejectionCode::CollectionNonTransferable),
+			(Error::<T, I>::NFTAlreadyExists, RejectionCode::AlreadyExists),
+			(Error::<T, I>::RecipientOptedOut, RejectionCode::RecipientOptedOut),
+			(Error::<T, I>::MetadataHashMismatch, RejectionCode::MetadataHashMismatch),
+			(Error::<T, I>::MetadataTooLong, RejectionCode::MetadataTooLong),
+			(Error::<T, I>::CollectionMetadataQuotaExceeded, RejectionCode::MetadataTooLong),
+			(Error::<T, I>::MissingProvenance, RejectionCode::MissingProvenance),
+			(Error::<T, I>::InvalidLockProof, RejectionCode::InvalidLockProof),
+			(Error::<T, I>::BackendMintFailed, RejectionCode::MintFailed),
+			(Error::<T, I>::AssetTransactorFailed, RejectionCode::MintFailed),
+			(Error::<T, I>::CollectionIdExhausted, RejectionCode::Other),
+		];
+		codes
+			.into_iter()
+			.find_map(|(known, code)| (DispatchError::from(known) == error).then_some(code))
+			.unwrap_or(RejectionCode::Other)
+	}
+
+	/// Whether `error` refused a delivery for having arrived before
+	fn is_replay(error: DispatchError) -> bool {
+		[Error::<T, I>::NFTAlreadyExists, Error::<T, I>::InvalidNonce]
+			.into_iter()
+			.any(|replay| DispatchError::from(replay) == error)
+	}
+
+	/// Tell `from_para_id` why its delivery of the item, under the ids it sent, was refused.
+	/// Replays aren't reported, and a report that can't be sent is dropped.
+	pub(crate) fn report_rejection_to(
+		from_para_id: u32,
+		transfer_id: TransferId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		error: DispatchError,
+	) {
+		let config = Self::destination_config(from_para_id).unwrap_or_default();
+		if config.protocol == TransferProtocol::Transact || Self::is_replay(error) {
+			return
+		}
+
+		let code = Self::rejection_code(error);
+		let call = Call::<T, I>::report_rejection { transfer_id, code };
+		let sent = match T::RejectionReportFee::get() {
+			0 => Self::send_bridge_call(from_para_id, call),
+			fee => Self::send_paid_bridge_call(from_para_id, call, fee),
+		};
+		if sent.is_ok() {
+			Self::deposit_event(Event::RejectionReported {
+				collection_id,
+				item_id,
+				from_para_id,
+				transfer_id,
+				code,
+			});
+		}
+	}
+
+	/// `send_bridge_call`, buying `fee` of the relay token's worth of execution on `dest_para_id`
+	/// out of this chain's sovereign account there
+	fn send_paid_bridge_call(dest_para_id: u32, call: Call<T, I>, fee: u128) -> DispatchResult {
+		let call = Self::bridge_call_bytes(dest_para_id, &call)?;
+		let fees: MultiAsset = (MultiLocation::parent(), fee).into();
+		let message = Xcm(vec![
+			WithdrawAsset(fees.clone().into()),
+			BuyExecution { fees, weight_limit: Limited(T::RemoteCallWeight::get()) },
+			Transact {
+				origin_kind: OriginKind::SovereignAccount,
+				require_weight_at_most: T::RemoteCallWeight::get(),
+				call: call.into(),
+			},
+		]);
+
+		let (ticket, _) =
+			Self::validate_versioned(dest_para_id, Self::dest_location(dest_para_id), message)
+				.map_err(|_| Error::<T, I>::FailedToSendXCM)?;
+		T::XcmSender::deliver(ticket).map_err(|_| Error::<T, I>::DeliveryFailed)?;
+
+		Ok(())
+	}
+
+	/// Mark transfer `transfer_id`, sent to `location`, as refused there. Only a transfer whose
+	/// message may still be on its way can be refused.
+	pub(crate) fn do_report_rejection(
+		location: &MultiLocation,
+		transfer_id: TransferId,
+		code: RejectionCode,
+	) -> DispatchResult {
+		let (collection_id, item_id) =
+			TransferKeys::<T, I>::get(transfer_id).ok_or(Error::<T, I>::TransferNotRejectable)?;
+		PendingTransfers::<T, I>::try_mutate(collection_id, item_id, |pending| {
+			// The item may since have been sent again under another id
+			let transfer = pending
+				.as_mut()
+				.filter(|transfer| transfer.id == transfer_id)
+				.ok_or(Error::<T, I>::TransferNotRejectable)?;
+			ensure!(*location == transfer.dest, Error::<T, I>::OriginMismatch);
+			let awaiting_delivery = matches!(
+				transfer.status,
+				TransferStatus::InFlight | TransferStatus::SendFailed { .. }
+			);
+			ensure!(awaiting_delivery, Error::<T, I>::TransferNotRejectable);
+			// A refusal proves an earlier failed attempt arrived after all
+			if !transfer.status.is_delivered() {
+				FailedSends::<T, I>::remove(transfer_id);
+				if let Some(dest_para_id) = Self::sibling_para_id(location) {
+					InFlightCount::<T, I>::mutate(dest_para_id, |count| {
+						*count = count.saturating_add(1)
+					});
+				}
+			}
+			transfer.status = TransferStatus::Rejected { code };
+			Ok::<_, DispatchError>(())
+		})?;
+
+		Self::deposit_event(Event::NFTTransferRejected {
+			collection_id,
+			item_id,
+			transfer_id,
+			code,
+		});
+
+		Ok(())
+	}
+}
@@ -0,0 +1,100 @@
+//! Royalties on local transfers
+//!
+//! A collection admin may attach a `RoyaltyInfo` to an item. A `transfer` declaring a non-zero
+//! sale price then pays the royalty share of it before the item moves: straight to a local
+//! beneficiary, or into the `RoyaltyPots` entry of a remote one, which the location claims to
+//! an account of its choosing with `claim_royalties`. Transfers without a price pay nothing.
+
+use crate::*;
+use frame_support::traits::{
+	fungible::Mutate as _,
+	tokens::{
+		nonfungibles::{Inspect, Transfer},
+		Preservation,
+	},
+};
+use sp_runtime::{traits::Zero, DispatchError};
+use xcm::v3::MultiLocation;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Move an item `from` owns to `to`, paying its royalty on `sale_price` first
+	pub(crate) fn do_transfer(
+		from: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		to: T::AccountId,
+		sale_price: Option<BalanceOf<T, I>>,
+	) -> DispatchResult {
+		Self::ensure_not_bridge_locked(collection_id, item_id)?;
+		ensure!(Self::is_owner(collection_id, item_id, &from), Error::<T, I>::NotOwner);
+		Self::ensure_owner_in_sync(collection_id, item_id, &from)?;
+
+		if let Some(sale_price) = sale_price.filter(|price| !price.is_zero()) {
+			Self::pay_royalty(&from, collection_id, item_id, sale_price)?;
+		}
+
+		if T::Nfts::owner(&collection_id, &item_id).is_some() {
+			T::Nfts::transfer(&collection_id, &item_id, &to)?;
+		}
+		NFTOwners::<T, I>::insert(collection_id, item_id, to.clone());
+
+		Self::deposit_event(Event::Transferred { collection_id, item_id, from, to, sale_price });
+
+		Ok(())
+	}
+
+	/// Pay the royalty of an item sold for `sale_price` from `payer`. Items without a royalty
+	/// record owe nothing.
+	fn pay_royalty(
+		payer: &T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		sale_price: BalanceOf<T, I>,
+	) -> DispatchResult {
+		let Some(royalty) = Royalties::<T, I>::get(collection_id, item_id) else { return Ok(()) };
+		let amount = royalty.rate.mul_floor(sale_price);
+		if amount.is_zero() {
+			return Ok(())
+		}
+
+		match &royalty.beneficiary {
+			RoyaltyBeneficiary::Local(account) => {
+				T::Currency::transfer(payer, account, amount, Preservation::Preserve)?;
+			},
+			RoyaltyBeneficiary::Remote(location) => {
+				T::Currency::transfer(
+					payer,
+					&Self::escrow_account(),
+					amount,
+					Preservation::Preserve,
+				)?;
+				RoyaltyPots::<T, I>::mutate(location, |pot| *pot = pot.saturating_add(amount));
+			},
+		}
+
+		Self::deposit_event(Event::RoyaltyPaid {
+			collection_id,
+			item_id,
+			beneficiary: royalty.beneficiary,
+			amount,
+		});
+
+		Ok(())
+	}
+
+	/// Pay out the royalties escrowed for `location` to `to`
+	pub(crate) fn do_claim_royalties(
+		location: MultiLocation,
+		to: &T::AccountId,
+	) -> Result<BalanceOf<T, I>, DispatchError> {
+		let amount = RoyaltyPots::<T, I>::take(location);
+		ensure!(!amount.is_zero(), Error::<T, I>::NoRoyaltiesToClaim);
+		T::Currency::transfer(
+			&Self::escrow_account(),
+			to,
+			amount,
+			Preservation::Expendable,
+		)?;
+		Ok(amount)
+	}
+}
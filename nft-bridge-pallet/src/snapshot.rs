@@ -0,0 +1,101 @@
+//! Paged export of bridge state for offchain indexers
+//!
+//! `export_state` walks `NFTOwners`, then `PendingTransfers`, then `NFTMetadata`, each in
+//! storage key order, so a run of pages read at one block reproduces the three maps exactly.
+//! The cursor is the raw storage key of the last entry returned: opaque to callers, but valid
+//! against any block, so a page read later continues where the previous one stopped.
+
+use crate::*;
+use frame_support::storage::{PrefixIterator, StoragePrefixedMap};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_std::vec::Vec;
+
+/// Most entries `export_state` returns in one page
+pub const MAX_EXPORT_STATE_PAGE: u32 = 1_000;
+
+/// An entry of `export_state`, in the order and with the keys of the storage it comes from
+pub type BridgeStateEntryOf<T, I> = BridgeStateEntry<
+	<T as Config<I>>::CollectionId,
+	<T as Config<I>>::ItemId,
+	<T as frame_system::Config>::AccountId,
+	BlockNumberFor<T>,
+>;
+
+/// Move entries from `iter` into `page` until it holds `limit`, returning the raw key of the
+/// last one taken if the page filled up
+fn fill_page<K1, K2, V, E>(
+	page: &mut Vec<E>,
+	limit: usize,
+	mut iter: PrefixIterator<(K1, K2, V)>,
+	entry: impl Fn(K1, K2, V) -> E,
+) -> Option<Vec<u8>> {
+	while page.len() < limit {
+		let (key1, key2, value) = iter.next()?;
+		page.push(entry(key1, key2, value));
+	}
+	Some(iter.last_raw_key().to_vec())
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Up to `limit` entries of the bridge's owner, pending transfer and metadata maps after
+	/// `cursor`, and the cursor of the next page, `None` once the export is complete. A cursor
+	/// that isn't a key of one of those maps yields an empty, final page.
+	pub fn export_state(
+		cursor: Option<Vec<u8>>,
+		limit: u32,
+	) -> (Vec<BridgeStateEntryOf<T, I>>, Option<Vec<u8>>) {
+		let limit = limit.min(MAX_EXPORT_STATE_PAGE) as usize;
+		let mut page = Vec::new();
+		if limit == 0 {
+			return (page, cursor)
+		}
+
+		// The map the cursor points into; every map before it is already exported
+		let prefixes = [
+			NFTOwners::<T, I>::final_prefix(),
+			PendingTransfers::<T, I>::final_prefix(),
+			NFTMetadata::<T, I>::final_prefix(),
+		];
+		let (stage, from) = match cursor {
+			None => (0, None),
+			Some(key) => match prefixes.iter().position(|prefix| key.starts_with(prefix)) {
+				Some(stage) => (stage, Some(key)),
+				None => return (page, None),
+			},
+		};
+		let start = |at: usize| from.clone().filter(|_| stage == at);
+
+		if stage == 0 {
+			let iter = match start(0) {
+				Some(key) => NFTOwners::<T, I>::iter_from(key),
+				None => NFTOwners::<T, I>::iter(),
+			};
+			let next = fill_page(&mut page, limit, iter, |collection_id, item_id, owner| {
+				BridgeStateEntry::Owner { collection_id, item_id, owner }
+			});
+			if next.is_some() {
+				return (page, next)
+			}
+		}
+		if stage <= 1 {
+			let iter = match start(1) {
+				Some(key) => PendingTransfers::<T, I>::iter_from(key),
+				None => PendingTransfers::<T, I>::iter(),
+			};
+			let next = fill_page(&mut page, limit, iter, |collection_id, item_id, transfer| {
+				BridgeStateEntry::Pending { collection_id, item_id, transfer }
+			});
+			if next.is_some() {
+				return (page, next)
+			}
+		}
+		let iter = match start(2) {
+			Some(key) => NFTMetadata::<T, I>::iter_from(key),
+			None => NFTMetadata::<T, I>::iter(),
+		};
+		let next = fill_page(&mut page, limit, iter, |collection_id, item_id, metadata| {
+			BridgeStateEntry::Metadata { collection_id, item_id, metadata }
+		});
+		(page, next)
+	}
+}
@@ -0,0 +1,237 @@
+//! Metadata staged ahead of a transfer
+//!
+//! `stage_metadata` stores an item's metadata on this chain against a deposit and reports its
+//! hash. `send_staged_nft` then moves the item without the blob: it is billed as a plain send
+//! and relayers pass only the hash on to the destination, which can fetch the blob from here.
+//! Staged metadata that is not sent within `StagingTimeout` blocks may be purged by anyone.
+//!
+//! A wrapper received with only a hash keeps it in `DeferredMetadata`. Its owner fetches the
+//! blob with `request_metadata`, which asks the origin chain to `serve_metadata`; the origin
+//! answers with `metadata_served`, accepted only for an outstanding request and the right hash.
+
+use crate::*;
+use frame_support::traits::{
+	fungible::MutateHold,
+	tokens::Precision,
+	Get,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{
+	traits::{Hash, Saturating},
+	DispatchError,
+};
+use sp_std::vec::Vec;
+
+/// Staged metadata as stored in `StagedMetadata`
+pub type StagedMetadataOf<T, I = ()> = StagedMetadataInfo<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T, I>,
+	BlockNumberFor<T>,
+	<T as frame_system::Config>::Hash,
+>;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Stage `metadata` for an item `who` owns, replacing anything staged for it before
+	pub(crate) fn do_stage_metadata(
+		who: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		metadata: Vec<u8>,
+		metadata_uri: Option<Vec<u8>>,
+	) -> Result<T::Hash, DispatchError> {
+		ensure!(Self::is_owner(collection_id, item_id, &who), Error::<T, I>::NotOwner);
+		let uri_len = metadata_uri.as_ref().map_or(0, |uri| uri.len());
+		Self::ensure_metadata_within_limits(metadata.len(), uri_len)?;
+
+		if let Some(previous) = StagedMetadata::<T, I>::take(collection_id, item_id) {
+			Self::release_staging_deposit(&previous)?;
+		}
+
+		let bytes = metadata.len().saturating_add(uri_len) as u32;
+		let deposit = T::StagingDepositPerByte::get().saturating_mul(bytes.into());
+		T::Currency::hold(&HoldReason::MetadataDeposit.into(), &who, deposit)?;
+
+		let metadata_hash = T::Hashing::hash(&metadata);
+		StagedMetadata::<T, I>::insert(
+			collection_id,
+			item_id,
+			StagedMetadataInfo {
+				depositor: who,
+				deposit,
+				metadata,
+				metadata_uri,
+				metadata_hash,
+				staged_at: frame_system::Pallet::<T>::block_number(),
+			},
+		);
+
+		Ok(metadata_hash)
+	}
+
+	/// Send an item whose metadata was staged, carrying only the metadata hash
+	pub(crate) fn do_send_staged_nft(
+		sender: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		dest_para_id: u32,
+		valid_until: Option<BlockNumberFor<T>>,
+	) -> Result<T::Hash, DispatchError> {
+		let staged = StagedMetadata::<T, I>::take(collection_id, item_id)
+			.ok_or(Error::<T, I>::StagedMetadataNotFound)?;
+		ensure!(staged.depositor == sender, Error::<T, I>::NotOwner);
+		Self::release_staging_deposit(&staged)?;
+
+		// The metadata fee was covered by the deposit, so the send itself carries no payload
+		Self::do_xcm_transfer_nft(
+			sender,
+			collection_id,
+			item_id,
+			dest_para_id,
+			Vec::new(),
+			None,
+			None,
+			ExecutionLimit::Default,
+			false,
+			valid_until,
+		)?;
+		Self::set_item_metadata(
+			collection_id,
+			item_id,
+			staged.metadata,
+			combine_uris(staged.metadata_uri, Vec::new()),
+		)?;
+
+		Ok(staged.metadata_hash)
+	}
+
+	/// Remove metadata staged more than `StagingTimeout` blocks ago, refunding its deposit
+	pub(crate) fn do_purge_staged_metadata(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> DispatchResult {
+		let staged = StagedMetadata::<T, I>::get(collection_id, item_id)
+			.ok_or(Error::<T, I>::StagedMetadataNotFound)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(
+			now >= staged.staged_at.saturating_add(T::StagingTimeout::get()),
+			Error::<T, I>::StagingNotExpired
+		);
+
+		StagedMetadata::<T, I>::remove(collection_id, item_id);
+		Self::release_staging_deposit(&staged)
+	}
+
+	/// Ask the origin chain of a wrapper for its deferred metadata
+	pub(crate) fn do_request_metadata(
+		who: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> DispatchResult {
+		ensure!(Self::is_owner(collection_id, item_id, &who), Error::<T, I>::NotOwner);
+		ensure!(
+			DeferredMetadata::<T, I>::contains_key(collection_id, item_id),
+			Error::<T, I>::NoMetadataToFetch
+		);
+		let from_para_id =
+			ItemOrigin::<T, I>::get(collection_id, item_id)
+				.ok_or(Error::<T, I>::NoMetadataToFetch)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		if let Some(expires_at) = MetadataRequests::<T, I>::get(collection_id, item_id) {
+			ensure!(now >= expires_at, Error::<T, I>::MetadataRequestPending);
+		}
+
+		let remote_item_id = Self::remote_item_id(collection_id, item_id, from_para_id);
+		Self::send_bridge_call(
+			from_para_id,
+			Call::<T, I>::serve_metadata {
+				collection_id: Self::remote_collection_id(collection_id, from_para_id),
+				item_id: remote_item_id,
+			},
+		)?;
+		let expires_at = now.saturating_add(T::MetadataRequestTimeout::get());
+		MetadataRequests::<T, I>::insert(collection_id, item_id, expires_at);
+
+		Self::deposit_event(Event::MetadataRequested { collection_id, item_id, from_para_id });
+
+		Ok(())
+	}
+
+	/// Send an item's metadata to `dest_para_id`, which must hold the item or have it in flight
+	pub(crate) fn do_serve_metadata(
+		dest_para_id: u32,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> DispatchResult {
+		let sent_there = PendingTransfers::<T, I>::get(collection_id, item_id)
+			.map_or(false, |transfer| Self::sibling_para_id(&transfer.dest) == Some(dest_para_id));
+		ensure!(
+			sent_there || ItemsAbroad::<T, I>::get(collection_id, item_id) == Some(dest_para_id),
+			Error::<T, I>::OriginMismatch
+		);
+		let metadata =
+			NFTMetadata::<T, I>::get(collection_id, item_id).ok_or(Error::<T, I>::NFTNotFound)?;
+		let metadata_uri = Self::nft_metadata_uri(collection_id, item_id);
+
+		Self::send_bridge_call(
+			dest_para_id,
+			Call::<T, I>::metadata_served { collection_id, item_id, metadata, metadata_uri },
+		)?;
+
+		Self::deposit_event(Event::MetadataServed { collection_id, item_id, dest_para_id });
+
+		Ok(())
+	}
+
+	/// Store metadata answering an outstanding request once it matches the deferred hash
+	pub(crate) fn do_metadata_served(
+		from_para_id: u32,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		metadata: Vec<u8>,
+		metadata_uri: Option<Vec<u8>>,
+	) -> DispatchResult {
+		let collection_id = Self::local_collection_id(from_para_id, collection_id);
+		let item_id = RemoteItemRemap::<T, I>::get((from_para_id, collection_id), item_id)
+			.unwrap_or(item_id);
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(
+			MetadataRequests::<T, I>::get(collection_id, item_id).map_or(false, |at| now < at),
+			Error::<T, I>::MetadataRequestNotFound
+		);
+		ensure!(
+			ItemOrigin::<T, I>::get(collection_id, item_id) == Some(from_para_id),
+			Error::<T, I>::WrongReserve
+		);
+		let expected = DeferredMetadata::<T, I>::get(collection_id, item_id)
+			.ok_or(Error::<T, I>::MetadataRequestNotFound)?;
+		ensure!(T::Hashing::hash(&metadata) == expected, Error::<T, I>::MetadataHashMismatch);
+		Self::ensure_metadata_within_limits(
+			metadata.len(),
+			metadata_uri.as_ref().map_or(0, |uri| uri.len()),
+		)?;
+
+		Self::set_item_metadata(
+			collection_id,
+			item_id,
+			metadata,
+			combine_uris(metadata_uri, Vec::new()),
+		)?;
+		MetadataRequests::<T, I>::remove(collection_id, item_id);
+		DeferredMetadata::<T, I>::remove(collection_id, item_id);
+
+		Self::deposit_event(Event::MetadataFetched { collection_id, item_id });
+
+		Ok(())
+	}
+
+	/// Release a staging deposit held on its depositor
+	pub(crate) fn release_staging_deposit(staged: &StagedMetadataOf<T, I>) -> DispatchResult {
+		T::Currency::release(
+			&HoldReason::MetadataDeposit.into(),
+			&staged.depositor,
+			staged.deposit,
+			Precision::Exact,
+		)?;
+		Ok(())
+	}
+}
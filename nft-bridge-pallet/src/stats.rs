@@ -0,0 +1,48 @@
+//! Periodic bridge statistics
+//!
+//! Monitoring that can't query state follows `PeriodStats`, emitted in `on_initialize` of every
+//! block that is a multiple of `StatsPeriod`. Activity is counted in `PeriodCounters`, reset with
+//! each event, and in `LifetimeCounters`, which is never reset. `pending` is the number of
+//! transfers in flight when the period ends, summed over at most `STATS_SCAN_LIMIT`
+//! destinations; transfers still queued on this chain aren't included.
+
+use crate::*;
+use frame_support::{traits::Get, weights::Weight};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::Zero;
+use sp_std::vec::Vec;
+use xcm_handler::STATS_SCAN_LIMIT;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Add `by` to one of the counters, for the current period and for all time
+	pub(crate) fn count_activity(counter: fn(&mut BridgeCounters) -> &mut u32, by: u32) {
+		let add = |counters: &mut BridgeCounters| {
+			let count = counter(counters);
+			*count = count.saturating_add(by);
+		};
+		PeriodCounters::<T, I>::mutate(add);
+		LifetimeCounters::<T, I>::mutate(add);
+	}
+
+	/// Emit `PeriodStats` and start a new period if `now` ends one
+	pub(crate) fn emit_period_stats(now: BlockNumberFor<T>) -> Weight {
+		let period = T::StatsPeriod::get();
+		if period.is_zero() || !(now % period).is_zero() {
+			return Weight::zero()
+		}
+
+		let counters = PeriodCounters::<T, I>::take();
+		let in_flight: Vec<u32> =
+			InFlightCount::<T, I>::iter_values().take(STATS_SCAN_LIMIT).collect();
+		Self::deposit_event(Event::PeriodStats {
+			sent: counters.sent,
+			received: counters.received,
+			cancelled: counters.cancelled,
+			expired: counters.expired,
+			pending: in_flight.iter().fold(0u32, |total, count| total.saturating_add(*count)),
+		});
+
+		T::WeightInfo::emit_period_stats()
+			.saturating_add(T::DbWeight::get().reads(in_flight.len() as u64))
+	}
+}
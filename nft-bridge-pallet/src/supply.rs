@@ -0,0 +1,93 @@
+//! Maximum supply of wrapped collections
+//!
+//! `WrappedSupply` counts the wrappers of each collection held on this chain. It grows when a
+//! wrapper arrives and shrinks when one leaves for another chain or is burned. An inbound item
+//! that would take it past the collection's `max_supply` is diverted rather than bounced: like
+//! an NFT whose beneficiary can't be resolved, it is minted to the sovereign account of its
+//! source chain and recorded in `OrphanedInbound`, where governance can hand it to the recipient
+//! with `reassign_orphaned` once the collection has room for it again. Parked items don't count
+//! towards the supply until then. The NFT backend's own supply limits are left to the backend,
+//! whose refusal fails the mint.
+
+use crate::*;
+use sp_runtime::traits::Convert;
+use sp_std::vec::Vec;
+use xcm_executor::traits::Convert as XcmConvert;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Whether receiving the item adds a wrapper here: it is new to this chain, or a wrapper
+	/// coming back from abroad. A pending item never left, and native items aren't wrappers.
+	pub(crate) fn adds_wrapper(collection_id: T::CollectionId, item_id: T::ItemId) -> bool {
+		if PendingTransfers::<T, I>::contains_key(collection_id, item_id) {
+			return false
+		}
+		!ItemsAbroad::<T, I>::contains_key(collection_id, item_id) ||
+			ItemOrigin::<T, I>::contains_key(collection_id, item_id)
+	}
+
+	/// Whether the collection has room for one more wrapper under its `max_supply`
+	pub(crate) fn has_wrapper_capacity(collection_id: T::CollectionId) -> bool {
+		Collections::<T, I>::get(collection_id)
+			.and_then(|info| info.max_supply)
+			.map_or(true, |max| WrappedSupply::<T, I>::get(collection_id) < max)
+	}
+
+	pub(crate) fn note_wrapper_added(collection_id: T::CollectionId) {
+		WrappedSupply::<T, I>::mutate(collection_id, |supply| *supply = supply.saturating_add(1));
+	}
+
+	/// Count a wrapper out, if the item is one. A parked wrapper was never counted in.
+	pub(crate) fn note_wrapper_removed(collection_id: T::CollectionId, item_id: T::ItemId) {
+		if !ItemOrigin::<T, I>::contains_key(collection_id, item_id) ||
+			OrphanedInbound::<T, I>::contains_key(collection_id, item_id)
+		{
+			return
+		}
+		WrappedSupply::<T, I>::mutate_exists(collection_id, |supply| {
+			*supply = supply.map(|supply| supply.saturating_sub(1)).filter(|supply| *supply > 0);
+		});
+	}
+
+	/// Park an inbound item its collection has no room for with the sovereign account of
+	/// `from_para_id`, keeping its metadata, and record the receive as failed
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn divert_over_supply(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		from_para_id: u32,
+		remote_ids: (T::CollectionId, T::ItemId),
+		recipient: &T::AccountId,
+		metadata: Option<Vec<u8>>,
+		metadata_uris: Option<ItemUris>,
+	) -> DispatchResult {
+		let metadata = metadata.unwrap_or_else(|| {
+			NFTMetadata::<T, I>::get(collection_id, item_id).unwrap_or_default()
+		});
+		let (uri_len, _) = Self::uri_lengths(&metadata_uris);
+		Self::ensure_metadata_within_limits(metadata.len(), uri_len)?;
+		Self::set_item_metadata(collection_id, item_id, metadata, metadata_uris)?;
+
+		// Without an account to park it with the item can only be refused
+		let holder = T::LocationToAccountId::convert(Self::dest_location(from_para_id))
+			.map_err(|_| Error::<T, I>::CollectionSupplyExceeded)?;
+		// A wrapper back from abroad is still in the backend, anything else is minted there
+		let returning = ItemsAbroad::<T, I>::take(collection_id, item_id).is_some();
+		let deposited = Self::transactor_deposit(collection_id, item_id, &holder)?;
+		if !deposited && !returning {
+			Self::backend_mint(collection_id, item_id, &holder)?;
+		}
+		NFTOwners::<T, I>::insert(collection_id, item_id, &holder);
+		if !returning {
+			ItemOrigin::<T, I>::insert(collection_id, item_id, from_para_id);
+		}
+		let beneficiary = T::AccountIdToLocation::convert(recipient.clone());
+		let orphan = OrphanedNft { from_para_id, holder, beneficiary };
+		Self::note_orphaned(collection_id, item_id, orphan);
+
+		let (remote_collection_id, remote_item_id) = remote_ids;
+		let error = Error::<T, I>::CollectionSupplyExceeded.into();
+		Self::note_inbound_failure(from_para_id, remote_collection_id, remote_item_id, error);
+
+		Ok(())
+	}
+}
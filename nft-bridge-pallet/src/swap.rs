@@ -0,0 +1,343 @@
+//! Cross-chain NFT swaps
+//!
+//! A swap runs between the proposing chain A and the counterpart chain B, both running this
+//! pallet:
+//! 1. `propose_swap` on A escrows the offered NFT and sends the offer to B (`swap_offered`)
+//! 2. `accept_swap` on B escrows the requested NFT and tells A (`swap_accepted`)
+//! 3. If the deadline has not passed, A bridges the offered NFT to the requested NFT's owner and
+//!    tells B (`swap_settled`), which bridges the requested NFT to the proposer
+//!
+//! Escrowed NFTs keep their owner but cannot be sent or transferred. A releases its escrow once
+//! the deadline has passed. B cannot read A's block numbers, so it releases its escrow
+//! `SwapAcceptTimeout` of its own blocks after accepting.
+
+use crate::*;
+use frame_support::traits::Get;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::vec::Vec;
+use xcm::v3::prelude::*;
+
+/// A swap offer as stored in `PendingSwaps` and `IncomingSwaps`
+pub type SwapOfferOf<T, I = ()> = SwapOffer<
+	<T as frame_system::Config>::AccountId,
+	<T as Config<I>>::CollectionId,
+	<T as Config<I>>::ItemId,
+	BlockNumberFor<T>,
+>;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Escrow the proposer's NFT, record the swap and send the offer to `their_para`
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn do_propose_swap(
+		proposer: T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		their_para: u32,
+		their_collection_id: T::CollectionId,
+		their_item_id: T::ItemId,
+		their_owner: T::AccountId,
+		deadline: BlockNumberFor<T>,
+	) -> Result<SwapId, DispatchError> {
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(deadline > now, Error::<T, I>::SwapDeadlinePassed);
+
+		Self::escrow_for_swap(&proposer, collection_id, item_id)?;
+
+		let swap_id = NextSwapId::<T, I>::mutate(|id| {
+			let current = *id;
+			*id = id.wrapping_add(1);
+			current
+		});
+		let offer = SwapOffer {
+			proposer: proposer.clone(),
+			collection_id,
+			item_id,
+			their_para,
+			their_collection_id,
+			their_item_id,
+			their_owner,
+			deadline,
+			state: SwapState::Offered { at: now },
+		};
+		let call = Call::<T, I>::swap_offered { swap_id, offer: offer.clone() };
+		Self::send_bridge_call(their_para, call)?;
+		PendingSwaps::<T, I>::insert(swap_id, offer);
+
+		Self::deposit_event(Event::SwapProposed { swap_id, proposer, their_para });
+
+		Ok(swap_id)
+	}
+
+	/// Record an offer received from `from_para_id`
+	pub(crate) fn do_swap_offered(
+		from_para_id: u32,
+		swap_id: SwapId,
+		mut offer: SwapOfferOf<T, I>,
+	) -> DispatchResult {
+		ensure!(
+			!IncomingSwaps::<T, I>::contains_key(from_para_id, swap_id),
+			Error::<T, I>::SwapAlreadyAccepted
+		);
+
+		offer.state = SwapState::Offered { at: frame_system::Pallet::<T>::block_number() };
+		let their_owner = offer.their_owner.clone();
+		IncomingSwaps::<T, I>::insert(from_para_id, swap_id, offer);
+
+		Self::deposit_event(Event::SwapOffered { from_para_id, swap_id, their_owner });
+
+		Ok(())
+	}
+
+	/// Escrow the requested NFT and tell the proposing chain the offer was accepted
+	pub(crate) fn do_accept_swap(
+		who: T::AccountId,
+		from_para_id: u32,
+		swap_id: SwapId,
+	) -> DispatchResult {
+		let mut offer =
+			IncomingSwaps::<T, I>::get(from_para_id, swap_id).ok_or(Error::<T, I>::SwapNotFound)?;
+		ensure!(offer.their_owner == who, Error::<T, I>::NotSwapParty);
+		ensure!(
+			matches!(offer.state, SwapState::Offered { .. }),
+			Error::<T, I>::SwapAlreadyAccepted
+		);
+
+		Self::escrow_for_swap(&who, offer.their_collection_id, offer.their_item_id)?;
+		Self::send_bridge_call(from_para_id, Call::<T, I>::swap_accepted { swap_id })?;
+
+		offer.state = SwapState::Accepted { at: frame_system::Pallet::<T>::block_number() };
+		IncomingSwaps::<T, I>::insert(from_para_id, swap_id, offer);
+
+		Self::deposit_event(Event::SwapAccepted { from_para_id, swap_id });
+
+		Ok(())
+	}
+
+	/// The counterpart accepted: deliver the offered NFT and ask the counterpart to deliver theirs
+	pub(crate) fn do_swap_accepted(from_para_id: u32, swap_id: SwapId) -> DispatchResult {
+		let offer = PendingSwaps::<T, I>::get(swap_id).ok_or(Error::<T, I>::SwapNotFound)?;
+		ensure!(offer.their_para == from_para_id, Error::<T, I>::OriginMismatch);
+		ensure!(
+			frame_system::Pallet::<T>::block_number() <= offer.deadline,
+			Error::<T, I>::SwapDeadlinePassed
+		);
+
+		SwapEscrow::<T, I>::remove(offer.collection_id, offer.item_id);
+		Self::do_swap_delivery(
+			&offer.proposer,
+			offer.collection_id,
+			offer.item_id,
+			from_para_id,
+			&offer.their_owner,
+		)?;
+		Self::send_bridge_call(from_para_id, Call::<T, I>::swap_settled { swap_id })?;
+		PendingSwaps::<T, I>::remove(swap_id);
+
+		Self::deposit_event(Event::SwapCompleted { swap_id });
+
+		Ok(())
+	}
+
+	/// The proposing chain delivered its NFT: deliver the requested NFT to the proposer
+	pub(crate) fn do_swap_settled(from_para_id: u32, swap_id: SwapId) -> DispatchResult {
+		let offer =
+			IncomingSwaps::<T, I>::get(from_para_id, swap_id).ok_or(Error::<T, I>::SwapNotFound)?;
+		ensure!(matches!(offer.state, SwapState::Accepted { .. }), Error::<T, I>::SwapNotFound);
+
+		SwapEscrow::<T, I>::remove(offer.their_collection_id, offer.their_item_id);
+		Self::do_swap_delivery(
+			&offer.their_owner,
+			offer.their_collection_id,
+			offer.their_item_id,
+			from_para_id,
+			&offer.proposer,
+		)?;
+		IncomingSwaps::<T, I>::remove(from_para_id, swap_id);
+
+		Self::deposit_event(Event::SwapSettled { from_para_id, swap_id });
+
+		Ok(())
+	}
+
+	/// Withdraw from a swap and release the caller's escrowed NFT.
+	///
+	/// `from_para_id` is `None` for a swap proposed on this chain, which can be cancelled once its
+	/// deadline has passed. An offer received from `from_para_id` can be declined at any time
+	/// before accepting, and abandoned `SwapAcceptTimeout` blocks after accepting.
+	pub(crate) fn do_cancel_swap(
+		who: T::AccountId,
+		from_para_id: Option<u32>,
+		swap_id: SwapId,
+	) -> DispatchResult {
+		let now = frame_system::Pallet::<T>::block_number();
+		match from_para_id {
+			None => {
+				let offer = PendingSwaps::<T, I>::get(swap_id).ok_or(Error::<T, I>::SwapNotFound)?;
+				ensure!(offer.proposer == who, Error::<T, I>::NotSwapParty);
+				ensure!(now > offer.deadline, Error::<T, I>::SwapDeadlineNotReached);
+				SwapEscrow::<T, I>::remove(offer.collection_id, offer.item_id);
+				PendingSwaps::<T, I>::remove(swap_id);
+			},
+			Some(from) => {
+				let offer =
+					IncomingSwaps::<T, I>::get(from, swap_id).ok_or(Error::<T, I>::SwapNotFound)?;
+				ensure!(offer.their_owner == who, Error::<T, I>::NotSwapParty);
+				if let SwapState::Accepted { at } = offer.state {
+					ensure!(
+						now > at.saturating_add(T::SwapAcceptTimeout::get()),
+						Error::<T, I>::SwapDeadlineNotReached
+					);
+					SwapEscrow::<T, I>::remove(offer.their_collection_id, offer.their_item_id);
+				}
+				IncomingSwaps::<T, I>::remove(from, swap_id);
+			},
+		}
+
+		Self::deposit_event(Event::SwapCancelled { from_para_id, swap_id });
+
+		Ok(())
+	}
+
+	/// Drop swaps nobody can complete any more and release their escrow, scanning as many
+	/// entries as `remaining_weight` allows from where the previous sweep stopped
+	pub(crate) fn expire_swaps(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+		let per_item = T::WeightInfo::expire_swap();
+		let limit = remaining_weight.checked_div_per_component(&per_item).unwrap_or(0);
+		if limit == 0 {
+			return Weight::zero()
+		}
+
+		// Swaps proposed here lapse at their deadline
+		let mut iter = match SwapExpiryCursor::<T, I>::get() {
+			Some(key) => PendingSwaps::<T, I>::iter_from(key),
+			None => PendingSwaps::<T, I>::iter(),
+		};
+		let mut expired = Vec::new();
+		let mut scanned = 0u64;
+		while scanned < limit {
+			let Some((swap_id, offer)) = iter.next() else { break };
+			scanned += 1;
+			if now > offer.deadline {
+				expired.push((None, swap_id, Some((offer.collection_id, offer.item_id))));
+			}
+		}
+		if scanned < limit {
+			SwapExpiryCursor::<T, I>::kill();
+		} else {
+			SwapExpiryCursor::<T, I>::put(iter.last_raw_key());
+		}
+
+		// Offers received here lapse `SwapAcceptTimeout` blocks after their last change
+		let mut iter = match IncomingSwapExpiryCursor::<T, I>::get() {
+			Some(key) => IncomingSwaps::<T, I>::iter_from(key),
+			None => IncomingSwaps::<T, I>::iter(),
+		};
+		let start = scanned;
+		while scanned < limit {
+			let Some((from, swap_id, offer)) = iter.next() else { break };
+			scanned += 1;
+			// An offer that was never accepted here escrowed nothing
+			let (at, escrowed) = match offer.state {
+				SwapState::Offered { at } => (at, None),
+				SwapState::Accepted { at } =>
+					(at, Some((offer.their_collection_id, offer.their_item_id))),
+			};
+			if now > at.saturating_add(T::SwapAcceptTimeout::get()) {
+				expired.push((Some(from), swap_id, escrowed));
+			}
+		}
+		if scanned < limit {
+			IncomingSwapExpiryCursor::<T, I>::kill();
+		} else if scanned > start {
+			IncomingSwapExpiryCursor::<T, I>::put(iter.last_raw_key());
+		}
+
+		for (from_para_id, swap_id, escrowed) in expired {
+			match from_para_id {
+				None => PendingSwaps::<T, I>::remove(swap_id),
+				Some(from) => IncomingSwaps::<T, I>::remove(from, swap_id),
+			}
+			if let Some((collection_id, item_id)) = escrowed {
+				SwapEscrow::<T, I>::remove(collection_id, item_id);
+			}
+			Self::deposit_event(Event::SwapExpired { from_para_id, swap_id });
+		}
+
+		per_item.saturating_mul(scanned)
+	}
+
+	/// Hold an NFT for a swap. Its owner keeps it, but it cannot be sent or transferred.
+	fn escrow_for_swap(
+		who: &T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+	) -> DispatchResult {
+		Self::ensure_transferable(collection_id)?;
+		Self::ensure_not_migrating(collection_id)?;
+		ensure!(
+			!PendingTransfers::<T, I>::contains_key(collection_id, item_id),
+			Error::<T, I>::TransferAlreadyPending
+		);
+		ensure!(
+			!SwapEscrow::<T, I>::contains_key(collection_id, item_id),
+			Error::<T, I>::ItemInSwap
+		);
+		let owner = Self::owner(collection_id, item_id).ok_or(Error::<T, I>::NFTNotFound)?;
+		ensure!(&owner == who, Error::<T, I>::NotOwner);
+		Self::ensure_owner_in_sync(collection_id, item_id, &owner)?;
+
+		SwapEscrow::<T, I>::insert(collection_id, item_id, ());
+
+		Ok(())
+	}
+
+	/// Bridge a released escrow NFT to `beneficiary` on `dest_para_id`.
+	///
+	/// Swap deliveries are sent immediately: queueing them behind the destination's in-flight
+	/// cap could hold one side of a settled swap indefinitely.
+	fn do_swap_delivery(
+		owner: &T::AccountId,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		dest_para_id: u32,
+		beneficiary: &T::AccountId,
+	) -> DispatchResult {
+		let metadata = NFTMetadata::<T, I>::get(collection_id, item_id).unwrap_or_default();
+		Self::ensure_metadata_fits_destination(dest_para_id, metadata.len())?;
+		let transfer_id = Self::begin_transfer(
+			owner,
+			collection_id,
+			item_id,
+			dest_para_id,
+			metadata,
+			None,
+			TransferStatus::InFlight,
+			None,
+		)?;
+		let message = Self::build_program(
+			beneficiary,
+			&[(collection_id, item_id)],
+			&Self::dest_location(dest_para_id),
+			None,
+			ExecutionLimit::Default,
+		)?;
+		let (message_hash, delivery_fee) = Self::send_paid(owner, dest_para_id, message)?;
+
+		Self::index_message(message_hash, transfer_id);
+		InFlightCount::<T, I>::mutate(dest_para_id, |count| *count = count.saturating_add(1));
+		Self::count_activity(|counters| &mut counters.sent, 1);
+		// The swap itself is the settlement, so its deliveries carry no bridging fee
+		Self::deposit_event(Event::NFTSent {
+			collection_id,
+			item_id,
+			dest_para_id,
+			fee: Zero::zero(),
+			delivery_fee,
+			canonical_id: Self::note_canonical_id(collection_id, item_id),
+		});
+		T::OnNftSent::on_bridged(collection_id, item_id, dest_para_id, owner);
+
+		Ok(())
+	}
+}
@@ -4,21 +4,67 @@
 #[cfg(test)]
 mod tests {
     use crate::*;
+    use codec::{Decode, Encode};
     use frame_support::{
         assert_ok, assert_noop,
         dispatch::DispatchResult,
         parameter_types,
-        traits::{ConstU32, ConstU64, Everything},
+        traits::{
+            tokens::nonfungibles, ConstU32, ConstU64, Contains, Everything, Get,
+            PalletInfo as _,
+        },
+        instances::Instance2,
+        BoundedVec, PalletId,
     };
     use sp_core::H256;
     use sp_runtime::{
         testing::Header,
-        traits::{BlakeTwo256, IdentityLookup},
+        traits::{BlakeTwo256, Convert, IdentityLookup, ValidateUnsigned},
+        transaction_validity::TransactionSource,
+        Perbill,
     };
+    use xcm::v3::prelude::*;
 
     type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
     type Block = frame_system::mocking::MockBlock<Test>;
 
+    // Stand-in for a marketplace pallet that bridges items as part of sale settlement
+    #[frame_support::pallet]
+    pub mod pallet_marketplace {
+        use crate::{NftBridging, TransferId};
+        use frame_support::pallet_prelude::*;
+        use frame_system::pallet_prelude::*;
+
+        #[pallet::pallet]
+        pub struct Pallet<T>(_);
+
+        #[pallet::config]
+        pub trait Config: frame_system::Config + crate::Config {
+            type Bridge: NftBridging<Self::AccountId, Self::CollectionId, Self::ItemId>;
+        }
+
+        #[pallet::storage]
+        pub type LastSettlement<T> = StorageValue<_, TransferId, OptionQuery>;
+
+        #[pallet::call]
+        impl<T: Config> Pallet<T> {
+            #[pallet::call_index(0)]
+            #[pallet::weight(0)]
+            pub fn settle_and_bridge(
+                origin: OriginFor<T>,
+                collection_id: T::CollectionId,
+                item_id: T::ItemId,
+                dest_para_id: u32,
+            ) -> DispatchResult {
+                let seller = ensure_signed(origin)?;
+                let transfer_id =
+                    T::Bridge::send(seller, collection_id, item_id, dest_para_id, Vec::new(), None)?;
+                LastSettlement::<T>::put(transfer_id);
+                Ok(())
+            }
+        }
+    }
+
     frame_support::construct_runtime!(
         pub enum Test where
             Block = Block,
@@ -26,7 +72,13 @@ mod tests {
             UncheckedExtrinsic = UncheckedExtrinsic,
         {
             System: frame_system,
+            Balances: pallet_balances,
+            Uniques: pallet_uniques,
             NftBridge: pallet_nft_bridge,
+            OtherBridge: pallet_nft_bridge::<Instance2>::{
+                Pallet, Call, Storage, Event<T>, ValidateUnsigned
+            },
+            Marketplace: pallet_marketplace,
         }
     );
 
@@ -53,7 +105,7 @@ mod tests {
         type DbWeight = ();
         type Version = ();
         type PalletInfo = PalletInfo;
-        type AccountData = ();
+        type AccountData = pallet_balances::AccountData<u64>;
         type OnNewAccount = ();
         type OnKilledAccount = ();
         type SystemWeightInfo = ();
@@ -62,23 +114,350 @@ mod tests {
         type MaxConsumers = frame_support::traits::ConstU32<16>;
     }
 
+    impl pallet_balances::Config for Test {
+        type Balance = u64;
+        type DustRemoval = ();
+        type RuntimeEvent = RuntimeEvent;
+        type ExistentialDeposit = ConstU64<1>;
+        type AccountStore = System;
+        type WeightInfo = ();
+        type MaxLocks = ();
+        type MaxReserves = ();
+        type ReserveIdentifier = [u8; 8];
+        type FreezeIdentifier = ();
+        type MaxFreezes = ();
+        type HoldIdentifier = RuntimeHoldReason;
+        type MaxHolds = ConstU32<3>;
+    }
+
+    impl pallet_uniques::Config for Test {
+        type RuntimeEvent = RuntimeEvent;
+        type CollectionId = u32;
+        type ItemId = u32;
+        type Currency = Balances;
+        type ForceOrigin = frame_system::EnsureRoot<u64>;
+        type CreateOrigin =
+            frame_support::traits::AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+        type Locker = ();
+        type CollectionDeposit = ConstU64<0>;
+        type ItemDeposit = ConstU64<0>;
+        type MetadataDepositBase = ConstU64<0>;
+        type AttributeDepositBase = ConstU64<0>;
+        type DepositPerByte = ConstU64<0>;
+        type StringLimit = ConstU32<64>;
+        type KeyLimit = ConstU32<32>;
+        type ValueLimit = ConstU32<64>;
+        #[cfg(feature = "runtime-benchmarks")]
+        type Helper = ();
+        type WeightInfo = ();
+    }
+
     // Mock configuration for the NFT Bridge pallet
     parameter_types! {
         pub const NftBridgePalletId: PalletId = PalletId(*b"nftbridg");
+        pub const OtherBridgePalletId: PalletId = PalletId(*b"nftbrdg2");
+        pub const RemoteExecutionFee: u128 = 1_000_000_000;
+        pub const RejectionReportFee: u128 = 1_000_000;
+        pub static AllowRemoteCalls: bool = true;
+        pub static FailSends: u32 = 0;
+        pub static FailDeliveries: u32 = 0;
+        pub static UnixNow: u64 = 1_700_000_000;
+        pub static HistoryDepositPerHop: u64 = 0;
+        pub static UntrustedSources: Vec<u32> = vec![];
+        pub static RequireLockProof: bool = false;
+        pub static BridgeLockStrategy: LockStrategy = LockStrategy::Detach;
+        pub static Permissionless: bool = true;
+        pub static AutoRegister: bool = false;
+        pub static WrappedRange: Option<(u32, u32)> = None;
+        pub static ItemIdOverride: Option<u32> = None;
+        pub static TransactorEnabled: bool = false;
+        pub static BackendMints: bool = false;
+        pub static MaxAttributes: u32 = 2;
+        pub static MaxAttributeLen: u32 = 1024;
+        pub static TransactorCalls: Vec<(&'static str, MultiAsset, MultiLocation)> = vec![];
+        pub const RetryBackoff: u64 = 10;
+        pub static BridgeFee: u64 = 0;
+        pub static FeePerMetadataByte: u64 = 0;
+        pub static DeliveryFee: u128 = 0;
+        pub static MaxPendingPerAccount: u32 = 64;
+        pub static MaxOutboundMessageSize: u32 = 64 * 1024;
+        pub const MaxMetadataLen: u32 = 1024;
+        pub const MaxUriLen: u32 = 256;
+        pub const FeeAccount: u64 = 99;
+        pub const RemoteCallWeight: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+        pub const DefaultWeightLimit: Weight = Weight::from_parts(400_000_000_000, 64 * 1024);
+    }
+
+    thread_local! {
+        static SENT_XCM: std::cell::RefCell<Vec<(MultiLocation, xcm::VersionedXcm<()>)>> =
+            std::cell::RefCell::new(Vec::new());
+    }
+
+    /// Messages handed to the mock XCM sender, in delivery order and the version they were sent in
+    pub fn sent_versioned_xcm() -> Vec<(MultiLocation, xcm::VersionedXcm<()>)> {
+        SENT_XCM.with(|q| q.borrow().clone())
+    }
+
+    /// The hash the mock XCM sender returned for the last message it delivered
+    pub fn last_message_hash() -> XcmHash {
+        let sent = sent_versioned_xcm();
+        let index = sent.len() - 1;
+        (index as u32, &sent[index].1).using_encoded(sp_io::hashing::blake2_256)
+    }
+
+    /// Messages handed to the mock XCM sender, in delivery order
+    pub fn sent_xcm() -> Vec<(MultiLocation, Xcm<()>)> {
+        sent_versioned_xcm()
+            .into_iter()
+            .map(|(dest, message)| (dest, message.try_into().expect("mock messages convert")))
+            .collect()
     }
 
     // Mock XCM sender that just records messages for testing
     pub struct MockXcmSender;
-    impl SendXcm for MockXcmSender {
-        type Ticket = ();
+    impl SendVersionedXcm for MockXcmSender {
+        type Ticket = (MultiLocation, xcm::VersionedXcm<()>);
         fn validate(
-            _destination: &mut Option<MultiLocation>,
-            _message: &mut Option<Xcm<()>>,
+            destination: MultiLocation,
+            message: xcm::VersionedXcm<()>,
         ) -> SendResult<Self::Ticket> {
-            Ok(((), MultiAssets::new()))
+            // Refuse the next `FailSends` messages
+            if FailSends::get() > 0 {
+                FailSends::set(FailSends::get() - 1);
+                return Err(SendError::Transport("mock"));
+            }
+            // Ask for `DeliveryFee` of the relay token, like a transport with computed fees
+            let fees = match DeliveryFee::get() {
+                0 => MultiAssets::new(),
+                fee => (Parent, fee).into(),
+            };
+            Ok(((destination, message), fees))
+        }
+        fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+            // Lose the next `FailDeliveries` messages that passed `validate`
+            if FailDeliveries::get() > 0 {
+                FailDeliveries::set(FailDeliveries::get() - 1);
+                return Err(SendError::Transport("mock delivery"));
+            }
+            // Identical messages sent twice still get distinct hashes, like a real transport
+            let hash = SENT_XCM.with(|q| {
+                let mut q = q.borrow_mut();
+                let hash = (q.len() as u32, &ticket.1).using_encoded(sp_io::hashing::blake2_256);
+                q.push(ticket);
+                hash
+            });
+            Ok(hash)
+        }
+    }
+
+    // Delivery fees are paid 1:1 in the relay token; nothing else is accepted
+    pub struct RelayTokenToBalance;
+    impl Convert<MultiAssets, Option<u64>> for RelayTokenToBalance {
+        fn convert(assets: MultiAssets) -> Option<u64> {
+            match assets.into_inner().as_slice() {
+                [MultiAsset { id: Concrete(location), fun: Fungible(amount) }]
+                    if *location == MultiLocation::parent() =>
+                    (*amount).try_into().ok(),
+                _ => None,
+            }
+        }
+    }
+
+    thread_local! {
+        static BRIDGED: std::cell::RefCell<Vec<(&'static str, u32, u32, u32, u64)>> =
+            std::cell::RefCell::new(Vec::new());
+    }
+
+    /// Calls made to the bridge hooks, tagged with the hook that was called
+    pub fn bridged_calls() -> Vec<(&'static str, u32, u32, u32, u64)> {
+        BRIDGED.with(|b| b.borrow().clone())
+    }
+
+    // Hook that records every invocation under the given tag
+    pub struct RecordBridged<Tag>(sp_std::marker::PhantomData<Tag>);
+    impl<Tag: Get<&'static str>> OnNftBridged<Test> for RecordBridged<Tag> {
+        fn on_bridged(collection_id: u32, item_id: u32, counterpart_para: u32, account: &u64) {
+            BRIDGED.with(|b| {
+                b.borrow_mut().push((Tag::get(), collection_id, item_id, counterpart_para, *account))
+            });
+        }
+    }
+
+    parameter_types! {
+        pub const SentTag: &'static str = "sent";
+        pub const ReceivedTag: &'static str = "received";
+    }
+
+    // Runtime policy refusing every transfer to para 6000
+    pub struct GeofenceFilter;
+    impl EnsureNftTransfer<Test> for GeofenceFilter {
+        fn check(_: &u64, _: &u32, _: &u32, dest_para_id: u32) -> DispatchResult {
+            if dest_para_id == 6000 {
+                return Err(sp_runtime::DispatchError::Other("GeofencedDestination"))
+            }
+            Ok(())
+        }
+    }
+
+    // With an override `base`, offers base, base + 1 and base + 2; otherwise keeps the remote id
+    pub struct TestItemIds;
+    impl DeriveItemId<Test> for TestItemIds {
+        fn derive(_: u32, _: &u32, remote_item_id: &u32, attempt: u32) -> Option<u32> {
+            match ItemIdOverride::get() {
+                Some(base) => (attempt < 3).then_some(base + attempt),
+                None => (attempt == 0).then_some(*remote_item_id),
+            }
+        }
+    }
+
+    // Records every NFT movement while enabled; otherwise handles no assets at all
+    pub struct RecordingTransactor;
+    impl xcm_executor::traits::TransactAsset for RecordingTransactor {
+        fn deposit_asset(what: &MultiAsset, who: &MultiLocation, _: &XcmContext) -> XcmResult {
+            if !TransactorEnabled::get() {
+                return Err(XcmError::AssetNotFound)
+            }
+            TransactorCalls::mutate(|calls| calls.push(("deposit", what.clone(), *who)));
+            Ok(())
+        }
+        fn withdraw_asset(
+            what: &MultiAsset,
+            who: &MultiLocation,
+            _: Option<&XcmContext>,
+        ) -> Result<xcm_executor::Assets, XcmError> {
+            if !TransactorEnabled::get() {
+                return Err(XcmError::AssetNotFound)
+            }
+            TransactorCalls::mutate(|calls| calls.push(("withdraw", what.clone(), *who)));
+            Ok(what.clone().into())
+        }
+    }
+
+    // Reads ownership from Uniques; mints and burns there only while `BackendMints` is set
+    pub struct TestNfts;
+    impl nonfungibles::Inspect<u64> for TestNfts {
+        type CollectionId = u32;
+        type ItemId = u32;
+        fn owner(collection: &u32, item: &u32) -> Option<u64> {
+            <Uniques as nonfungibles::Inspect<u64>>::owner(collection, item)
+        }
+        fn collection_owner(collection: &u32) -> Option<u64> {
+            <Uniques as nonfungibles::Inspect<u64>>::collection_owner(collection)
+        }
+    }
+    impl nonfungibles::Mutate<u64> for TestNfts {
+        fn mint_into(collection: &u32, item: &u32, who: &u64) -> DispatchResult {
+            if !BackendMints::get() {
+                return Err(sp_runtime::TokenError::Unsupported.into())
+            }
+            <Uniques as nonfungibles::Mutate<u64>>::mint_into(collection, item, who)
+        }
+        fn burn(collection: &u32, item: &u32, maybe_check_owner: Option<&u64>) -> DispatchResult {
+            if !BackendMints::get() {
+                return Err(sp_runtime::TokenError::Unsupported.into())
+            }
+            <Uniques as nonfungibles::Mutate<u64>>::burn(collection, item, maybe_check_owner)
+        }
+    }
+    impl nonfungibles::Transfer<u64> for TestNfts {
+        fn transfer(collection: &u32, item: &u32, destination: &u64) -> DispatchResult {
+            <Uniques as nonfungibles::Transfer<u64>>::transfer(collection, item, destination)
+        }
+    }
+
+    // Accepts exactly the proof `b"valid"`
+    pub struct AcceptValidProof;
+    impl VerifyLockProof<Test> for AcceptValidProof {
+        fn verify(_: u32, _: u32, _: u32, proof: &[u8]) -> bool {
+            proof == b"valid"
+        }
+    }
+
+    // Every parachain is trusted unless listed in `UntrustedSources`
+    pub struct TrustedSources;
+    impl Contains<u32> for TrustedSources {
+        fn contains(para_id: &u32) -> bool {
+            !UntrustedSources::get().contains(para_id)
+        }
+    }
+
+    // Beneficiaries on the destination are addressed by their 64-bit account index
+    pub struct AccountToLocation;
+    impl sp_runtime::traits::Convert<u64, MultiLocation> for AccountToLocation {
+        fn convert(who: u64) -> MultiLocation {
+            MultiLocation { parents: 0, interior: X1(AccountIndex64 { network: None, index: who }) }
+        }
+    }
+
+    // Clock of both bridges, at `UnixNow` seconds
+    pub struct MockTime;
+    impl frame_support::traits::UnixTime for MockTime {
+        fn now() -> core::time::Duration {
+            core::time::Duration::from_secs(UnixNow::get())
+        }
+    }
+
+    const SOVEREIGN_BASE: u64 = 1_000_000;
+
+    // Reverse of `AccountToLocation`, also resolving sibling chains to sovereign accounts
+    pub struct LocationToAccount;
+    impl xcm_executor::traits::Convert<MultiLocation, u64> for LocationToAccount {
+        fn convert(location: MultiLocation) -> Result<u64, MultiLocation> {
+            match location {
+                MultiLocation { parents: 0, interior: X1(AccountIndex64 { index, .. }) } => {
+                    Ok(index)
+                },
+                // Sibling sovereign accounts
+                MultiLocation { parents: 1, interior: X1(Parachain(id)) } => {
+                    Ok(SOVEREIGN_BASE + id as u64)
+                },
+                other => Err(other),
+            }
+        }
+    }
+
+    parameter_types! {
+        pub static UseTeleportBuilder: bool = false;
+        pub static NftAssetPrefix: InteriorMultiLocation = Here;
+        pub static RelayNetwork: NetworkId = NetworkId::Polkadot;
+    }
+
+    // Builds the default program unless a test switches to a teleport-style program
+    pub struct TestProgramBuilder;
+    impl BuildNftTransferProgram<Test> for TestProgramBuilder {
+        fn build(params: TransferParams<Test>) -> Result<Xcm<()>, BuildError> {
+            if !UseTeleportBuilder::get() {
+                return <xcm_handler::ReserveTransferProgram as BuildNftTransferProgram<Test>>::build(
+                    params,
+                );
+            }
+            if params.remote_call.is_some() {
+                return Err(BuildError::RemoteCallUnsupported);
+            }
+            Ok(Xcm(vec![
+                ReceiveTeleportedAsset(params.assets),
+                ClearOrigin,
+                BuyExecution { fees: params.fees, weight_limit: params.weight_limit },
+                DepositAsset { assets: AllCounted(1).into(), beneficiary: params.beneficiary },
+            ]))
+        }
+    }
+
+    // Test origin carrying a location: signed account `n` stands for sibling parachain `n`
+    pub struct EnsureSiblingAccount;
+    impl EnsureOrigin<RuntimeOrigin> for EnsureSiblingAccount {
+        type Success = MultiLocation;
+        fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+            o.into().and_then(|o| match o {
+                frame_system::RawOrigin::Signed(who) => {
+                    Ok(MultiLocation { parents: 1, interior: X1(Parachain(who as u32)) })
+                },
+                r => Err(RuntimeOrigin::from(r)),
+            })
         }
-        fn deliver(_ticket: Self::Ticket) -> Result<XcmHash, SendError> {
-            Ok([0u8; 32])
+        #[cfg(feature = "runtime-benchmarks")]
+        fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+            Ok(RuntimeOrigin::signed(2000))
         }
     }
 
@@ -87,9 +466,186 @@ mod tests {
         type CollectionId = u32;
         type ItemId = u32;
         type SendOrigin = frame_system::EnsureSigned<Self::AccountId>;
+        type ReceiveOrigin = EnsureSiblingAccount;
+        type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+        type LockStrategy = BridgeLockStrategy;
+        type TrustedSources = TrustedSources;
+        type LockProofVerifier = AcceptValidProof;
+        type RequireLockProof = RequireLockProof;
         type XcmSender = MockXcmSender;
-        type AssetTransactor = ();
+        type ProgramBuilder = TestProgramBuilder;
+        type NftAssetPrefix = NftAssetPrefix;
+        type SelfParaId = ConstU32<1000>;
+        type RelayNetwork = RelayNetwork;
+        type AccountIdToLocation = AccountToLocation;
+        type LocationToAccountId = LocationToAccount;
+        type AssetTransactor = RecordingTransactor;
+        type Nfts = TestNfts;
         type PalletId = NftBridgePalletId;
+        type AllowRemoteCalls = AllowRemoteCalls;
+        type MaxRemoteCallLen = ConstU32<256>;
+        type RemoteCallWeight = RemoteCallWeight;
+        type DefaultWeightLimit = DefaultWeightLimit;
+        type MaxBatchSize = ConstU32<8>;
+        type MaxOutboundQueue = ConstU32<4>;
+        type MaxPendingPerAccount = MaxPendingPerAccount;
+        type DeliveryDedupWindow = ConstU32<2>;
+        type MaxFailureRecords = ConstU32<2>;
+        type MaxAuthorities = ConstU32<3>;
+        type RejectionReportFee = RejectionReportFee;
+        type UnixTime = MockTime;
+        type MaxInboundAge = ConstU64<3_600>;
+        type StatsPeriod = ConstU64<10>;
+        type TransferTimeout = ConstU64<1000>;
+        type WarnAfter = ConstU64<500>;
+        type SwapAcceptTimeout = ConstU64<20>;
+        type MetadataSyncPeriod = ConstU64<10>;
+        type MaxSubscribersPerUpdate = ConstU32<2>;
+        type MaxExportBatchSize = ConstU32<10>;
+        type StagingTimeout = ConstU64<20>;
+        type StagingDepositPerByte = ConstU64<1>;
+        type MetadataRequestTimeout = ConstU64<10>;
+        type QueryTimeout = ConstU64<10>;
+        type OwnershipQueryDeposit = ConstU64<5>;
+        type MaxOwnershipQueries = ConstU32<2>;
+        type MaxHistoryHops = ConstU32<6>;
+        type KeepRecentHops = ConstU32<2>;
+        type HistoryDepositPerHop = HistoryDepositPerHop;
+        type ChunkSize = ConstU32<4>;
+        type MaxMetadataChunks = ConstU32<8>;
+        type ReassemblyTimeout = ConstU64<10>;
+        type MaxAttributes = MaxAttributes;
+        type MaxAttributeLen = MaxAttributeLen;
+        type PermissionlessCollections = Permissionless;
+        type AutoRegisterWrappedCollections = AutoRegister;
+        type WrappedCollectionRange = WrappedRange;
+        type MaxCollectionMetadataBytes = ConstU64<4096>;
+        type RetryBackoff = RetryBackoff;
+        type MaxRetries = ConstU32<3>;
+        type StaleThreshold = ConstU64<100>;
+        type MaxStaleScan = ConstU32<16>;
+        type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+        type RemoteExecutionFee = RemoteExecutionFee;
+        type MaxOutboundMessageSize = MaxOutboundMessageSize;
+        type MaxMetadataLen = MaxMetadataLen;
+        type MaxUriLen = MaxUriLen;
+        type MaxUrisPerItem = ConstU32<4>;
+        type Currency = Balances;
+        type RuntimeHoldReason = RuntimeHoldReason;
+        type BridgeFee = BridgeFee;
+        type PriorityFee = ConstU64<5>;
+        type PriorityInFlightCap = ConstU32<1>;
+        type FeePerMetadataByte = FeePerMetadataByte;
+        type FeeDestination = FeeAccount;
+        type FeeAssetConverter = RelayTokenToBalance;
+        type OnNftSent = RecordBridged<SentTag>;
+        type TransferFilter = GeofenceFilter;
+        type ItemIdGenerator = TestItemIds;
+        type OnNftReceived = RecordBridged<ReceivedTag>;
+        type WeightInfo = ();
+    }
+
+    // A second, independent lane: same limits, default hooks
+    impl Config<Instance2> for Test {
+        type RuntimeEvent = RuntimeEvent;
+        type CollectionId = u32;
+        type ItemId = u32;
+        type SendOrigin = frame_system::EnsureSigned<Self::AccountId>;
+        type ReceiveOrigin = EnsureSiblingAccount;
+        // Root or a listed bridge authority
+        type AdminOrigin = frame_support::traits::EitherOfDiverse<
+            frame_system::EnsureRoot<Self::AccountId>,
+            EnsureBridgeAuthority<Test, Instance2>,
+        >;
+        type LockStrategy = BridgeLockStrategy;
+        type TrustedSources = TrustedSources;
+        type LockProofVerifier = ();
+        type RequireLockProof = RequireLockProof;
+        type XcmSender = MockXcmSender;
+        type ProgramBuilder = xcm_handler::ReserveTransferProgram;
+        type NftAssetPrefix = NftAssetPrefix;
+        type SelfParaId = ConstU32<1000>;
+        type RelayNetwork = RelayNetwork;
+        type AccountIdToLocation = AccountToLocation;
+        type LocationToAccountId = LocationToAccount;
+        type AssetTransactor = RecordingTransactor;
+        type Nfts = TestNfts;
+        type PalletId = OtherBridgePalletId;
+        type AllowRemoteCalls = AllowRemoteCalls;
+        type MaxRemoteCallLen = ConstU32<256>;
+        type RemoteCallWeight = RemoteCallWeight;
+        type DefaultWeightLimit = DefaultWeightLimit;
+        type MaxBatchSize = ConstU32<8>;
+        type MaxOutboundQueue = ConstU32<4>;
+        type MaxPendingPerAccount = MaxPendingPerAccount;
+        type DeliveryDedupWindow = ConstU32<2>;
+        type MaxFailureRecords = ConstU32<2>;
+        type MaxAuthorities = ConstU32<3>;
+        type RejectionReportFee = RejectionReportFee;
+        type UnixTime = MockTime;
+        type MaxInboundAge = ConstU64<0>;
+        type StatsPeriod = ConstU64<0>;
+        type TransferTimeout = ConstU64<1000>;
+        type WarnAfter = ConstU64<500>;
+        type SwapAcceptTimeout = ConstU64<20>;
+        type MetadataSyncPeriod = ConstU64<10>;
+        type MaxSubscribersPerUpdate = ConstU32<2>;
+        type MaxExportBatchSize = ConstU32<10>;
+        type StagingTimeout = ConstU64<20>;
+        type StagingDepositPerByte = ConstU64<1>;
+        type MetadataRequestTimeout = ConstU64<10>;
+        type QueryTimeout = ConstU64<10>;
+        type OwnershipQueryDeposit = ConstU64<5>;
+        type MaxOwnershipQueries = ConstU32<2>;
+        type MaxHistoryHops = ConstU32<6>;
+        type KeepRecentHops = ConstU32<2>;
+        type HistoryDepositPerHop = HistoryDepositPerHop;
+        type ChunkSize = ConstU32<4>;
+        type MaxMetadataChunks = ConstU32<8>;
+        type ReassemblyTimeout = ConstU64<10>;
+        type MaxAttributes = MaxAttributes;
+        type MaxAttributeLen = MaxAttributeLen;
+        type PermissionlessCollections = Permissionless;
+        type AutoRegisterWrappedCollections = AutoRegister;
+        type WrappedCollectionRange = WrappedRange;
+        type MaxCollectionMetadataBytes = ConstU64<4096>;
+        type RetryBackoff = RetryBackoff;
+        type MaxRetries = ConstU32<3>;
+        type StaleThreshold = ConstU64<100>;
+        type MaxStaleScan = ConstU32<16>;
+        type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+        type RemoteExecutionFee = RemoteExecutionFee;
+        type MaxOutboundMessageSize = MaxOutboundMessageSize;
+        type MaxMetadataLen = MaxMetadataLen;
+        type MaxUriLen = MaxUriLen;
+        type MaxUrisPerItem = ConstU32<4>;
+        type Currency = Balances;
+        type RuntimeHoldReason = RuntimeHoldReason;
+        type BridgeFee = BridgeFee;
+        type PriorityFee = ConstU64<5>;
+        type PriorityInFlightCap = ConstU32<1>;
+        type FeePerMetadataByte = FeePerMetadataByte;
+        type FeeDestination = FeeAccount;
+        type FeeAssetConverter = RelayTokenToBalance;
+        type OnNftSent = ();
+        type TransferFilter = ();
+        type ItemIdGenerator = ();
+        type OnNftReceived = ();
+        type WeightInfo = ();
+    }
+
+    impl pallet_marketplace::Config for Test {
+        type Bridge = NftBridge;
+    }
+
+    pub type Extrinsic = sp_runtime::testing::TestXt<RuntimeCall, ()>;
+
+    impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+    where
+        RuntimeCall: From<C>,
+    {
+        type OverarchingCall = RuntimeCall;
+        type Extrinsic = Extrinsic;
     }
 
     pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -119,11 +675,20 @@ mod tests {
                 item_id,
                 dest_para_id,
                 metadata.clone(),
-                None // no metadata URI
+                None, // no metadata URI
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
             ));
 
-            // Verify that the NFT is no longer owned by the sender
-            assert!(NftBridge::owner(collection_id, item_id).is_none());
+            // Verify that the NFT is held in escrow
+            assert_eq!(
+                NftBridge::owner(collection_id, item_id),
+                Some(NftBridge::escrow_account_for(2000))
+            );
+            assert!(!NftBridge::is_owner(collection_id, item_id, &sender));
 
             // Verify that the NFT is in pending transfer state
             assert!(NftBridge::pending_transfer(collection_id, item_id).is_some());
@@ -133,6 +698,9 @@ mod tests {
                 collection_id,
                 item_id,
                 dest_para_id,
+                fee: 0,
+                delivery_fee: 0,
+                canonical_id: NftBridge::canonical_id(collection_id, item_id),
             }));
         });
     }
@@ -146,15 +714,23 @@ mod tests {
             let recipient = 2;
             let metadata = b"test_metadata".to_vec();
 
-            // Call the receive_nft function (in practice, this would be called by an authorized source)
+            // Call the receive_nft function as the sibling parachain the NFT comes from
             assert_ok!(NftBridge::receive_nft(
-                RuntimeOrigin::root(), // For testing, using root as authorized origin
+                RuntimeOrigin::signed(from_para_id as u64),
                 collection_id,
                 item_id,
                 from_para_id,
                 recipient,
                 metadata,
-                None // no metadata URI
+                None, // no metadata URI
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
             ));
 
             // Verify that the NFT is now owned by the recipient
@@ -165,6 +741,7 @@ mod tests {
                 collection_id,
                 item_id,
                 from_para_id,
+                canonical_id: NftBridge::canonical_id(collection_id, item_id),
             }));
         });
     }
@@ -190,6 +767,11 @@ mod tests {
                     item_id,
                     dest_para_id,
                     metadata,
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
                     None
                 ),
                 Error::<Test>::NotOwner
@@ -217,7 +799,12 @@ mod tests {
                 item_id,
                 dest_para_id,
                 metadata.clone(),
-                metadata_uri.clone()
+                metadata_uri.clone(),
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
             ));
 
             // Verify that metadata is stored
@@ -240,10 +827,14 @@ mod tests {
             NFTOwners::<Test>::insert(collection_id, item_id, sender);
 
             // Lock the NFT
-            assert_ok!(NftBridge::lock_nft(collection_id, item_id, &sender));
+            assert_ok!(NftBridge::lock_nft(collection_id, item_id, &sender, 2000));
 
-            // Verify that the NFT is no longer owned by the sender
-            assert!(NftBridge::owner(collection_id, item_id).is_none());
+            // Verify that the NFT is held in escrow
+            assert_eq!(
+                NftBridge::owner(collection_id, item_id),
+                Some(NftBridge::escrow_account_for(2000))
+            );
+            assert!(!NftBridge::is_owner(collection_id, item_id, &sender));
 
             // Unlock the NFT
             assert_ok!(NftBridge::unlock_nft(collection_id, item_id, &sender));
@@ -267,4 +858,7241 @@ mod tests {
             );
         });
     }
-}
\ No newline at end of file
+    #[test]
+    fn force_receive_refuses_to_overwrite_without_flag() {
+        new_test_ext().execute_with(|| {
+            let collection_id = 1;
+            let item_id = 1;
+            let from_para_id = 2000;
+            let existing_owner = 3;
+            let recipient = 2;
+
+            NFTOwners::<Test>::insert(collection_id, item_id, existing_owner);
+
+            // Only the admin origin may force a delivery
+            assert_noop!(
+                NftBridge::force_receive(
+                    RuntimeOrigin::signed(recipient),
+                    collection_id,
+                    item_id,
+                    from_para_id,
+                    recipient,
+                    b"test_metadata".to_vec(),
+                    None,
+                    true
+                ),
+                sp_runtime::DispatchError::BadOrigin
+            );
+
+            // Without the overwrite flag the existing owner is kept
+            assert_noop!(
+                NftBridge::force_receive(
+                    RuntimeOrigin::root(),
+                    collection_id,
+                    item_id,
+                    from_para_id,
+                    recipient,
+                    b"test_metadata".to_vec(),
+                    None,
+                    false
+                ),
+                Error::<Test>::NFTAlreadyExists
+            );
+            assert_eq!(NftBridge::owner(collection_id, item_id), Some(existing_owner));
+        });
+    }
+
+    #[test]
+    fn force_receive_overwrites_with_flag() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let collection_id = 1;
+            let item_id = 1;
+            let from_para_id = 2000;
+            let existing_owner = 3;
+            let recipient = 2;
+            let metadata = b"test_metadata".to_vec();
+
+            NFTOwners::<Test>::insert(collection_id, item_id, existing_owner);
+
+            assert_ok!(NftBridge::force_receive(
+                RuntimeOrigin::root(),
+                collection_id,
+                item_id,
+                from_para_id,
+                recipient,
+                metadata.clone(),
+                None,
+                true
+            ));
+
+            assert_eq!(NftBridge::owner(collection_id, item_id), Some(recipient));
+            assert_eq!(NftBridge::nft_metadata(collection_id, item_id), Some(metadata));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::ForceReceived {
+                collection_id,
+                item_id,
+                from_para_id,
+                owner: recipient,
+                overwritten: true,
+            }));
+        });
+    }
+    #[test]
+    fn reject_inbound_bounces_item_to_source_chain() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let collection_id = 1;
+            let item_id = 2;
+            let from_para_id = 2000;
+            let holder = SOVEREIGN_BASE + 2000;
+
+            // A receive past the collection's supply parks the item in the holding area
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, Some(2000), 3));
+            assert_ok!(NftBridge::set_collection_max_supply(RuntimeOrigin::signed(3), 1, Some(1)));
+            assert_ok!(receive(Delivery::default()));
+            assert_ok!(receive(Delivery { item_id, ..Default::default() }));
+            assert_eq!(NftBridge::owner(collection_id, item_id), Some(holder));
+            let sent_before = sent_xcm().len();
+
+            // The item came from 2000, not 2001, and item 1 was credited
+            assert_noop!(
+                NftBridge::reject_inbound(RuntimeOrigin::root(), collection_id, item_id, 2001),
+                Error::<Test>::NotOrphaned
+            );
+            assert_noop!(
+                NftBridge::reject_inbound(RuntimeOrigin::root(), collection_id, 1, from_para_id),
+                Error::<Test>::NotOrphaned
+            );
+
+            assert_ok!(NftBridge::reject_inbound(
+                RuntimeOrigin::root(),
+                collection_id,
+                item_id,
+                from_para_id
+            ));
+
+            // The item is gone from the holding area along with its metadata
+            assert!(NftBridge::orphaned_inbound(collection_id, item_id).is_none());
+            assert_eq!(NftBridge::owner(collection_id, item_id), None);
+            assert!(NftBridge::nft_metadata(collection_id, item_id).is_none());
+            assert_eq!(NftBridge::wrapped_supply(collection_id), 1);
+            assert_ok!(NftBridge::do_try_state());
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::InboundRejected {
+                collection_id,
+                item_id,
+                from_para_id,
+                reason: RejectionReason::AdminRejected,
+            }));
+
+            // A single bounce message went back to the source chain
+            let sent = sent_xcm();
+            assert_eq!(sent.len(), sent_before + 1);
+            let (dest, message) = sent.last().unwrap();
+            assert_eq!(*dest, MultiLocation { parents: 1, interior: X1(Parachain(from_para_id)) });
+            match message.0.last().unwrap() {
+                Transact { origin_kind, call, .. } => {
+                    assert_eq!(*origin_kind, OriginKind::SovereignAccount);
+                    let pallet_index =
+                        <Test as frame_system::Config>::PalletInfo::index::<NftBridge>().unwrap() as u8;
+                    let expected = (
+                        pallet_index,
+                        crate::Call::<Test>::unlock_bounced {
+                            collection_id,
+                            item_id,
+                            owner: holder,
+                            reason: RejectionReason::AdminRejected,
+                        },
+                    )
+                        .encode();
+                    assert_eq!(call.clone().into_encoded(), expected);
+                },
+                other => panic!("expected Transact, got {:?}", other),
+            }
+        });
+    }
+
+    #[test]
+    fn unlock_bounced_returns_item_to_owner() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let collection_id = 1;
+            let item_id = 1;
+
+            NFTOwners::<Test>::insert(collection_id, item_id, sender);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                collection_id,
+                item_id,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+
+            // Only the destination chain may bounce the item
+            assert_noop!(
+                NftBridge::unlock_bounced(
+                    RuntimeOrigin::signed(2001),
+                    collection_id,
+                    item_id,
+                    sender,
+                    RejectionReason::AdminRejected
+                ),
+                Error::<Test>::OriginMismatch
+            );
+
+            // The destination can't redirect the item to someone else
+            assert_ok!(NftBridge::unlock_bounced(
+                RuntimeOrigin::signed(2000),
+                collection_id,
+                item_id,
+                9,
+                RejectionReason::AdminRejected
+            ));
+
+            assert_eq!(NftBridge::owner(collection_id, item_id), Some(sender));
+            assert!(NftBridge::pending_transfer(collection_id, item_id).is_none());
+        });
+    }
+    #[test]
+    fn receive_nft_accepts_matching_origin() {
+        new_test_ext().execute_with(|| {
+            let from_para_id = 2000;
+            let recipient = 2;
+
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(from_para_id as u64),
+                1,
+                1,
+                from_para_id,
+                recipient,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+
+            assert_eq!(NftBridge::owner(1, 1), Some(recipient));
+        });
+    }
+
+    #[test]
+    fn receive_nft_rejects_mismatching_origin() {
+        new_test_ext().execute_with(|| {
+            let recipient = 2;
+
+            // Para 2001 claims the NFT came from para 2000
+            assert_noop!(
+                NftBridge::receive_nft(
+                    RuntimeOrigin::signed(2001),
+                    1,
+                    1,
+                    2000,
+                    recipient,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    vec![],
+                    None,
+                    None,
+                    None,
+                    None
+                ),
+                Error::<Test>::OriginMismatch
+            );
+
+            // Origins that carry no location are rejected outright
+            assert_noop!(
+                NftBridge::receive_nft(
+                    RuntimeOrigin::root(),
+                    1,
+                    1,
+                    2000,
+                    recipient,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    vec![],
+                    None,
+                    None,
+                    None,
+                    None
+                ),
+                sp_runtime::DispatchError::BadOrigin
+            );
+        });
+    }
+    #[test]
+    fn each_origin_is_enforced_separately() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let collection_id = 1;
+            let item_id = 1;
+            NFTOwners::<Test>::insert(collection_id, item_id, sender);
+
+            // SendOrigin: root is not a signed sender
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::root(),
+                    collection_id,
+                    item_id,
+                    2000,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None
+                ),
+                sp_runtime::DispatchError::BadOrigin
+            );
+
+            // ReceiveOrigin: an unsigned origin carries no location
+            assert_noop!(
+                NftBridge::receive_nft(
+                    RuntimeOrigin::none(),
+                    collection_id,
+                    2,
+                    2000,
+                    sender,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    vec![],
+                    None,
+                    None,
+                    None,
+                    None
+                ),
+                sp_runtime::DispatchError::BadOrigin
+            );
+
+            // AdminOrigin: a regular signed account cannot force operations
+            assert_noop!(
+                NftBridge::force_receive(
+                    RuntimeOrigin::signed(sender),
+                    collection_id,
+                    2,
+                    2000,
+                    sender,
+                    b"test_metadata".to_vec(),
+                    None,
+                    false
+                ),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_noop!(
+                NftBridge::reject_inbound(RuntimeOrigin::signed(sender), collection_id, 2, 2000),
+                sp_runtime::DispatchError::BadOrigin
+            );
+        });
+    }
+    #[test]
+    fn send_nft_fails_if_already_pending() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let collection_id = 1;
+            let item_id = 1;
+
+            NFTOwners::<Test>::insert(collection_id, item_id, sender);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                collection_id,
+                item_id,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+
+            // The second send reports the pending transfer rather than a missing NFT
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(sender),
+                    collection_id,
+                    item_id,
+                    2001,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None
+                ),
+                Error::<Test>::TransferAlreadyPending
+            );
+
+            // Even ahead of the bridge being paused
+            BridgePaused::<Test>::put(true);
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(sender),
+                    collection_id,
+                    item_id,
+                    2001,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None
+                ),
+                Error::<Test>::TransferAlreadyPending
+            );
+        });
+    }
+    #[test]
+    fn validate_send_quotes_without_mutating_state() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let collection_id = 1;
+            let item_id = 1;
+            let dest_para_id = 2000;
+            let metadata = b"test_metadata".to_vec();
+
+            NFTOwners::<Test>::insert(collection_id, item_id, sender);
+
+            let quote = NftBridge::validate_send(
+                sender,
+                collection_id,
+                item_id,
+                dest_para_id,
+                metadata.len() as u32,
+                0,
+            )
+            .unwrap();
+
+            // Nothing was locked, stored or sent
+            assert_eq!(NftBridge::owner(collection_id, item_id), Some(sender));
+            assert!(NftBridge::pending_transfer(collection_id, item_id).is_none());
+            assert!(NftBridge::nft_metadata(collection_id, item_id).is_none());
+            assert!(sent_xcm().is_empty());
+
+            // The quoted remote fee is what the real send buys on the destination
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                collection_id,
+                item_id,
+                dest_para_id,
+                metadata,
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            let (_, message) = sent_xcm().pop().unwrap();
+            assert!(message.0.iter().any(|instruction| matches!(
+                instruction,
+                BuyExecution { fees, .. } if *fees == quote.remote_execution_fee
+            )));
+            assert_eq!(quote.delivery_fees, MultiAssets::new());
+
+            // A pending item can no longer be quoted
+            assert_eq!(
+                NftBridge::validate_send(sender, collection_id, item_id, dest_para_id, 0, 0),
+                Err(Error::<Test>::TransferAlreadyPending.into())
+            );
+        });
+    }
+    #[test]
+    fn estimate_send_fee_reports_each_component() {
+        new_test_ext().execute_with(|| {
+            let collection_id = 1;
+            let item_id = 1;
+
+            assert_eq!(
+                NftBridge::estimate_send_fee(collection_id, item_id, 2000, 13),
+                Err(Error::<Test>::NFTNotFound.into())
+            );
+
+            NFTOwners::<Test>::insert(collection_id, item_id, 1);
+            let breakdown = NftBridge::estimate_send_fee(collection_id, item_id, 2000, 13).unwrap();
+
+            assert_eq!(breakdown.local_weight, <() as WeightInfo>::send_nft(13, 1));
+            assert_eq!(breakdown.delivery_fees, MultiAssets::new());
+            assert_eq!(breakdown.remote_execution_fee, RemoteExecutionFee::get());
+        });
+    }
+    #[test]
+    fn bridge_hooks_are_invoked() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let recipient = 2;
+
+            NFTOwners::<Test>::insert(1, 1, sender);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2001),
+                1,
+                2,
+                2001,
+                recipient,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+
+            // Failed transfers never reach the hooks
+            assert!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                3,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            )
+            .is_err());
+
+            assert_eq!(
+                bridged_calls(),
+                vec![("sent", 1, 1, 2000, sender), ("received", 1, 2, 2001, recipient)]
+            );
+        });
+    }
+    #[test]
+    fn other_pallets_can_bridge_through_the_trait() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let seller = 1;
+            let collection_id = 1;
+            let item_id = 1;
+
+            NFTOwners::<Test>::insert(collection_id, item_id, seller);
+            assert_ok!(Marketplace::settle_and_bridge(
+                RuntimeOrigin::signed(seller),
+                collection_id,
+                item_id,
+                2000
+            ));
+
+            let transfer = NftBridge::pending_transfer(collection_id, item_id).unwrap();
+            assert_eq!(pallet_marketplace::LastSettlement::<Test>::get(), Some(transfer.id));
+            assert_eq!(transfer.sender, seller);
+            assert_eq!(sent_xcm().len(), 1);
+
+            // Cancelling follows the same rules as the extrinsic
+            assert_noop!(
+                <NftBridge as NftBridging<_, _, _>>::cancel(seller, collection_id, item_id),
+                Error::<Test>::TransferInFlight
+            );
+            reject(collection_id, item_id);
+            assert_ok!(<NftBridge as NftBridging<_, _, _>>::cancel(seller, collection_id, item_id));
+            assert_eq!(NftBridge::owner(collection_id, item_id), Some(seller));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::TransferCancelled {
+                collection_id,
+                item_id,
+                transfer_id: transfer.id,
+                owner: seller,
+            }));
+        });
+    }
+
+    #[test]
+    fn cancel_transfer_only_by_sender() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            NFTOwners::<Test>::insert(1, 1, sender);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            reject(1, 1);
+
+            assert_noop!(
+                NftBridge::cancel_transfer(RuntimeOrigin::signed(2), 1, 1),
+                Error::<Test>::NotOwner
+            );
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(sender), 1, 1));
+            assert!(NftBridge::pending_transfer(1, 1).is_none());
+        });
+    }
+    /// The program executed on the destination after the reserve withdraw
+    fn remote_program(message: &Xcm<()>) -> Xcm<()> {
+        message
+            .0
+            .iter()
+            .find_map(|instruction| match instruction {
+                InitiateReserveWithdraw { xcm, .. } => Some(xcm.clone()),
+                _ => None,
+            })
+            .expect("transfer programs always forward to the reserve")
+    }
+
+    #[test]
+    fn remote_call_is_appended_only_when_supplied() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let remote_call: BoundedVec<u8, ConstU32<256>> = vec![7u8, 0, 42].try_into().unwrap();
+
+            NFTOwners::<Test>::insert(1, 1, sender);
+            NFTOwners::<Test>::insert(1, 2, sender);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                2,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                Some(remote_call.clone()),
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+
+            let sent = sent_xcm();
+            let plain = remote_program(&sent[0].1);
+            assert_eq!(plain.0.len(), 1);
+            assert!(matches!(plain.0[0], DepositAsset { .. }));
+
+            let with_call = remote_program(&sent[1].1);
+            assert_eq!(with_call.0.len(), 2);
+            assert!(matches!(with_call.0[0], DepositAsset { .. }));
+            match &with_call.0[1] {
+                Transact { origin_kind, require_weight_at_most, call } => {
+                    assert_eq!(*origin_kind, OriginKind::SovereignAccount);
+                    assert_eq!(*require_weight_at_most, RemoteCallWeight::get());
+                    assert_eq!(call.clone().into_encoded(), remote_call.into_inner());
+                },
+                other => panic!("expected Transact, got {:?}", other),
+            }
+        });
+    }
+
+    #[test]
+    fn remote_call_rejected_when_disabled() {
+        new_test_ext().execute_with(|| {
+            AllowRemoteCalls::set(false);
+            NFTOwners::<Test>::insert(1, 1, 1);
+
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    1,
+                    2000,
+                    b"test_metadata".to_vec(),
+                    None,
+                    Some(vec![7u8].try_into().unwrap()),
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None
+                ),
+                Error::<Test>::RemoteCallsDisabled
+            );
+        });
+    }
+    #[test]
+    fn program_builder_controls_the_message() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            NFTOwners::<Test>::insert(1, 1, sender);
+            NFTOwners::<Test>::insert(1, 2, sender);
+
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            UseTeleportBuilder::set(true);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                2,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+
+            let sent = sent_xcm();
+            assert!(matches!(sent[0].1 .0[0], ReserveAssetDeposited(_)));
+            assert!(matches!(sent[1].1 .0[0], ReceiveTeleportedAsset(_)));
+            assert_ne!(sent[0].1, sent[1].1);
+
+            // Both builders were handed the same beneficiary
+            let beneficiary = AccountToLocation::convert(sender);
+            assert!(sent[1].1 .0.iter().any(|i| matches!(
+                i,
+                DepositAsset { beneficiary: b, .. } if *b == beneficiary
+            )));
+
+            // Builder failures surface as a pallet error and roll back the lock
+            NFTOwners::<Test>::insert(1, 3, sender);
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(sender),
+                    1,
+                    3,
+                    2000,
+                    b"test_metadata".to_vec(),
+                    None,
+                    Some(vec![7u8].try_into().unwrap()),
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None
+                ),
+                Error::<Test>::ProgramBuildFailed
+            );
+        });
+    }
+    #[test]
+    fn messages_downgrade_to_destination_xcm_version() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            NFTOwners::<Test>::insert(1, 1, sender);
+
+            assert_noop!(
+                NftBridge::set_destination_xcm_version(RuntimeOrigin::signed(sender), 2000, Some(2)),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(NftBridge::set_destination_xcm_version(RuntimeOrigin::root(), 2000, Some(2)));
+
+            let message = NftBridge::build_transfer_message(
+                &sender,
+                1,
+                1,
+                &MultiLocation { parents: 1, interior: X1(Parachain(2000)) },
+                None,
+                ExecutionLimit::Default
+            )
+            .unwrap();
+
+            // Para 2000 gets v2, everyone else the latest version
+            assert!(matches!(
+                NftBridge::versioned_for_destination(2000, message.clone()),
+                Ok(xcm::VersionedXcm::V2(_))
+            ));
+            assert!(matches!(
+                NftBridge::versioned_for_destination(2001, message.clone()),
+                Ok(xcm::VersionedXcm::V3(_))
+            ));
+
+            // The downgraded transfer still goes out, handed to the transport as v2
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            let (_, sent) = sent_versioned_xcm().pop().unwrap();
+            assert!(matches!(sent, xcm::VersionedXcm::V2(_)));
+
+            // Versions the message can't be expressed in are rejected before anything is locked
+            NFTOwners::<Test>::insert(1, 2, sender);
+            assert_ok!(NftBridge::set_destination_xcm_version(RuntimeOrigin::root(), 2001, Some(1)));
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(sender),
+                    1,
+                    2,
+                    2001,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None
+                ),
+                Error::<Test>::UnsupportedXcmVersion
+            );
+        });
+    }
+    /// The weight limit bought by a transfer program
+    fn bought_weight_limit(message: &Xcm<()>) -> WeightLimit {
+        message
+            .0
+            .iter()
+            .find_map(|instruction| match instruction {
+                BuyExecution { weight_limit, .. } => Some(weight_limit.clone()),
+                _ => None,
+            })
+            .expect("transfer programs always buy execution")
+    }
+
+    #[test]
+    fn execution_limit_is_threaded_into_buy_execution() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let custom = Weight::from_parts(5_000_000, 1024);
+            let configured = Weight::from_parts(7_000_000, 2048);
+            for item_id in 1..=4 {
+                NFTOwners::<Test>::insert(1, item_id, sender);
+            }
+            let send = |item_id, dest_para_id, limit| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(sender),
+                    1,
+                    item_id,
+                    dest_para_id,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    limit,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+
+            // Unlimited needs a trusted destination
+            assert_noop!(send(1, 2000, ExecutionLimit::Unlimited), Error::<Test>::UnlimitedWeightNotAllowed);
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2001,
+                Some(DestinationConfig {
+                    trusted: true,
+                    weight_limit: Some(configured),
+                    max_in_flight: None,
+                    max_message_size: None,
+                    ..Default::default()
+                })
+            ));
+
+            assert_ok!(send(1, 2000, ExecutionLimit::Default));
+            assert_ok!(send(2, 2001, ExecutionLimit::Default));
+            assert_ok!(send(3, 2000, ExecutionLimit::Limited(custom)));
+            assert_ok!(send(4, 2001, ExecutionLimit::Unlimited));
+
+            let limits: Vec<_> = sent_xcm().iter().map(|(_, m)| bought_weight_limit(m)).collect();
+            assert_eq!(
+                limits,
+                vec![Limited(DefaultWeightLimit::get()), Limited(configured), Limited(custom), Unlimited]
+            );
+        });
+    }
+    #[test]
+    fn asset_prefix_is_configurable() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            NFTOwners::<Test>::insert(1, 1, sender);
+            NFTOwners::<Test>::insert(1, 2, sender);
+
+            // Without a prefix the pallet instance anchors the asset
+            let pallet_index =
+                <Test as frame_system::Config>::PalletInfo::index::<NftBridge>().unwrap() as u8;
+            assert_eq!(NftBridge::nft_asset_prefix(), Ok(X1(PalletInstance(pallet_index))));
+
+            let prefix = X2(PalletInstance(50), GeneralKey { length: 3, data: [7u8; 32] });
+            NftAssetPrefix::set(prefix);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+
+            let (_, message) = sent_xcm().pop().unwrap();
+            let asset = match &message.0[0] {
+                ReserveAssetDeposited(assets) => assets.inner()[0].clone(),
+                other => panic!("expected ReserveAssetDeposited, got {:?}", other),
+            };
+            match &asset.id {
+                AssetId::Concrete(location) => {
+                    assert_eq!(location.parents, 0);
+                    assert_eq!(location.interior.split_last().0, prefix);
+                },
+                other => panic!("expected a concrete asset id, got {:?}", other),
+            }
+
+            // The matcher only accepts assets under the same prefix
+            assert!(NftBridge::match_nft_asset(&asset).is_some());
+            NftAssetPrefix::set(Here);
+            assert!(NftBridge::match_nft_asset(&asset).is_none());
+        });
+    }
+    #[test]
+    fn pending_by_destination_follows_every_settlement_path() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            for item_id in 1..=3 {
+                NFTOwners::<Test>::insert(1, item_id, sender);
+            }
+            let send = |item_id, dest_para_id| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(sender),
+                    1,
+                    item_id,
+                    dest_para_id,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            assert_ok!(send(1, 2000));
+            assert_ok!(send(2, 2000));
+            assert_ok!(send(3, 2004));
+
+            assert_eq!(NftBridge::pending_to(2000, 10).len(), 2);
+            assert_eq!(NftBridge::pending_to(2000, 1).len(), 1);
+            assert_eq!(NftBridge::pending_to(2004, 10), vec![(1, 3)]);
+            assert_ok!(NftBridge::do_try_state());
+
+            // Cancel
+            reject(1, 1);
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(sender), 1, 1));
+            assert_eq!(NftBridge::pending_to(2000, 10), vec![(1, 2)]);
+
+            // Bounce from the destination
+            assert_ok!(NftBridge::unlock_bounced(
+                RuntimeOrigin::signed(2000),
+                1,
+                2,
+                sender,
+                RejectionReason::AdminRejected
+            ));
+            assert!(NftBridge::pending_to(2000, 10).is_empty());
+
+            // Coming back in over the bridge settles it as well
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2004),
+                1,
+                3,
+                2004,
+                sender,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            assert!(NftBridge::pending_to(2004, 10).is_empty());
+            assert_ok!(NftBridge::do_try_state());
+        });
+    }
+
+    #[test]
+    fn try_state_detects_index_drift() {
+        new_test_ext().execute_with(|| {
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+
+            PendingByDestination::<Test>::remove(2000, (1, 1));
+            assert!(NftBridge::do_try_state().is_err());
+        });
+    }
+    #[test]
+    fn transfers_resolve_by_message_hash() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            NFTOwners::<Test>::insert(1, 1, sender);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(sender),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+
+            let hash = last_message_hash();
+            let pending = NftBridge::pending_transfer(1, 1).unwrap();
+
+            let record = NftBridge::transfer_by_message_hash(hash).unwrap();
+            assert_eq!(record.id, pending.id);
+            assert_eq!((record.collection_id, record.item_id), (1, 1));
+            assert_eq!(record.pending, Some(pending));
+
+            // Once settled the hash still resolves, without a pending record
+            reject(1, 1);
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(sender), 1, 1));
+            assert_eq!(NftBridge::transfer_by_message_hash(hash).unwrap().pending, None);
+
+            assert!(NftBridge::transfer_by_message_hash([1u8; 32]).is_none());
+        });
+    }
+
+    #[test]
+    fn settled_transfer_records_can_be_pruned() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(send(1, 1, 1));
+            let first = NftBridge::pending_transfer(1, 1).unwrap().id;
+            let first_hash = last_message_hash();
+            reject(1, 1);
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 1));
+
+            // Sending the same item again yields a new transfer under a new hash
+            assert_ok!(send(1, 1, 1));
+            let second = NftBridge::pending_transfer(1, 1).unwrap().id;
+            let second_hash = last_message_hash();
+            assert_ne!(first_hash, second_hash);
+            assert_eq!(TransferMessages::<Test>::get(first).into_inner(), vec![first_hash]);
+
+            let ids: BoundedVec<_, _> = vec![first, second].try_into().unwrap();
+            assert_noop!(
+                NftBridge::prune_transfer_records(RuntimeOrigin::signed(1), ids.clone()),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(NftBridge::prune_transfer_records(RuntimeOrigin::root(), ids));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::TransferRecordsPruned {
+                count: 1,
+            }));
+
+            // The settled transfer is gone, the pending one is untouched
+            assert!(NftBridge::transfer_by_message_hash(first_hash).is_none());
+            assert!(TransferKeys::<Test>::get(first).is_none());
+            assert!(!TransferMessages::<Test>::contains_key(first));
+            assert_eq!(NftBridge::transfer_by_message_hash(second_hash).unwrap().id, second);
+        });
+    }
+    fn batch(items: &[(u32, u32)]) -> BoundedVec<BatchItem<u32, u32>, ConstU32<8>> {
+        items
+            .iter()
+            .map(|&(collection_id, item_id)| BatchItem {
+                collection_id,
+                item_id,
+                metadata: b"test_metadata".to_vec(),
+                metadata_uri: None,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn best_effort_batch_partitions_items() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let sender = 1;
+            NFTOwners::<Test>::insert(1, 1, sender);
+            NFTOwners::<Test>::insert(1, 2, 2);
+            NFTOwners::<Test>::insert(2, 1, sender);
+
+            // Atomic mode refuses the whole batch
+            assert_noop!(
+                NftBridge::send_nfts(
+                    RuntimeOrigin::signed(sender),
+                    batch(&[(1, 1), (1, 2), (2, 1)]),
+                    2000,
+                    BatchMode::Atomic,
+                    None
+                ),
+                Error::<Test>::NotOwner
+            );
+
+            assert_ok!(NftBridge::send_nfts(
+                RuntimeOrigin::signed(sender),
+                batch(&[(1, 1), (1, 2), (2, 1)]),
+                2000,
+                BatchMode::BestEffort,
+                None
+            ));
+
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::BatchSendResult {
+                dest_para_id: 2000,
+                sent: vec![(1, 1), (2, 1)],
+                skipped: vec![((1, 2), Error::<Test>::NotOwner.into())],
+            }));
+            assert!(NftBridge::pending_transfer(1, 1).is_some());
+            assert!(NftBridge::pending_transfer(2, 1).is_some());
+            assert_eq!(NftBridge::owner(1, 2), Some(2));
+            assert!(NftBridge::nft_metadata(1, 2).is_none());
+            assert_eq!(sent_xcm().len(), 2);
+
+            // A batch where nothing can be sent fails
+            assert_noop!(
+                NftBridge::send_nfts(
+                    RuntimeOrigin::signed(sender),
+                    batch(&[(1, 2)]),
+                    2000,
+                    BatchMode::BestEffort,
+                    None
+                ),
+                Error::<Test>::NothingSent
+            );
+        });
+    }
+    #[test]
+    fn offchain_worker_reports_stale_transfers_once() {
+        use frame_support::traits::Hooks;
+        use sp_core::offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt};
+
+        let mut ext = new_test_ext();
+        let (offchain, _) = testing::TestOffchainExt::new();
+        let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+        ext.register_extension(OffchainDbExt::new(offchain.clone()));
+        ext.register_extension(OffchainWorkerExt::new(offchain));
+        ext.register_extension(TransactionPoolExt::new(pool));
+
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            let transfer_id = NftBridge::pending_transfer(1, 1).unwrap().id;
+
+            // Too young to report
+            NftBridge::offchain_worker(50);
+            assert!(pool_state.read().transactions.is_empty());
+
+            System::set_block_number(101);
+            NftBridge::offchain_worker(101);
+            let tx = pool_state.write().transactions.pop().unwrap();
+            let tx = Extrinsic::decode(&mut &*tx).unwrap();
+            assert_eq!(tx.signature, None);
+            let expected = crate::Call::report_stale { collection_id: 1, item_id: 1, transfer_id };
+            assert_eq!(tx.call, RuntimeCall::NftBridge(expected.clone()));
+
+            // The report passes pool validation and lands on-chain
+            assert_ok!(<NftBridge as ValidateUnsigned>::validate_unsigned(
+                TransactionSource::Local,
+                &expected
+            ));
+            assert_ok!(NftBridge::report_stale(RuntimeOrigin::none(), 1, 1, transfer_id));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::TransferStale {
+                collection_id: 1,
+                item_id: 1,
+                transfer_id,
+                age: 100,
+            }));
+
+            // Reported transfers are neither resubmitted nor accepted again
+            System::set_block_number(102);
+            NftBridge::offchain_worker(102);
+            assert!(pool_state.read().transactions.is_empty());
+            assert!(<NftBridge as ValidateUnsigned>::validate_unsigned(
+                TransactionSource::Local,
+                &expected
+            )
+            .is_err());
+        });
+    }
+    #[test]
+    fn failed_sends_are_retried_with_backoff() {
+        new_test_ext().execute_with(|| {
+            use frame_support::traits::Hooks;
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            FailSends::set(2);
+
+            // The first send fails but the NFT stays locked for a retry
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            let transfer = NftBridge::pending_transfer(1, 1).unwrap();
+            assert_eq!(transfer.status, TransferStatus::SendFailed { next_attempt: 11 });
+            assert!(sent_xcm().is_empty());
+            // Nothing went out, so nothing was reported as sent
+            assert!(!System::events().iter().any(|record| matches!(
+                record.event,
+                RuntimeEvent::NftBridge(crate::Event::NFTSent { .. })
+            )));
+
+            // Nothing happens before the attempt is due
+            NftBridge::on_idle(10, Weight::MAX);
+            assert!(sent_xcm().is_empty());
+
+            // The second attempt fails too and backs off quadratically
+            System::set_block_number(11);
+            NftBridge::on_idle(11, Weight::MAX);
+            let transfer = NftBridge::pending_transfer(1, 1).unwrap();
+            assert_eq!(transfer.status, TransferStatus::SendFailed { next_attempt: 51 });
+            assert_eq!(transfer.retry_count, 1);
+
+            System::set_block_number(51);
+            NftBridge::on_idle(51, Weight::MAX);
+            assert_eq!(sent_xcm().len(), 1);
+            assert_eq!(NftBridge::pending_transfer(1, 1).unwrap().status, TransferStatus::InFlight);
+            assert!(FailedSends::<Test>::get(transfer.id).is_none());
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::TransferResent {
+                collection_id: 1,
+                item_id: 1,
+                transfer_id: transfer.id,
+                retry_count: 1,
+            }));
+        });
+    }
+
+    #[test]
+    fn transfers_are_abandoned_after_max_retries() {
+        new_test_ext().execute_with(|| {
+            use frame_support::traits::Hooks;
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            FailSends::set(u32::MAX);
+
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            let transfer_id = NftBridge::pending_transfer(1, 1).unwrap().id;
+
+            for block in [11, 51, 141] {
+                System::set_block_number(block);
+                NftBridge::on_idle(block, Weight::MAX);
+            }
+
+            // The NFT is handed back to the sender
+            assert!(NftBridge::pending_transfer(1, 1).is_none());
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+            assert!(FailedSends::<Test>::get(transfer_id).is_none());
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::TransferAbandoned {
+                collection_id: 1,
+                item_id: 1,
+                transfer_id,
+            }));
+            FailSends::set(0);
+        });
+    }
+    #[test]
+    fn inbound_checks_fail_with_distinct_errors() {
+        new_test_ext().execute_with(|| {
+            use sp_runtime::traits::Hash;
+            System::set_block_number(1);
+            let metadata = b"test_metadata".to_vec();
+            let deliver = |from: u32, item_id: u32, owner: u64, nonce, metadata_hash| {
+                receive(Delivery {
+                    from,
+                    item_id,
+                    owner,
+                    nonce,
+                    metadata_hash,
+                    ..Default::default()
+                })
+            };
+
+            UntrustedSources::set(vec![2001]);
+            assert_eq!(
+                deliver(2001, 1, 2, None, None),
+                Err(Error::<Test>::UntrustedSourceChain.into())
+            );
+
+            InboundNonce::<Test>::insert(2000, 5);
+            assert_eq!(deliver(2000, 1, 2, Some(5), None), Err(Error::<Test>::InvalidNonce.into()));
+
+            let wrong_hash = BlakeTwo256::hash(b"other_metadata");
+            assert_eq!(
+                deliver(2000, 1, 2, None, Some(wrong_hash)),
+                Err(Error::<Test>::MetadataHashMismatch.into())
+            );
+
+            // Item 2 left for para 2002, so only 2002 may bring it back
+            NFTOwners::<Test>::insert(1, 2, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                2,
+                2002,
+                metadata.clone(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            assert_eq!(deliver(2000, 2, 2, None, None), Err(Error::<Test>::WrongReserve.into()));
+
+            assert_ok!(NftBridge::set_inbound_opt_out(RuntimeOrigin::signed(3), true));
+            assert_eq!(
+                deliver(2000, 1, 3, None, None),
+                Err(Error::<Test>::RecipientOptedOut.into())
+            );
+
+            NFTOwners::<Test>::insert(1, 3, 4);
+            assert_eq!(
+                deliver(2000, 3, 2, None, None),
+                Err(Error::<Test>::NFTAlreadyExists.into())
+            );
+
+            // With everything in order the same message goes through and bumps the nonce
+            assert_ok!(deliver(2000, 1, 2, Some(6), Some(BlakeTwo256::hash(&metadata))));
+            assert_eq!(NftBridge::owner(1, 1), Some(2));
+            assert_eq!(InboundNonce::<Test>::get(2000), 6);
+        });
+    }
+    #[test]
+    fn collection_metadata_quota_is_enforced() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let deliver = |collection_id: u32, item_id: u32| {
+                receive(Delivery {
+                    collection_id,
+                    item_id,
+                    metadata: vec![0u8; 1000],
+                    ..Default::default()
+                })
+            };
+
+            // Four items fill collection 1's 4096 byte quota
+            for item in 0..4 {
+                assert_ok!(deliver(1, item));
+            }
+            assert_eq!(CollectionMetadataBytes::<Test>::get(1), 4000);
+            assert_eq!(deliver(1, 4), Err(Error::<Test>::CollectionMetadataQuotaExceeded.into()));
+
+            // Other collections have their own quota
+            assert_ok!(deliver(2, 4));
+
+            // Governance can raise the quota of a single collection
+            assert_ok!(NftBridge::set_collection_metadata_quota(
+                RuntimeOrigin::root(),
+                1,
+                Some(8192)
+            ));
+            assert_ok!(deliver(1, 4));
+            assert_eq!(CollectionMetadataBytes::<Test>::get(1), 5000);
+        });
+    }
+    #[test]
+    fn purge_orphaned_metadata_removes_only_orphans() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let metadata = b"test_metadata".to_vec();
+
+            // Owned, pending and orphaned items
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                1,
+                2000,
+                1,
+                metadata.clone(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            NFTOwners::<Test>::insert(1, 2, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                2,
+                2000,
+                metadata.clone(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            for item in 3..6 {
+                NFTMetadata::<Test>::insert(1, item, metadata.clone());
+                let uris = vec![(MediaKind::Primary, b"ipfs://orphan".to_vec())];
+                MetadataUris::<Test>::insert(1, item, NftBridge::bound_uris(uris).unwrap());
+            }
+            // An item whose wrapper lives on 2000 isn't an orphan
+            NFTMetadata::<Test>::insert(1, 6, metadata.clone());
+            ItemsAbroad::<Test>::insert(1, 6, 2000);
+
+            assert_ok!(NftBridge::purge_orphaned_metadata(RuntimeOrigin::signed(9), 10));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::MetadataPurged {
+                count: 3,
+            }));
+
+            assert!(NftBridge::nft_metadata(1, 1).is_some());
+            assert!(NftBridge::nft_metadata(1, 2).is_some());
+            assert!(NftBridge::nft_metadata(1, 6).is_some());
+            for item in 3..6 {
+                assert!(NftBridge::nft_metadata(1, item).is_none());
+                assert!(NftBridge::nft_metadata_uri(1, item).is_none());
+            }
+        });
+    }
+    #[test]
+    fn frozen_nfts_stay_visible_but_cannot_move() {
+        new_test_ext().execute_with(|| {
+            let send = |item: u32| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item,
+                    2000,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+
+            BridgeLockStrategy::set(LockStrategy::Freeze(CompletionMode::Escrow));
+            assert_ok!(send(1));
+
+            // The owner is still visible but the NFT can neither move nor be sent again
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+            assert!(NftBridge::is_bridge_locked(&1, &1));
+            assert_noop!(send(1), Error::<Test>::TransferAlreadyPending);
+
+            // Only the destination may confirm, which moves the NFT to escrow
+            assert_noop!(
+                NftBridge::confirm_transfer(RuntimeOrigin::signed(2001), 1, 1),
+                Error::<Test>::OriginMismatch
+            );
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert_eq!(NftBridge::owner(1, 1), Some(NftBridge::escrow_account_for(2000)));
+            assert!(!NftBridge::is_bridge_locked(&1, &1));
+
+            BridgeLockStrategy::set(LockStrategy::Freeze(CompletionMode::Burn));
+            assert_ok!(send(2));
+            assert_eq!(NftBridge::owner(1, 2), Some(1));
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 2));
+            assert_eq!(NftBridge::owner(1, 2), None);
+        });
+    }
+
+    #[test]
+    fn frozen_nfts_can_come_back() {
+        new_test_ext().execute_with(|| {
+            BridgeLockStrategy::set(LockStrategy::Freeze(CompletionMode::Escrow));
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+
+            // The destination hands the NFT straight back to someone else
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                1,
+                2000,
+                2,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            assert_eq!(NftBridge::owner(1, 1), Some(2));
+            assert!(!NftBridge::is_bridge_locked(&1, &1));
+        });
+    }
+    #[test]
+    fn confirmation_clears_metadata_but_cancel_keeps_it() {
+        new_test_ext().execute_with(|| {
+            let send = |item: u32| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item,
+                    2000,
+                    b"test_metadata".to_vec(),
+                    Some(b"ipfs://item".to_vec()),
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+            assert_ok!(send(1));
+            assert_ok!(send(2));
+            let transfer_id = NftBridge::pending_transfer(1, 1).unwrap().id;
+
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert!(NftBridge::nft_metadata(1, 1).is_none());
+            assert!(NftBridge::nft_metadata_uri(1, 1).is_none());
+            assert_eq!(
+                Tombstones::<Test>::get(transfer_id),
+                Some(TransferTombstone { dest_para_id: 2000, confirmed_at: 1 })
+            );
+            assert_eq!(TransferKeys::<Test>::get(transfer_id), Some((1, 1)));
+
+            // A cancelled item comes back with its metadata
+            reject(1, 2);
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 2));
+            assert_eq!(NftBridge::nft_metadata(1, 2), Some(b"test_metadata".to_vec()));
+            assert_eq!(NftBridge::nft_metadata_uri(1, 2), Some(b"ipfs://item".to_vec()));
+        });
+    }
+    #[test]
+    fn bridged_at_tracks_when_items_enter_the_bridge() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(5);
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                1,
+                2000,
+                1,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            let status = NftBridge::transfer_status(1, 1).unwrap();
+            assert_eq!(status.bridged_at, Some(5));
+            assert!(status.pending.is_none());
+
+            System::set_block_number(20);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            let status = NftBridge::transfer_status(1, 1).unwrap();
+            assert_eq!(status.bridged_at, Some(20));
+            assert_eq!(status.pending.unwrap().sent_at, 20);
+
+            // Settling the transfer clears the record
+            System::set_block_number(40);
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert!(BridgedAt::<Test>::get(1, 1).is_none());
+            assert_eq!(NftBridge::transfer_status(1, 1), None);
+        });
+    }
+    #[test]
+    fn expiry_sweep_unlocks_with_one_event_per_item() {
+        new_test_ext().execute_with(|| {
+            use frame_support::traits::Hooks;
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+            assert_ok!(NftBridge::send_nfts(
+                RuntimeOrigin::signed(1),
+                batch(&[(1, 1), (1, 2)]),
+                2000,
+                BatchMode::Atomic,
+                None
+            ));
+            // Refused by the destination, so neither can arrive any more
+            reject(1, 1);
+            reject(1, 2);
+
+            let expired = || {
+                System::events()
+                    .into_iter()
+                    .filter(|record| {
+                        matches!(
+                            record.event,
+                            RuntimeEvent::NftBridge(crate::Event::TransferExpired { .. })
+                        )
+                    })
+                    .count()
+            };
+
+            NftBridge::on_idle(1000, Weight::MAX);
+            assert_eq!(expired(), 0);
+
+            System::set_block_number(1001);
+            NftBridge::on_idle(1001, Weight::MAX);
+            NftBridge::on_idle(1001, Weight::MAX);
+            assert_eq!(expired(), 2);
+            System::assert_has_event(RuntimeEvent::NftBridge(crate::Event::TransferExpired {
+                collection_id: 1,
+                item_id: 2,
+                dest_para_id: 2000,
+                sender: 1,
+            }));
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+            assert_eq!(NftBridge::owner(1, 2), Some(1));
+        });
+    }
+    #[test]
+    fn in_flight_cap_queues_and_drains_in_order() {
+        new_test_ext().execute_with(|| {
+            use frame_support::traits::Hooks;
+            System::set_block_number(1);
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig { max_in_flight: Some(1), ..Default::default() })
+            ));
+            for item in 1..=4 {
+                NFTOwners::<Test>::insert(1, item, 1);
+            }
+            assert_ok!(NftBridge::send_nfts(
+                RuntimeOrigin::signed(1),
+                batch(&[(1, 1), (1, 2), (1, 3), (1, 4)]),
+                2000,
+                BatchMode::Atomic,
+                None
+            ));
+
+            // Only the first transfer went out, the rest wait in order
+            assert_eq!(sent_xcm().len(), 1);
+            assert_eq!(InFlightCount::<Test>::get(2000), 1);
+            let queued: Vec<_> = OutboundQueue::<Test>::get(2000).into_iter().collect();
+            let ids: Vec<_> =
+                (2..=4).map(|item| NftBridge::pending_transfer(1, item).unwrap().id).collect();
+            assert_eq!(queued, ids);
+            assert_eq!(NftBridge::pending_transfer(1, 2).unwrap().status, TransferStatus::Queued);
+
+            // Queued transfers can be cancelled at once and never reach the transport
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 3));
+            assert_eq!(NftBridge::owner(1, 3), Some(1));
+            assert_eq!(OutboundQueue::<Test>::get(2000).into_inner(), vec![ids[0], ids[2]]);
+
+            // No slot is free until the first transfer is confirmed
+            NftBridge::on_idle(1, Weight::MAX);
+            assert_eq!(sent_xcm().len(), 1);
+
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            NftBridge::on_idle(1, Weight::MAX);
+            assert_eq!(sent_xcm().len(), 2);
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::TransferDequeued {
+                collection_id: 1,
+                item_id: 2,
+                transfer_id: ids[0],
+                dest_para_id: 2000,
+            }));
+            assert_eq!(NftBridge::pending_transfer(1, 2).unwrap().status, TransferStatus::InFlight);
+            assert_eq!(OutboundQueue::<Test>::get(2000).into_inner(), vec![ids[2]]);
+        });
+    }
+    #[test]
+    fn bridge_lock_filter_covers_every_held_state() {
+        new_test_ext().execute_with(|| {
+            let locked = |item: u32| BridgeLockFilter::<Test>::contains(&(1, item));
+            let send = |item: u32| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item,
+                    2000,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            for item in 1..=4 {
+                NFTOwners::<Test>::insert(1, item, 1);
+            }
+
+            // Owned items are free to move
+            assert!(!locked(1));
+
+            // Pending
+            assert_ok!(send(1));
+            assert!(locked(1));
+
+            // Queued behind a full destination
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig { max_in_flight: Some(1), ..Default::default() })
+            ));
+            assert_ok!(send(2));
+            assert_eq!(NftBridge::pending_transfer(1, 2).unwrap().status, TransferStatus::Queued);
+            assert!(locked(2));
+
+            // Frozen in place
+            BridgeLockStrategy::set(LockStrategy::Freeze(CompletionMode::Escrow));
+            assert_ok!(NftBridge::set_destination_config(RuntimeOrigin::root(), 2000, None));
+            assert_ok!(send(3));
+            assert_eq!(NftBridge::owner(1, 3), Some(1));
+            assert!(locked(3));
+
+            assert!(!locked(4));
+        });
+    }
+    #[test]
+    fn bridge_storage_keys_match_hashed_keys() {
+        new_test_ext().execute_with(|| {
+            use sp_io::hashing::{blake2_128, twox_128};
+            let key = |storage: &[u8]| {
+                let mut key = twox_128(b"NftBridge").to_vec();
+                key.extend(twox_128(storage));
+                for part in [1u32.encode(), 7u32.encode()] {
+                    key.extend(blake2_128(&part));
+                    key.extend(part);
+                }
+                key
+            };
+
+            assert_eq!(
+                NftBridge::bridge_storage_keys(1, 7),
+                vec![key(b"NFTOwners"), key(b"PendingTransfers")]
+            );
+            assert_eq!(NftBridge::ownership_proof(1, 7), None);
+
+            NFTOwners::<Test>::insert(1, 7, 3);
+            let statement = NftBridge::ownership_proof(1, 7).unwrap();
+            assert_eq!(statement.owner_key, key(b"NFTOwners"));
+            assert_eq!(statement.owner, Some(3));
+            assert_eq!(statement.pending, None);
+        });
+    }
+
+    #[test]
+    fn lock_proofs_are_verified_when_given_or_required() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let deliver = |item_id: u32, proof: Option<&[u8]>| {
+                receive(Delivery {
+                    item_id,
+                    lock_proof: proof.map(|p| p.to_vec()),
+                    ..Default::default()
+                })
+            };
+
+            assert_eq!(
+                deliver(1, Some(&b"forged"[..])),
+                Err(Error::<Test>::InvalidLockProof.into())
+            );
+            assert_ok!(deliver(1, None));
+
+            RequireLockProof::set(true);
+            assert_eq!(deliver(2, None), Err(Error::<Test>::InvalidLockProof.into()));
+            assert_ok!(deliver(2, Some(&b"valid"[..])));
+        });
+    }
+    #[test]
+    fn routed_destinations_get_a_two_hop_program() {
+        new_test_ext().execute_with(|| {
+            let send = |item: u32, dest: u32| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item,
+                    dest,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            let sibling = |para: u32| MultiLocation { parents: 1, interior: X1(Parachain(para)) };
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+
+            // Direct: the destination deposits the NFT itself
+            assert_ok!(send(1, 3000));
+            let (target, message) = sent_xcm()[0].clone();
+            assert_eq!(target, sibling(3000));
+            assert!(matches!(
+                message.0.last(),
+                Some(InitiateReserveWithdraw { reserve, .. }) if *reserve == sibling(3000)
+            ));
+            assert!(matches!(remote_program(&message).0[..], [DepositAsset { .. }]));
+
+            // Routed: withdrawn on 1000, which pays for itself and forwards to 3000
+            assert_ok!(NftBridge::set_route(RuntimeOrigin::root(), 3000, Some(1000)));
+            assert_ok!(send(2, 3000));
+            let (target, message) = sent_xcm()[1].clone();
+            assert_eq!(target, sibling(1000));
+            assert!(matches!(
+                message.0.last(),
+                Some(InitiateReserveWithdraw { reserve, .. }) if *reserve == sibling(1000)
+            ));
+            let hop = remote_program(&message);
+            let [BuyExecution { .. }, DepositReserveAsset { dest, xcm, .. }] = &hop.0[..] else {
+                panic!("unexpected hop program {:?}", hop)
+            };
+            assert_eq!(*dest, sibling(3000));
+            assert!(matches!(xcm.0[..], [BuyExecution { .. }, DepositAsset { .. }]));
+
+            // Bookkeeping follows the final destination
+            assert_eq!(NftBridge::pending_transfer(1, 2).unwrap().dest, sibling(3000));
+            let mut pending = NftBridge::pending_to(3000, 10);
+            pending.sort();
+            assert_eq!(pending, vec![(1, 1), (1, 2)]);
+        });
+    }
+    #[test]
+    fn bundles_cross_collections_in_one_message() {
+        new_test_ext().execute_with(|| {
+            let items = [(1, 1), (1, 2), (2, 1)];
+            for (collection, item) in items {
+                NFTOwners::<Test>::insert(collection, item, 1);
+            }
+            assert_ok!(NftBridge::send_nfts(
+                RuntimeOrigin::signed(1),
+                batch(&items),
+                2000,
+                BatchMode::Bundle,
+                None
+            ));
+
+            // One message carrying three distinct assets
+            assert_eq!(sent_xcm().len(), 1);
+            let (_, message) = sent_xcm()[0].clone();
+            let ReserveAssetDeposited(assets) = &message.0[0] else { panic!("not a reserve transfer") };
+            let expected: Vec<_> = items
+                .iter()
+                .map(|&(collection, item)| {
+                    NftBridge::match_nft_asset(&NftBridge::nft_asset(collection, item).unwrap())
+                        .unwrap()
+                })
+                .collect();
+            let mut matched = NftBridge::match_nft_assets(assets).unwrap();
+            matched.sort();
+            let mut expected_sorted = expected.clone();
+            expected_sorted.sort();
+            assert_eq!(matched, expected_sorted);
+            assert!(matches!(
+                remote_program(&message).0[..],
+                [DepositAsset { assets: Wild(AllCounted(3)), .. }]
+            ));
+            for (collection, item) in items {
+                assert!(NftBridge::pending_transfer(collection, item).is_some());
+            }
+
+            // The receiving side credits the whole bundle
+            assert_ok!(NftBridge::receive_nfts(
+                RuntimeOrigin::signed(2000),
+                batch(&[(5, 1), (5, 2), (6, 1)]),
+                2000,
+                3
+            ));
+            assert_eq!(NftBridge::owner(5, 1), Some(3));
+            assert_eq!(NftBridge::owner(6, 1), Some(3));
+        });
+    }
+
+    #[test]
+    fn failing_bundle_item_rolls_back_the_whole_receive() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            // The last item already exists here
+            NFTOwners::<Test>::insert(6, 1, 9);
+
+            let items = batch(&[(5, 1), (5, 2), (6, 1)]);
+            assert_ok!(NftBridge::receive_nfts(RuntimeOrigin::signed(2000), items, 2000, 3));
+            assert_eq!(NftBridge::owner(5, 1), None);
+            assert_eq!(NftBridge::owner(5, 2), None);
+            // The refusal is recorded against the item that caused it
+            let failures = NftBridge::inbound_failures(2000);
+            assert_eq!(failures.len(), 1);
+            assert_eq!((failures[0].collection_id, failures[0].item_id), (6, 1));
+            assert_eq!(failures[0].reason, Error::<Test>::NFTAlreadyExists.into());
+            System::assert_last_event(
+                Event::InboundDeliveryFailed {
+                    from_para_id: 2000,
+                    collection_id: 6,
+                    item_id: 1,
+                    reason: Error::<Test>::NFTAlreadyExists.into(),
+                }
+                .into(),
+            );
+        });
+    }
+    // The pallet call carried by the last bridge message sent to `para_id`
+    fn last_bridge_call(para_id: u32) -> crate::Call<Test> {
+        let (_, message) = sent_xcm()
+            .into_iter()
+            .filter(|(dest, _)| *dest == MultiLocation { parents: 1, interior: X1(Parachain(para_id)) })
+            .filter(|(_, message)| matches!(message.0.last(), Some(Transact { .. })))
+            .last()
+            .expect("no bridge call sent");
+        let Some(Transact { call, .. }) = message.0.last() else { unreachable!() };
+        let encoded = call.clone().into_encoded();
+        <(u8, crate::Call<Test>)>::decode(&mut &encoded[..]).unwrap().1
+    }
+
+    #[test]
+    fn swap_accepted_before_deadline_delivers_both_nfts() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            // Proposing chain 1000 holds (1, 1) of account 1, counterpart 2000 holds (7, 7) of 5.
+            // Both sides run in the same test runtime under different keys.
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(7, 7, 5);
+
+            assert_ok!(NftBridge::propose_swap(RuntimeOrigin::signed(1), 1, 1, 2000, 7, 7, 5, 50));
+            assert!(NftBridge::is_bridge_locked(&1, &1));
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    1,
+                    3000,
+                    vec![],
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None
+                ),
+                Error::<Test>::ItemInSwap
+            );
+
+            // The counterpart records the offer and its owner accepts
+            let crate::Call::swap_offered { swap_id, offer } = last_bridge_call(2000) else {
+                panic!("expected swap_offered")
+            };
+            assert_ok!(NftBridge::swap_offered(RuntimeOrigin::signed(1000), swap_id, offer));
+            assert_ok!(NftBridge::accept_swap(RuntimeOrigin::signed(5), 1000, swap_id));
+            assert!(NftBridge::is_bridge_locked(&7, &7));
+            assert_eq!(last_bridge_call(1000), crate::Call::swap_accepted { swap_id });
+
+            // The proposing chain sends (1, 1) to 5 and tells the counterpart to settle
+            assert_ok!(NftBridge::swap_accepted(RuntimeOrigin::signed(2000), swap_id));
+            assert!(NftBridge::pending_swap(swap_id).is_none());
+            assert_eq!(
+                NftBridge::pending_transfer(1, 1).unwrap().dest,
+                MultiLocation { parents: 1, interior: X1(Parachain(2000)) }
+            );
+            let (_, delivery) = sent_xcm()
+                .into_iter()
+                .find(|(_, message)| matches!(message.0.last(), Some(InitiateReserveWithdraw { .. })))
+                .unwrap();
+            assert!(matches!(
+                remote_program(&delivery).0.first(),
+                Some(DepositAsset { beneficiary, .. }) if *beneficiary == AccountToLocation::convert(5)
+            ));
+            assert_eq!(last_bridge_call(2000), crate::Call::swap_settled { swap_id });
+
+            // The counterpart sends (7, 7) to the proposer
+            assert_ok!(NftBridge::swap_settled(RuntimeOrigin::signed(1000), swap_id));
+            assert!(NftBridge::incoming_swap(1000, swap_id).is_none());
+            assert!(!SwapEscrow::<Test>::contains_key(7, 7));
+            assert_eq!(
+                NftBridge::pending_transfer(7, 7).unwrap().dest,
+                MultiLocation { parents: 1, interior: X1(Parachain(1000)) }
+            );
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::SwapSettled {
+                from_para_id: 1000,
+                swap_id,
+            }));
+        });
+    }
+
+    #[test]
+    fn swap_can_be_cancelled_only_after_deadline() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_ok!(NftBridge::propose_swap(RuntimeOrigin::signed(1), 1, 1, 2000, 7, 7, 5, 10));
+
+            assert_noop!(
+                NftBridge::cancel_swap(RuntimeOrigin::signed(1), None, 0),
+                Error::<Test>::SwapDeadlineNotReached
+            );
+            System::set_block_number(11);
+            assert_noop!(
+                NftBridge::cancel_swap(RuntimeOrigin::signed(2), None, 0),
+                Error::<Test>::NotSwapParty
+            );
+            assert_ok!(NftBridge::cancel_swap(RuntimeOrigin::signed(1), None, 0));
+            assert!(!NftBridge::is_bridge_locked(&1, &1));
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+
+            // A late acceptance finds nothing to settle
+            assert_noop!(
+                NftBridge::swap_accepted(RuntimeOrigin::signed(2000), 0),
+                Error::<Test>::SwapNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn unanswered_swaps_expire_and_release_escrow() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(7, 7, 5);
+
+            // Proposing side: the counterparty never answers
+            assert_ok!(NftBridge::propose_swap(RuntimeOrigin::signed(1), 1, 1, 2000, 7, 7, 5, 10));
+            // Counterpart side: accepted an offer the proposing chain never settles
+            let crate::Call::swap_offered { offer, .. } = last_bridge_call(2000) else {
+                panic!("expected swap_offered")
+            };
+            assert_ok!(NftBridge::swap_offered(RuntimeOrigin::signed(1000), 4, offer));
+            assert_ok!(NftBridge::accept_swap(RuntimeOrigin::signed(5), 1000, 4));
+
+            NftBridge::on_idle(10, Weight::MAX);
+            assert!(NftBridge::pending_swap(0).is_some());
+
+            NftBridge::on_idle(11, Weight::MAX);
+            assert!(NftBridge::pending_swap(0).is_none());
+            assert!(!NftBridge::is_bridge_locked(&1, &1));
+            System::assert_has_event(RuntimeEvent::NftBridge(crate::Event::SwapExpired {
+                from_para_id: None,
+                swap_id: 0,
+            }));
+            // The counterpart's escrow outlives the proposer's deadline until its own timeout
+            assert!(NftBridge::is_bridge_locked(&7, &7));
+
+            NftBridge::on_idle(22, Weight::MAX);
+            assert!(NftBridge::incoming_swap(1000, 4).is_none());
+            assert!(!NftBridge::is_bridge_locked(&7, &7));
+            assert_eq!(NftBridge::owner(7, 7), Some(5));
+        });
+    }
+    fn send(sender: u64, collection_id: u32, item_id: u32) -> DispatchResult {
+        NFTOwners::<Test>::insert(collection_id, item_id, sender);
+        NftBridge::send_nft(
+            RuntimeOrigin::signed(sender),
+            collection_id,
+            item_id,
+            2000,
+            vec![],
+            None,
+            None,
+            ExecutionLimit::Default,
+            false,
+            vec![],
+            None,
+        )
+    }
+
+    /// An inbound NFT as a sibling delivers it, defaulting to item 1/1 from para 2000 for account 2
+    struct Delivery {
+        from: u32,
+        collection_id: u32,
+        item_id: u32,
+        owner: u64,
+        metadata: Vec<u8>,
+        metadata_uri: Option<Vec<u8>>,
+        nonce: Option<u64>,
+        metadata_hash: Option<H256>,
+        lock_proof: Option<Vec<u8>>,
+        delivery_id: Option<[u8; 32]>,
+        sent_at: Option<u64>,
+        transfer_id: Option<TransferId>,
+        history: Option<ProvenanceTrailOf<Test>>,
+    }
+
+    impl Default for Delivery {
+        fn default() -> Self {
+            Delivery {
+                from: 2000,
+                collection_id: 1,
+                item_id: 1,
+                owner: 2,
+                metadata: b"test_metadata".to_vec(),
+                metadata_uri: None,
+                nonce: None,
+                metadata_hash: None,
+                lock_proof: None,
+                delivery_id: None,
+                sent_at: None,
+                transfer_id: None,
+                history: None,
+            }
+        }
+    }
+
+    /// Deliver through `receive_nft`. A refused delivery is recorded and accepted, so the error
+    /// that refused it is read back from its `InboundDeliveryFailed` event, which needs a block
+    /// after genesis.
+    fn receive(delivery: Delivery) -> DispatchResult {
+        assert!(System::block_number() > 0, "refusals are only visible in events");
+        let seen = System::events().len();
+        NftBridge::receive_nft(
+            RuntimeOrigin::signed(delivery.from as u64),
+            delivery.collection_id,
+            delivery.item_id,
+            delivery.from,
+            delivery.owner,
+            delivery.metadata,
+            delivery.metadata_uri,
+            delivery.nonce,
+            delivery.metadata_hash,
+            delivery.lock_proof,
+            vec![],
+            delivery.delivery_id,
+            delivery.sent_at,
+            delivery.transfer_id,
+            delivery.history,
+        )?;
+        let refusal = System::events().into_iter().skip(seen).find_map(|record| {
+            match record.event {
+                RuntimeEvent::NftBridge(Event::InboundDeliveryFailed { reason, .. }) => Some(reason),
+                _ => None,
+            }
+        });
+        refusal.map_or(Ok(()), Err)
+    }
+
+    /// Have the destination refuse an NFT sent to it, so its message can no longer arrive
+    fn reject(collection_id: u32, item_id: u32) {
+        let transfer_id = NftBridge::pending_transfer(collection_id, item_id).unwrap().id;
+        let code = RejectionCode::Other;
+        assert_ok!(NftBridge::report_rejection(RuntimeOrigin::signed(2000), transfer_id, code));
+    }
+
+    #[test]
+    fn default_and_fixed_fee_policies_charge_the_sender() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            BridgeFee::set(10);
+            Balances::make_free_balance_be(&1, 100);
+            assert_ok!(NftBridge::set_collection_fee_policy(
+                RuntimeOrigin::root(),
+                2,
+                FeePolicy::Fixed(3)
+            ));
+
+            assert_ok!(send(1, 1, 1));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::NFTSent {
+                collection_id: 1,
+                item_id: 1,
+                dest_para_id: 2000,
+                fee: 10,
+                delivery_fee: 0,
+                canonical_id: NftBridge::canonical_id(1, 1),
+            }));
+            assert_ok!(send(1, 2, 1));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::NFTSent {
+                collection_id: 2,
+                item_id: 1,
+                dest_para_id: 2000,
+                fee: 3,
+                delivery_fee: 0,
+                canonical_id: NftBridge::canonical_id(2, 1),
+            }));
+            assert_eq!(Balances::free_balance(1), 87);
+            assert_eq!(Balances::free_balance(FeeAccount::get()), 13);
+
+            // Resetting to the default removes the override
+            assert_ok!(NftBridge::set_collection_fee_policy(
+                RuntimeOrigin::root(),
+                2,
+                FeePolicy::Default
+            ));
+            assert!(NftBridge::collection_fee_override(2).is_none());
+            assert_eq!(NftBridge::bridge_fee(2, 0), Some(10));
+
+            // An unfunded sender can't send, and nothing is locked
+            assert!(send(2, 1, 2).is_err());
+            assert!(NftBridge::pending_transfer(1, 2).is_none());
+        });
+    }
+
+    #[test]
+    fn waived_fee_touches_no_balances() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            BridgeFee::set(10);
+            assert_ok!(NftBridge::set_collection_fee_policy(
+                RuntimeOrigin::root(),
+                3,
+                FeePolicy::Waived
+            ));
+            System::reset_events();
+
+            // Account 2 holds nothing at all
+            assert_ok!(send(2, 3, 1));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::NFTSent {
+                collection_id: 3,
+                item_id: 1,
+                dest_para_id: 2000,
+                fee: 0,
+                delivery_fee: 0,
+                canonical_id: NftBridge::canonical_id(3, 1),
+            }));
+            assert!(!System::events().iter().any(|r| matches!(r.event, RuntimeEvent::Balances(_))));
+            assert_eq!(Balances::total_issuance(), 0);
+        });
+    }
+    #[test]
+    fn bridge_fee_grows_with_metadata_size() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            BridgeFee::set(10);
+            FeePerMetadataByte::set(2);
+            Balances::make_free_balance_be(&1, 10_000);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+
+            // The quote is what the send charges
+            let quote = NftBridge::validate_send(1, 1, 1, 2000, 13, 0).unwrap();
+            assert_eq!(quote.bridge_fee, 10 + 2 * 13);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            assert_eq!(Balances::free_balance(1), 10_000 - quote.bridge_fee);
+
+            // A maximal payload pays for all 1024 + 256 bytes
+            let breakdown = NftBridge::estimate_send_fee(1, 2, 2000, 1024).unwrap();
+            assert_eq!(breakdown.bridge_fee, 10 + 2 * 1024);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                2,
+                2000,
+                vec![0; 1024],
+                Some(vec![0; 256]),
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::NFTSent {
+                collection_id: 1,
+                item_id: 2,
+                dest_para_id: 2000,
+                fee: 10 + 2 * 1280,
+                delivery_fee: 0,
+                canonical_id: NftBridge::canonical_id(1, 2),
+            }));
+
+            // A send that fails after the fee was computed is refunded in full
+            NFTOwners::<Test>::insert(1, 3, 1);
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    3,
+                    2000,
+                    vec![0; 1025],
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None
+                ),
+                Error::<Test>::MetadataTooLong
+            );
+        });
+    }
+    #[test]
+    fn sender_pays_the_transport_delivery_fee() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            DeliveryFee::set(25);
+            Balances::make_free_balance_be(&1, 100);
+
+            assert_ok!(send(1, 1, 1));
+            assert_eq!(Balances::free_balance(1), 75);
+            assert_eq!(Balances::free_balance(FeeAccount::get()), 25);
+            assert_eq!(sent_xcm().len(), 1);
+
+            // A sender who can't cover the fee keeps the NFT and nothing is sent
+            NFTOwners::<Test>::insert(1, 2, 2);
+            assert!(NftBridge::send_nft(
+                RuntimeOrigin::signed(2),
+                1,
+                2,
+                2000,
+                vec![],
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            )
+            .is_err());
+            assert!(NftBridge::pending_transfer(1, 2).is_none());
+            assert_eq!(NftBridge::owner(1, 2), Some(2));
+            assert_eq!(sent_xcm().len(), 1);
+        });
+    }
+    #[test]
+    fn oversized_message_fails_before_any_state_change() {
+        new_test_ext().execute_with(|| {
+            let call = |len: usize| -> Option<BoundedVec<u8, ConstU32<256>>> {
+                Some(vec![0u8; len].try_into().unwrap())
+            };
+            let send = |item_id: u32, call_len: usize| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item_id,
+                    2000,
+                    vec![],
+                    None,
+                    call(call_len),
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+            NFTOwners::<Test>::insert(1, 3, 1);
+
+            // Measure a message with a 100-byte call and make that the limit
+            assert_ok!(send(1, 100));
+            let (_, message) = sent_xcm().pop().unwrap();
+            let size = xcm::VersionedXcm::from(message).encoded_size() as u32;
+            MaxOutboundMessageSize::set(size);
+
+            // One byte more is rejected without locking or charging anything
+            assert_ok!(send(2, 100));
+            assert_noop!(send(3, 101), Error::<Test>::MessageTooLarge);
+
+            // A tighter per-destination limit wins
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig { max_message_size: Some(size - 1), ..Default::default() })
+            ));
+            assert_noop!(send(3, 100), Error::<Test>::MessageTooLarge);
+            assert_eq!(
+                NftBridge::validate_send(1, 1, 3, 2000, 0, 0).map(|quote| quote.message_size < size),
+                Ok(true)
+            );
+        });
+    }
+    #[test]
+    fn preview_matches_the_message_actually_sent() {
+        new_test_ext().execute_with(|| {
+            NFTOwners::<Test>::insert(1, 1, 1);
+
+            let preview =
+                NftBridge::preview_send_xcm(1, 1, 1, 2000, b"test_metadata".to_vec(), None).unwrap();
+            // Nothing was locked or sent
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+            assert!(sent_xcm().is_empty());
+
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            let (_, sent) = sent_xcm().pop().unwrap();
+            assert_eq!(Xcm::<()>::try_from(preview), Ok(sent));
+
+            // A `Transact` destination is previewed with the metadata and URI it would carry
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2001,
+                Some(DestinationConfig {
+                    protocol: TransferProtocol::Transact,
+                    remote_bridge_pallet_index: 77,
+                    remote_receive_call_index: 3,
+                    ..Default::default()
+                })
+            ));
+            NFTOwners::<Test>::insert(1, 2, 1);
+            let uri = Some(b"ipfs://item".to_vec());
+            let preview = NftBridge::preview_send_xcm(1, 1, 2, 2001, b"meta".to_vec(), uri.clone())
+                .unwrap();
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                2,
+                2001,
+                b"meta".to_vec(),
+                uri,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            let (_, sent) = sent_xcm().pop().unwrap();
+            let call = transact_call(&sent);
+            let message = BridgeMessage::<u64, u32, u32>::decode(&mut &call[2..]).unwrap();
+            assert_eq!(message.metadata, b"meta".to_vec());
+            assert_eq!(Xcm::<()>::try_from(preview), Ok(sent));
+        });
+    }
+    #[test]
+    fn metadata_sync_updates_the_wrapper_on_its_holder() {
+        new_test_ext().execute_with(|| {
+            use sp_runtime::traits::Hash;
+            System::set_block_number(1);
+            // (1, 1) lives here and has a wrapper on 2000, which got it from para 1000
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTMetadata::<Test>::insert(1, 1, b"new_metadata".to_vec());
+
+            // Neither a stranger nor an unrelated admin may sync
+            assert_noop!(
+                NftBridge::sync_metadata(RuntimeOrigin::signed(3), 1, 1, 2000),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 3));
+            assert_ok!(NftBridge::sync_metadata(RuntimeOrigin::signed(3), 1, 1, 2000));
+
+            let crate::Call::update_metadata { metadata, metadata_hash, .. } = last_bridge_call(2000)
+            else {
+                panic!("expected update_metadata")
+            };
+            assert_eq!(metadata, b"new_metadata".to_vec());
+            assert_eq!(metadata_hash, BlakeTwo256::hash(&metadata));
+
+            // The holder received the wrapper from 1000
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(1000),
+                5,
+                5,
+                1000,
+                2,
+                b"old_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            assert_eq!(NftBridge::item_origin(5, 5), Some(1000));
+            assert_ok!(NftBridge::update_metadata(
+                RuntimeOrigin::signed(1000),
+                5,
+                5,
+                metadata_hash,
+                metadata,
+                None
+            ));
+            assert_eq!(NFTMetadata::<Test>::get(5, 5), Some(b"new_metadata".to_vec()));
+            System::assert_last_event(
+                Event::MetadataUpdated { collection_id: 5, item_id: 5, from_para_id: 1000 }.into(),
+            );
+        });
+    }
+
+    #[test]
+    fn metadata_update_only_accepted_from_the_origin_chain() {
+        new_test_ext().execute_with(|| {
+            use sp_runtime::traits::Hash;
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(1000),
+                5,
+                5,
+                1000,
+                2,
+                b"old_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            let metadata = b"forged".to_vec();
+            let metadata_hash = BlakeTwo256::hash(&metadata);
+
+            // Another chain can't rewrite the wrapper
+            assert_noop!(
+                NftBridge::update_metadata(
+                    RuntimeOrigin::signed(2000),
+                    5,
+                    5,
+                    metadata_hash,
+                    metadata.clone(),
+                    None
+                ),
+                Error::<Test>::WrongReserve
+            );
+            // Nor can anyone touch items native to this chain
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_noop!(
+                NftBridge::update_metadata(
+                    RuntimeOrigin::signed(1000),
+                    1,
+                    1,
+                    metadata_hash,
+                    metadata,
+                    None
+                ),
+                Error::<Test>::WrongReserve
+            );
+            assert_eq!(NFTMetadata::<Test>::get(5, 5), Some(b"old_metadata".to_vec()));
+        });
+    }
+
+    #[test]
+    fn metadata_sync_is_rate_limited_per_item() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+            NFTMetadata::<Test>::insert(1, 1, b"metadata".to_vec());
+            NFTMetadata::<Test>::insert(1, 2, b"metadata".to_vec());
+
+            assert_ok!(NftBridge::sync_metadata(RuntimeOrigin::signed(1), 1, 1, 2000));
+            assert_noop!(
+                NftBridge::sync_metadata(RuntimeOrigin::signed(1), 1, 1, 3000),
+                Error::<Test>::SyncRateLimited
+            );
+            // Other items are unaffected
+            assert_ok!(NftBridge::sync_metadata(RuntimeOrigin::signed(1), 1, 2, 2000));
+
+            System::set_block_number(11);
+            assert_ok!(NftBridge::sync_metadata(RuntimeOrigin::signed(1), 1, 1, 3000));
+        });
+    }
+    #[test]
+    fn bridging_an_item_subscribes_the_destination() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(send(1, 1, 1));
+            assert!(MetadataSubscribers::<Test>::contains_key(1, 2000));
+            assert!(!MetadataSubscribers::<Test>::contains_key(2, 2000));
+
+            assert_noop!(
+                NftBridge::remove_metadata_subscriber(RuntimeOrigin::signed(1), 1, 2000),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::remove_metadata_subscriber(RuntimeOrigin::root(), 1, 2000));
+            assert!(!MetadataSubscribers::<Test>::contains_key(1, 2000));
+        });
+    }
+
+    #[test]
+    fn metadata_update_fans_out_to_subscribers_and_queues_the_rest() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTMetadata::<Test>::insert(1, 1, b"old".to_vec());
+            for para_id in [2000, 3000, 4000] {
+                MetadataSubscribers::<Test>::insert(1, para_id, ());
+            }
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 3));
+
+            assert_noop!(
+                NftBridge::update_item_metadata(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    1,
+                    b"new".to_vec(),
+                    None
+                ),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::update_item_metadata(
+                RuntimeOrigin::signed(3),
+                1,
+                1,
+                b"new".to_vec(),
+                None
+            ));
+            assert_eq!(NFTMetadata::<Test>::get(1, 1), Some(b"new".to_vec()));
+
+            // MaxSubscribersPerUpdate is 2: two messages now, one queued
+            let synced = |para_id: u32| {
+                let dest = MultiLocation { parents: 1, interior: X1(Parachain(para_id)) };
+                sent_xcm().iter().filter(|(to, _)| *to == dest).count()
+            };
+            assert_eq!(sent_xcm().len(), 2);
+            assert_eq!(MetadataSyncCursors::<Test>::iter_keys().collect::<Vec<_>>(), vec![(1, 1)]);
+            let late_para = [2000, 3000, 4000].into_iter().find(|para| synced(*para) == 0).unwrap();
+            System::assert_has_event(
+                Event::MetadataSyncQueued { collection_id: 1, item_id: 1 }.into(),
+            );
+
+            NftBridge::on_idle(1, Weight::MAX);
+            assert_eq!(MetadataSyncCursors::<Test>::iter_keys().count(), 0);
+            for para_id in [2000, 3000, 4000] {
+                assert_eq!(synced(para_id), 1);
+            }
+            let crate::Call::update_metadata { metadata, .. } = last_bridge_call(late_para) else {
+                panic!("expected update_metadata")
+            };
+            assert_eq!(metadata, b"new".to_vec());
+            System::assert_last_event(
+                Event::MetadataSyncSent { collection_id: 1, item_id: 1, dest_para_id: late_para }
+                    .into(),
+            );
+        });
+    }
+    #[test]
+    fn collection_export_runs_to_completion_over_several_blocks() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let owners = [1, 1, 2, 2, 3];
+            for (item, owner) in (1..).zip(owners) {
+                NFTOwners::<Test>::insert(4, item, owner);
+            }
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 4, None, 9));
+
+            assert_noop!(
+                NftBridge::export_collection(RuntimeOrigin::signed(1), 4, 2000, 2),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_noop!(
+                NftBridge::export_collection(RuntimeOrigin::signed(9), 4, 2000, 11),
+                Error::<Test>::InvalidBatchSize
+            );
+            assert_ok!(NftBridge::export_collection(RuntimeOrigin::signed(9), 4, 2000, 2));
+            assert_noop!(send(1, 4, 1), Error::<Test>::CollectionMigrating);
+
+            // Two items per block; the third block sends the last one and finishes
+            for (block, exported) in [(2, 2), (3, 4)] {
+                NftBridge::on_idle(block, Weight::MAX);
+                let progress =
+                    Event::CollectionExportProgress { collection_id: 4, exported, skipped: 0 };
+                System::assert_last_event(progress.into());
+            }
+            NftBridge::on_idle(4, Weight::MAX);
+            System::assert_last_event(
+                Event::CollectionExported {
+                    collection_id: 4,
+                    dest_para_id: 2000,
+                    exported: 5,
+                    skipped: 0,
+                }
+                .into(),
+            );
+            assert_eq!(sent_xcm().len(), 5);
+            assert!(NftBridge::collection_export(4).is_none());
+
+            // Every item is in flight on behalf of its own owner
+            for (item, owner) in (1..).zip(owners) {
+                assert_eq!(NftBridge::owner(4, item), Some(NftBridge::escrow_account_for(2000)));
+                assert_eq!(NftBridge::pending_transfer(4, item).map(|t| t.sender), Some(owner));
+            }
+        });
+    }
+
+    #[test]
+    fn aborted_export_returns_unconfirmed_items() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            for item in 1..=4 {
+                NFTOwners::<Test>::insert(4, item, 1);
+            }
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 4, None, 9));
+            assert_ok!(NftBridge::export_collection(RuntimeOrigin::signed(9), 4, 2000, 2));
+
+            NftBridge::on_idle(2, Weight::MAX);
+            let sent: Vec<u32> = PendingTransfers::<Test>::iter_prefix(4).map(|(i, _)| i).collect();
+            assert_eq!(sent.len(), 2);
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 4, sent[0]));
+            reject(4, sent[1]);
+
+            assert_noop!(
+                NftBridge::abort_export(RuntimeOrigin::signed(9), 4, 1),
+                Error::<Test>::BadWitness
+            );
+            assert_ok!(NftBridge::abort_export(RuntimeOrigin::signed(9), 4, 2));
+            System::assert_last_event(
+                Event::CollectionExportAborted { collection_id: 4, restored: 1 }.into(),
+            );
+
+            // The confirmed item stays gone, everything else is back with its owner
+            assert_eq!(NftBridge::owner(4, sent[0]), None);
+            for item in (1..=4).filter(|item| *item != sent[0]) {
+                assert_eq!(NftBridge::owner(4, item), Some(1));
+            }
+            NftBridge::on_idle(3, Weight::MAX);
+            assert_eq!(sent_xcm().len(), 2);
+            assert_ok!(send(1, 4, sent[1]));
+        });
+    }
+    #[test]
+    fn non_transferable_collection_cannot_leave() {
+        new_test_ext().execute_with(|| {
+            // Root flags collection 1, the collection admin flags collection 2
+            assert_ok!(NftBridge::set_collection_transferability(
+                RuntimeOrigin::root(),
+                1,
+                Some(TransferRestriction::Outbound)
+            ));
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 2, None, 9));
+            assert_noop!(
+                NftBridge::set_collection_transferability(
+                    RuntimeOrigin::signed(1),
+                    2,
+                    Some(TransferRestriction::Outbound)
+                ),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::set_collection_transferability(
+                RuntimeOrigin::signed(9),
+                2,
+                Some(TransferRestriction::Outbound)
+            ));
+
+            for collection_id in [1, 2] {
+                assert_noop!(send(1, collection_id, 1), Error::<Test>::CollectionNonTransferable);
+                assert_eq!(NftBridge::owner(collection_id, 1), Some(1));
+            }
+            assert_noop!(
+                NftBridge::propose_swap(RuntimeOrigin::signed(1), 1, 1, 2000, 7, 7, 5, 50),
+                Error::<Test>::CollectionNonTransferable
+            );
+
+            // Lifting the flag makes the collection bridgeable again
+            assert_ok!(NftBridge::set_collection_transferability(
+                RuntimeOrigin::signed(9),
+                2,
+                None
+            ));
+            assert_ok!(send(1, 2, 1));
+        });
+    }
+
+    #[test]
+    fn non_transferable_collection_receives_unless_fully_restricted() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let deliver = |collection_id: u32, item_id: u32| {
+                receive(Delivery { collection_id, item_id, ..Default::default() })
+            };
+            assert_ok!(NftBridge::set_collection_transferability(
+                RuntimeOrigin::root(),
+                1,
+                Some(TransferRestriction::Outbound)
+            ));
+            assert_ok!(deliver(1, 1));
+
+            // A fully restricted collection only takes back its own items
+            assert_ok!(send(1, 1, 2));
+            assert_ok!(NftBridge::set_collection_transferability(
+                RuntimeOrigin::root(),
+                1,
+                Some(TransferRestriction::Full)
+            ));
+            assert_eq!(deliver(1, 3), Err(Error::<Test>::CollectionNonTransferable.into()));
+            assert_ok!(deliver(1, 2));
+            assert_eq!(NftBridge::owner(1, 2), Some(2));
+        });
+    }
+    #[test]
+    fn transfer_filter_veto_leaves_no_trace() {
+        new_test_ext().execute_with(|| {
+            BridgeFee::set(10);
+            Balances::make_free_balance_be(&1, 100);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            let send = |dest_para_id: u32| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    1,
+                    dest_para_id,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+
+            // The filter's own error reaches the caller and nothing is locked, charged or sent
+            assert_noop!(send(6000), sp_runtime::DispatchError::Other("GeofencedDestination"));
+            assert_eq!(Balances::free_balance(1), 100);
+            assert!(sent_xcm().is_empty());
+            assert_eq!(
+                NftBridge::validate_send(1, 1, 1, 6000, 0, 0).map(|_| ()),
+                Err(sp_runtime::DispatchError::Other("GeofencedDestination"))
+            );
+
+            assert_ok!(send(2000));
+            assert_eq!(Balances::free_balance(1), 90);
+        });
+    }
+    #[test]
+    fn priority_sends_skip_the_saturated_lane_in_order() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            Balances::make_free_balance_be(&1, 100);
+            let send = |item: u32, priority: bool| {
+                NFTOwners::<Test>::insert(1, item, 1);
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item,
+                    2000,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    priority,
+                    vec![],
+                    None,
+                )
+            };
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig { max_in_flight: Some(1), ..Default::default() })
+            ));
+
+            // The normal lane is full: item 2 waits
+            assert_ok!(send(1, false));
+            assert_ok!(send(2, false));
+            assert_eq!(sent_xcm().len(), 1);
+
+            // A priority send uses the extra slot and goes out in the same block, for a fee
+            assert_ok!(send(3, true));
+            assert_eq!(sent_xcm().len(), 2);
+            assert_eq!(Balances::free_balance(1), 95);
+            System::assert_has_event(
+                Event::NFTSent {
+                    collection_id: 1,
+                    item_id: 3,
+                    dest_para_id: 2000,
+                    fee: 5,
+                    delivery_fee: 0,
+                    canonical_id: NftBridge::canonical_id(1, 3),
+                }
+                .into(),
+            );
+
+            // With the priority slot taken too, priority sends queue ahead of item 2, in order
+            assert_ok!(send(4, true));
+            assert_ok!(send(5, true));
+            let queue: Vec<_> = OutboundQueue::<Test>::get(2000)
+                .into_iter()
+                .map(|id| QueuedSends::<Test>::get(id).unwrap().1)
+                .collect();
+            assert_eq!(queue, vec![4, 5, 2]);
+
+            // A freed slot goes to the oldest priority transfer
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            NftBridge::on_idle(1, Weight::MAX);
+            assert_eq!(NftBridge::pending_transfer(1, 4).unwrap().status, TransferStatus::InFlight);
+            assert_eq!(NftBridge::pending_transfer(1, 5).unwrap().status, TransferStatus::Queued);
+            assert_eq!(NftBridge::pending_transfer(1, 2).unwrap().status, TransferStatus::Queued);
+        });
+    }
+    #[test]
+    fn unknown_collection_is_reported_separately_from_missing_item() {
+        new_test_ext().execute_with(|| {
+            let send = |collection_id: u32, item_id: u32| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    collection_id,
+                    item_id,
+                    2000,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            let cancel = |collection_id: u32, item_id: u32| {
+                NftBridge::cancel_transfer(RuntimeOrigin::signed(1), collection_id, item_id)
+            };
+
+            // Collection 3 has never been seen
+            assert_noop!(send(3, 1), Error::<Test>::CollectionNotFound);
+            assert_noop!(cancel(3, 1), Error::<Test>::CollectionNotFound);
+
+            // Once its only item has left, the collection is still known
+            NFTOwners::<Test>::insert(3, 1, 1);
+            assert_ok!(send(3, 1));
+            assert_eq!(NftBridge::owner(3, 1), Some(NftBridge::escrow_account_for(2000)));
+            assert_noop!(send(3, 2), Error::<Test>::NFTNotFound);
+            assert_noop!(cancel(3, 2), Error::<Test>::NFTNotFound);
+        });
+    }
+
+    #[test]
+    fn received_collection_becomes_known() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                6,
+                1,
+                2000,
+                2,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            assert!(KnownCollections::<Test>::contains_key(6));
+            assert_noop!(
+                NftBridge::cancel_transfer(RuntimeOrigin::signed(2), 6, 2),
+                Error::<Test>::NFTNotFound
+            );
+        });
+    }
+    #[test]
+    fn only_registered_collections_use_the_bridge() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            Permissionless::set(false);
+            let deliver = |collection_id: u32| {
+                receive(Delivery { collection_id, ..Default::default() })
+            };
+            assert_noop!(send(1, 1, 1), Error::<Test>::CollectionNotRegistered);
+            assert_eq!(deliver(5), Err(Error::<Test>::CollectionNotRegistered.into()));
+            // Dry runs refuse the same sends
+            assert_eq!(
+                NftBridge::validate_send(1, 1, 1, 2000, 0, 0).map(|_| ()),
+                Err(Error::<Test>::CollectionNotRegistered.into())
+            );
+
+            assert_noop!(
+                NftBridge::register_collection(RuntimeOrigin::signed(1), 1, None, 9),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 9));
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 5, Some(2000), 9));
+            System::assert_last_event(
+                Event::CollectionRegistered { collection_id: 5, reserve: Some(2000), admin: 9 }
+                    .into(),
+            );
+            assert_noop!(
+                NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 9),
+                Error::<Test>::CollectionAlreadyRegistered
+            );
+            let info = NftBridge::collection_info(5).unwrap();
+            assert_eq!((info.reserve, info.admin, info.mode), (Some(2000), 9, CollectionMode::Wrapped));
+
+            assert_ok!(send(1, 1, 1));
+            assert_ok!(deliver(5));
+        });
+    }
+
+    #[test]
+    fn deregistration_waits_for_items_to_come_home() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 9));
+            let deregister = || NftBridge::deregister_collection(RuntimeOrigin::root(), 1);
+
+            // Pending, then confirmed and living on 2000
+            assert_ok!(send(1, 1, 1));
+            assert_noop!(deregister(), Error::<Test>::CollectionInUse);
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert_eq!(ItemsAbroad::<Test>::get(1, 1), Some(2000));
+            assert_noop!(deregister(), Error::<Test>::CollectionInUse);
+
+            // Back home it is native again, not a wrapper of 2000's
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                1,
+                2000,
+                1,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            assert_eq!(NftBridge::item_origin(1, 1), None);
+            assert_ok!(deregister());
+            assert!(NftBridge::collection_info(1).is_none());
+            System::assert_last_event(Event::CollectionDeregistered { collection_id: 1 }.into());
+
+            // A wrapped collection is in use while wrappers are held here
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 5, Some(2000), 9));
+            NFTOwners::<Test>::insert(5, 1, 2);
+            assert_noop!(
+                NftBridge::deregister_collection(RuntimeOrigin::root(), 5),
+                Error::<Test>::CollectionInUse
+            );
+        });
+    }
+    #[test]
+    fn first_inbound_item_registers_its_wrapped_collection() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            Permissionless::set(false);
+            AutoRegister::set(true);
+            let deliver = |from: u32, item_id: u32| {
+                receive(Delivery { from, collection_id: 5, item_id, ..Default::default() })
+            };
+            let created = || {
+                System::events()
+                    .into_iter()
+                    .filter(|record| {
+                        matches!(
+                            record.event,
+                            RuntimeEvent::NftBridge(Event::WrappedCollectionCreated { .. })
+                        )
+                    })
+                    .count()
+            };
+
+            // A refused item registers nothing
+            UntrustedSources::set(vec![3000]);
+            assert_eq!(deliver(3000, 1), Err(Error::<Test>::UntrustedSourceChain.into()));
+
+            assert_ok!(deliver(2000, 1));
+            System::assert_has_event(
+                Event::WrappedCollectionCreated { local: 5, remote_para: 2000, remote_collection: 5 }
+                    .into(),
+            );
+            let info = NftBridge::collection_info(5).unwrap();
+            assert_eq!(info.reserve, Some(2000));
+            assert_eq!(info.admin, NftBridge::escrow_account());
+            assert_eq!(info.mode, CollectionMode::Wrapped);
+
+            // The second item reuses it
+            assert_ok!(deliver(2000, 2));
+            assert_eq!(created(), 1);
+            assert_eq!(NftBridge::owner(5, 2), Some(2));
+        });
+    }
+
+    #[test]
+    fn colliding_inbound_item_is_remapped_and_translated_back() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            // A local item already holds id 7, the first candidate
+            NFTOwners::<Test>::insert(5, 7, 1);
+            ItemIdOverride::set(Some(7));
+
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                5,
+                1,
+                2000,
+                2,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+            ));
+            assert_eq!(NFTOwners::<Test>::get(5, 8), Some(2));
+            assert_eq!(ItemRemap::<Test>::get(5, 8), Some((2000, 1)));
+            assert_eq!(RemoteItemRemap::<Test>::get((2000, 5), 1), Some(8));
+            System::assert_has_event(
+                Event::ItemRemapped {
+                    collection_id: 5,
+                    item_id: 8,
+                    from_para_id: 2000,
+                    remote_item_id: 1,
+                }
+                .into(),
+            );
+
+            // Sent home, the wrapper is addressed by its id on the source chain
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(2),
+                5,
+                8,
+                2000,
+                vec![],
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None,
+            ));
+            let (_, message) = sent_xcm().pop().unwrap();
+            let ReserveAssetDeposited(assets) = &message.0[0] else { panic!("not a reserve transfer") };
+            assert_eq!(assets.inner()[0], NftBridge::nft_asset(5, 1).unwrap());
+
+            // And the source chain confirms it under that id too
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 5, 1));
+            assert!(!PendingTransfers::<Test>::contains_key(5, 8));
+        });
+    }
+
+    #[test]
+    fn nft_record_combines_every_field() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(3);
+            assert_eq!(NftBridge::nft_record(1, 1), None);
+
+            // A native item escrowed in an outbound transfer
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"native".to_vec(),
+                Some(b"ipfs://native".to_vec()),
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None,
+            ));
+            assert_eq!(
+                NftBridge::nft_record(1, 1),
+                Some(NftRecord {
+                    owner: 1,
+                    metadata: Some(b"native".to_vec()),
+                    metadata_uri: Some(b"ipfs://native".to_vec()),
+                    origin: NftOrigin::Native,
+                    status: Some(TransferStatus::InFlight),
+                    bridged_at: Some(3),
+                })
+            );
+
+            // A wrapper received from another chain
+            System::set_block_number(4);
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(3000),
+                5,
+                2,
+                3000,
+                2,
+                b"wrapped".to_vec(),
+                Some(b"ipfs://wrapped".to_vec()),
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+            ));
+            assert_eq!(
+                NftBridge::nft_record(5, 2),
+                Some(NftRecord {
+                    owner: 2,
+                    metadata: Some(b"wrapped".to_vec()),
+                    metadata_uri: Some(b"ipfs://wrapped".to_vec()),
+                    origin: NftOrigin::Wrapped { from_para_id: 3000 },
+                    status: None,
+                    bridged_at: Some(4),
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn locked_items_are_held_by_the_escrow_account() {
+        new_test_ext().execute_with(|| {
+            let escrow = NftBridge::escrow_account_for(2000);
+            assert_ok!(send(1, 1, 1));
+            assert_eq!(NftBridge::owner(1, 1), Some(escrow));
+            assert!(!NftBridge::is_owner(1, 1, &escrow));
+            assert_ok!(NftBridge::do_try_state());
+
+            // Confirmation clears the escrow entry
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert_eq!(NftBridge::owner(1, 1), None);
+
+            // Escrow holding an item nothing accounts for is flagged
+            NFTOwners::<Test>::insert(1, 2, escrow);
+            assert!(NftBridge::do_try_state().is_err());
+        });
+    }
+
+    #[test]
+    fn reconcile_escrow_flags_unaccounted_items() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let escrow = NftBridge::escrow_account_for(2000);
+            assert_ok!(send(1, 1, 1));
+            assert_ok!(send(1, 1, 2));
+            let mut escrowed = NftBridge::escrowed_for(2000, 10);
+            escrowed.sort();
+            assert_eq!(escrowed, vec![(1, 1), (1, 2)]);
+            assert_eq!(NftBridge::escrowed_for(2000, 1).len(), 1);
+
+            // An item slipped into escrow without a transfer
+            NFTOwners::<Test>::insert(2, 7, escrow);
+            assert_ok!(NftBridge::reconcile_escrow(RuntimeOrigin::signed(1), 2000, 10));
+            System::assert_has_event(
+                Event::EscrowDiscrepancy { collection_id: 2, item_id: 7, dest_para_id: 2000 }
+                    .into(),
+            );
+            System::assert_last_event(
+                Event::EscrowReconciled { dest_para_id: 2000, discrepancies: 1 }.into(),
+            );
+
+            // Once it is accounted for, the next full pass is clean
+            NFTOwners::<Test>::remove(2, 7);
+            assert_ok!(NftBridge::reconcile_escrow(RuntimeOrigin::signed(1), 2000, 10));
+            System::assert_last_event(
+                Event::EscrowReconciled { dest_para_id: 2000, discrepancies: 0 }.into(),
+            );
+        });
+    }
+
+    #[test]
+    fn staged_send_carries_only_the_metadata_hash() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            BridgeFee::set(10);
+            FeePerMetadataByte::set(2);
+            Balances::make_free_balance_be(&1, 10_000);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+            let metadata = vec![7; 1000];
+
+            // Classic send: the metadata is billed byte by byte
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                metadata.clone(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None,
+            ));
+            let (_, classic) = sent_xcm().pop().unwrap();
+
+            // Staged send: the deposit is held, then refunded when the item goes
+            assert_noop!(
+                NftBridge::stage_metadata(RuntimeOrigin::signed(2), 1, 2, metadata.clone(), None),
+                Error::<Test>::NotOwner
+            );
+            let before = Balances::free_balance(1);
+            assert_ok!(NftBridge::stage_metadata(
+                RuntimeOrigin::signed(1),
+                1,
+                2,
+                metadata.clone(),
+                None
+            ));
+            let metadata_hash = BlakeTwo256::hash(&metadata);
+            System::assert_last_event(
+                Event::MetadataStaged { collection_id: 1, item_id: 2, metadata_hash }.into(),
+            );
+            assert_eq!(Balances::free_balance(1), before - 1000);
+
+            assert_ok!(NftBridge::send_staged_nft(RuntimeOrigin::signed(1), 1, 2, 2000, None));
+            System::assert_has_event(
+                Event::NFTSent {
+                    collection_id: 1,
+                    item_id: 2,
+                    dest_para_id: 2000,
+                    fee: 10,
+                    delivery_fee: 0,
+                    canonical_id: NftBridge::canonical_id(1, 2),
+                }
+                .into(),
+            );
+            System::assert_last_event(
+                Event::StagedNftSent {
+                    collection_id: 1,
+                    item_id: 2,
+                    dest_para_id: 2000,
+                    metadata_hash,
+                }
+                .into(),
+            );
+            assert_eq!(Balances::free_balance(1), before - 10);
+            assert_eq!(NftBridge::nft_metadata(1, 2), Some(metadata));
+            assert!(NftBridge::staged_metadata(1, 2).is_none());
+
+            // The message never grows with the metadata, whichever flow sent it
+            let (_, staged) = sent_xcm().pop().unwrap();
+            assert_eq!(staged.encode().len(), classic.encode().len());
+        });
+    }
+    #[test]
+    fn expired_staged_metadata_can_be_purged() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            Balances::make_free_balance_be(&1, 100);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            let metadata = vec![1; 40];
+            assert_ok!(NftBridge::stage_metadata(RuntimeOrigin::signed(1), 1, 1, metadata, None));
+            assert_eq!(Balances::free_balance(1), 60);
+
+            System::set_block_number(20);
+            assert_noop!(
+                NftBridge::purge_staged_metadata(RuntimeOrigin::signed(3), 1, 1),
+                Error::<Test>::StagingNotExpired
+            );
+
+            System::set_block_number(21);
+            assert_ok!(NftBridge::purge_staged_metadata(RuntimeOrigin::signed(3), 1, 1));
+            System::assert_last_event(
+                Event::StagedMetadataPurged { collection_id: 1, item_id: 1 }.into(),
+            );
+            assert!(NftBridge::staged_metadata(1, 1).is_none());
+            assert_eq!(Balances::free_balance(1), 100);
+            assert_noop!(
+                NftBridge::send_staged_nft(RuntimeOrigin::signed(1), 1, 1, 2000, None),
+                Error::<Test>::StagedMetadataNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn deferred_metadata_is_fetched_from_the_origin_chain() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let metadata = b"full_metadata".to_vec();
+            let metadata_hash = BlakeTwo256::hash(&metadata);
+
+            // Origin side: item (3, 3) is abroad on 2001 and serves its metadata there only
+            ItemsAbroad::<Test>::insert(3, 3, 2001);
+            NFTMetadata::<Test>::insert(3, 3, metadata.clone());
+            assert_noop!(
+                NftBridge::serve_metadata(RuntimeOrigin::signed(2002), 3, 3),
+                Error::<Test>::OriginMismatch
+            );
+            assert_ok!(NftBridge::serve_metadata(RuntimeOrigin::signed(2001), 3, 3));
+            let crate::Call::metadata_served { metadata: served, .. } = last_bridge_call(2001)
+            else {
+                panic!("expected a metadata response")
+            };
+            assert_eq!(served, metadata);
+
+            // Holding side: a wrapper arrives from 2000 with only the hash
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                5,
+                1,
+                2000,
+                2,
+                vec![],
+                None,
+                None,
+                Some(metadata_hash),
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+            ));
+            assert_eq!(DeferredMetadata::<Test>::get(5, 1), Some(metadata_hash));
+
+            // Nothing was asked for yet
+            let respond = |metadata: Vec<u8>| {
+                NftBridge::metadata_served(RuntimeOrigin::signed(2000), 5, 1, metadata, None)
+            };
+            assert_noop!(respond(metadata.clone()), Error::<Test>::MetadataRequestNotFound);
+            assert_noop!(
+                NftBridge::request_metadata(RuntimeOrigin::signed(3), 5, 1),
+                Error::<Test>::NotOwner
+            );
+            assert_ok!(NftBridge::request_metadata(RuntimeOrigin::signed(2), 5, 1));
+            assert!(matches!(
+                last_bridge_call(2000),
+                crate::Call::serve_metadata { collection_id: 5, item_id: 1 }
+            ));
+            assert_noop!(
+                NftBridge::request_metadata(RuntimeOrigin::signed(2), 5, 1),
+                Error::<Test>::MetadataRequestPending
+            );
+
+            // A blob that doesn't match the hash is refused
+            assert_noop!(respond(b"forged".to_vec()), Error::<Test>::MetadataHashMismatch);
+            assert_ok!(respond(metadata.clone()));
+            assert_eq!(NftBridge::nft_metadata(5, 1), Some(metadata));
+            assert!(DeferredMetadata::<Test>::get(5, 1).is_none());
+            assert!(MetadataRequests::<Test>::get(5, 1).is_none());
+        });
+    }
+    #[test]
+    fn lapsed_metadata_request_rejects_late_responses() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let metadata = b"full_metadata".to_vec();
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                5,
+                1,
+                2000,
+                2,
+                vec![],
+                None,
+                None,
+                Some(BlakeTwo256::hash(&metadata)),
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+            ));
+            assert_ok!(NftBridge::request_metadata(RuntimeOrigin::signed(2), 5, 1));
+
+            System::set_block_number(11);
+            assert_noop!(
+                NftBridge::metadata_served(RuntimeOrigin::signed(2000), 5, 1, metadata, None),
+                Error::<Test>::MetadataRequestNotFound
+            );
+            // The owner may ask again
+            assert_ok!(NftBridge::request_metadata(RuntimeOrigin::signed(2), 5, 1));
+        });
+    }    // Receive wrapper (5, 1) from 2000 and open an assembly of `metadata` for it as transfer 9
+    fn open_assembly(metadata: &[u8]) -> Vec<MetadataChunkOf<Test>> {
+        ItemOrigin::<Test>::insert(5, 1, 2000);
+        NFTOwners::<Test>::insert(5, 1, 2);
+        let chunks = NftBridge::metadata_chunks(9, metadata).unwrap();
+        assert_ok!(NftBridge::metadata_chunk_header(
+            RuntimeOrigin::signed(2000),
+            9,
+            5,
+            1,
+            chunks.len() as u32,
+            BlakeTwo256::hash(metadata)
+        ));
+        chunks
+    }
+
+    #[test]
+    fn chunked_metadata_assembles_in_any_order() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let metadata = b"ten bytes!".to_vec();
+            let deliver = |chunk: &MetadataChunkOf<Test>| {
+                NftBridge::metadata_chunk(RuntimeOrigin::signed(2000), chunk.clone())
+            };
+
+            for order in [[0, 1, 2], [2, 0, 1]] {
+                NFTMetadata::<Test>::remove(5, 1);
+                let chunks = open_assembly(&metadata);
+                assert_eq!(chunks.len(), 3);
+                for index in order {
+                    assert!(NftBridge::nft_metadata(5, 1).is_none());
+                    assert_ok!(deliver(&chunks[index]));
+                }
+                assert_eq!(NftBridge::nft_metadata(5, 1), Some(metadata.clone()));
+                System::assert_last_event(
+                    Event::MetadataAssembled { collection_id: 5, item_id: 1, from_para_id: 2000 }
+                        .into(),
+                );
+                assert!(MetadataAssemblies::<Test>::get(2000, 9).is_none());
+                assert_eq!(PendingChunks::<Test>::iter().count(), 0);
+            }
+        });
+    }
+    #[test]
+    fn corrupted_chunk_is_refused() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let mut chunks = open_assembly(b"ten bytes!");
+            let mut data = chunks[1].data.to_vec();
+            data[0] ^= 0xff;
+            chunks[1].data = data.try_into().unwrap();
+            assert_noop!(
+                NftBridge::metadata_chunk(RuntimeOrigin::signed(2000), chunks[1].clone()),
+                Error::<Test>::ChunkHashMismatch
+            );
+
+            // A chunk that matches its own hash but not the announced blob fails the assembly
+            chunks[1].chunk_hash = BlakeTwo256::hash(&chunks[1].data);
+            for chunk in &chunks {
+                assert_ok!(NftBridge::metadata_chunk(RuntimeOrigin::signed(2000), chunk.clone()));
+            }
+            System::assert_last_event(
+                Event::MetadataAssemblyFailed { from_para_id: 2000, transfer_id: 9 }.into(),
+            );
+            assert!(NftBridge::nft_metadata(5, 1).is_none());
+        });
+    }
+    #[test]
+    fn half_complete_assembly_expires_and_is_purged() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let chunks = open_assembly(b"ten bytes!");
+            assert_ok!(NftBridge::metadata_chunk(RuntimeOrigin::signed(2000), chunks[0].clone()));
+            assert_noop!(
+                NftBridge::purge_metadata_assembly(RuntimeOrigin::signed(3), 2000, 9),
+                Error::<Test>::AssemblyNotExpired
+            );
+
+            System::set_block_number(11);
+            assert_noop!(
+                NftBridge::metadata_chunk(RuntimeOrigin::signed(2000), chunks[1].clone()),
+                Error::<Test>::MetadataAssemblyNotFound
+            );
+            assert_ok!(NftBridge::purge_metadata_assembly(RuntimeOrigin::signed(3), 2000, 9));
+            assert!(MetadataAssemblies::<Test>::get(2000, 9).is_none());
+            assert_eq!(PendingChunks::<Test>::iter().count(), 0);
+        });
+    }
+    #[test]
+    fn send_chunked_nft_streams_header_and_chunks() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            let metadata = vec![3; 30];
+            assert_ok!(NftBridge::send_chunked_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                metadata.clone(),
+                None
+            ));
+            System::assert_last_event(
+                Event::MetadataChunksSent {
+                    collection_id: 1,
+                    item_id: 1,
+                    transfer_id: 0,
+                    total: 8,
+                }
+                .into(),
+            );
+            assert_eq!(NftBridge::nft_metadata(1, 1), Some(metadata.clone()));
+
+            // Header plus eight chunks follow the transfer itself
+            let calls = sent_xcm()
+                .into_iter()
+                .filter(|(_, message)| matches!(message.0.last(), Some(Transact { .. })))
+                .count();
+            assert_eq!(calls, 9);
+            let crate::Call::metadata_chunk { chunk } = last_bridge_call(2000) else {
+                panic!("expected a chunk")
+            };
+            assert_eq!((chunk.index, chunk.total), (7, 8));
+
+            // Metadata beyond MaxMetadataChunks chunks is refused
+            NFTOwners::<Test>::insert(1, 2, 1);
+            assert_noop!(
+                NftBridge::send_chunked_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    2,
+                    2000,
+                    vec![3; 33],
+                    None
+                ),
+                Error::<Test>::MetadataTooLong
+            );
+        });
+    }
+
+    #[test]
+    fn nft_movements_go_through_the_asset_transactor() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            TransactorEnabled::set(true);
+            let location = |who: u64| AccountToLocation::convert(who);
+
+            // A received wrapper is deposited to its recipient
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                5,
+                1,
+                2000,
+                2,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+            ));
+            let asset = NftBridge::nft_asset(5, 1).unwrap();
+            assert_eq!(TransactorCalls::get(), vec![("deposit", asset.clone(), location(2))]);
+
+            // Sending withdraws it, cancelling deposits it back
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(2),
+                5,
+                1,
+                2000,
+                vec![],
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None,
+            ));
+            reject(5, 1);
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(2), 5, 1));
+            assert_eq!(
+                TransactorCalls::get(),
+                vec![
+                    ("deposit", asset.clone(), location(2)),
+                    ("withdraw", asset.clone(), location(2)),
+                    ("deposit", asset, location(2)),
+                ]
+            );
+            assert_eq!(NftBridge::owner(5, 1), Some(2));
+        });
+    }
+
+    #[test]
+    fn ownership_is_checked_against_the_nft_backend() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(Uniques::force_create(RuntimeOrigin::root(), 7, 1, true));
+            assert_ok!(Uniques::mint(RuntimeOrigin::signed(1), 7, 1, 1));
+            assert_ok!(NftBridge::import_nft(RuntimeOrigin::signed(9), 7, 1));
+            assert_eq!(NftBridge::owner(7, 1), Some(1));
+
+            // The item changes hands in the NFT pallet, leaving the bridge's record stale
+            assert_ok!(Uniques::transfer(RuntimeOrigin::signed(1), 7, 1, 2));
+            let send = |who: u64| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(who),
+                    7,
+                    1,
+                    2000,
+                    vec![],
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            assert_noop!(send(1), Error::<Test>::OwnershipOutOfSync);
+            assert_noop!(send(2), Error::<Test>::NotOwner);
+
+            // Importing refreshes the record and the new owner can send
+            assert_ok!(NftBridge::import_nft(RuntimeOrigin::signed(9), 7, 1));
+            System::assert_last_event(
+                Event::NftImported { collection_id: 7, item_id: 1, owner: 2 }.into(),
+            );
+            assert_ok!(send(2));
+
+            // Items in flight keep the bridge's view
+            assert_noop!(
+                NftBridge::import_nft(RuntimeOrigin::signed(9), 7, 1),
+                Error::<Test>::TransferAlreadyPending
+            );
+        });
+    }
+
+    #[test]
+    fn items_unknown_to_the_backend_fall_back_to_bridge_records() {
+        new_test_ext().execute_with(|| {
+            NFTOwners::<Test>::insert(3, 3, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                3,
+                3,
+                2000,
+                vec![],
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None,
+            ));
+            assert_noop!(
+                NftBridge::import_nft(RuntimeOrigin::signed(9), 4, 4),
+                Error::<Test>::NFTNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn check_consistency_reports_drift_page_by_page() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(Uniques::force_create(RuntimeOrigin::root(), 7, 1, true));
+            for item_id in 1..=3 {
+                assert_ok!(Uniques::mint(RuntimeOrigin::signed(1), 7, item_id, 1));
+                NFTOwners::<Test>::insert(7, item_id, 1);
+            }
+            // Item 2 moved outside the bridge
+            assert_ok!(Uniques::transfer(RuntimeOrigin::signed(1), 7, 2, 5));
+
+            // Returns the mismatches reported by one page and its cursor
+            let page = |start_after: Option<u32>| {
+                System::reset_events();
+                assert_ok!(NftBridge::check_consistency(
+                    RuntimeOrigin::signed(9),
+                    7,
+                    start_after,
+                    2
+                ));
+                let mut mismatched = vec![];
+                let mut next = None;
+                for record in System::events() {
+                    match record.event {
+                        RuntimeEvent::NftBridge(Event::OwnershipMismatch {
+                            item_id,
+                            bridge_owner,
+                            backend_owner,
+                            ..
+                        }) => mismatched.push((item_id, bridge_owner, backend_owner)),
+                        RuntimeEvent::NftBridge(Event::ConsistencyChecked {
+                            mismatches,
+                            next: cursor,
+                            ..
+                        }) => {
+                            assert_eq!(mismatches as usize, mismatched.len());
+                            next = cursor;
+                        },
+                        _ => {},
+                    }
+                }
+                (mismatched, next)
+            };
+
+            let (mut mismatched, next) = page(None);
+            assert!(next.is_some());
+            let (rest, next) = page(next);
+            assert_eq!(next, None);
+            mismatched.extend(rest);
+            assert_eq!(mismatched, vec![(2, 1, 5)]);
+            // Nothing was repaired
+            assert_eq!(NftBridge::owner(7, 2), Some(1));
+        });
+    }
+
+    #[test]
+    fn received_wrappers_are_minted_into_the_nft_backend() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            BackendMints::set(true);
+            assert_ok!(Uniques::force_create(RuntimeOrigin::root(), 5, 1, true));
+            let deliver = |collection_id: u32, item_id: u32| {
+                receive(Delivery { collection_id, item_id, ..Default::default() })
+            };
+
+            assert_ok!(deliver(5, 1));
+            assert_eq!(<Uniques as nonfungibles::Inspect<u64>>::owner(&5, &1), Some(2));
+            assert_eq!(NftBridge::owner(5, 1), Some(2));
+            assert_eq!(ItemOrigin::<Test>::get(5, 1), Some(2000));
+
+            // A collection the backend doesn't have, or an item it already has, rolls back
+            assert_eq!(deliver(6, 1), Err(Error::<Test>::BackendMintFailed.into()));
+            assert_ok!(Uniques::mint(RuntimeOrigin::signed(1), 5, 2, 1));
+            assert_eq!(deliver(5, 2), Err(Error::<Test>::BackendMintFailed.into()));
+        });
+    }
+
+    #[test]
+    fn attributes_written_to_the_backend_are_limited() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            BackendMints::set(true);
+            assert_ok!(Uniques::force_create(RuntimeOrigin::root(), 5, 1, true));
+            let deliver = |item_id: u32, metadata_len: usize, metadata_uri: Option<Vec<u8>>| {
+                receive(Delivery {
+                    collection_id: 5,
+                    item_id,
+                    metadata: vec![1; metadata_len],
+                    metadata_uri,
+                    ..Default::default()
+                })
+            };
+
+            // Values of exactly MaxAttributeLen bytes are written, one more is refused
+            MaxAttributeLen::set(16);
+            assert_eq!(deliver(1, 17, None), Err(Error::<Test>::AttributeTooLong.into()));
+            assert_ok!(deliver(1, 16, None));
+
+            // Metadata and URI make two attributes
+            MaxAttributes::set(1);
+            assert_eq!(
+                deliver(2, 1, Some(b"ipfs".to_vec())),
+                Err(Error::<Test>::TooManyAttributes.into())
+            );
+            assert_ok!(deliver(2, 1, None));
+            MaxAttributes::set(2);
+            assert_ok!(deliver(3, 1, Some(b"ipfs".to_vec())));
+        });
+    }
+
+    #[test]
+    fn sends_with_attributes_the_destination_refuses_fail_before_locking() {
+        new_test_ext().execute_with(|| {
+            for item_id in 1..=3 {
+                NFTOwners::<Test>::insert(1, item_id, 1);
+            }
+            let send = |item_id: u32, metadata_len: usize, metadata_uri: Option<Vec<u8>>| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item_id,
+                    2000,
+                    vec![1; metadata_len],
+                    metadata_uri,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+
+            // The destination writes values of exactly MaxAttributeLen bytes, not one more
+            MaxAttributeLen::set(16);
+            assert_noop!(send(1, 17, None), Error::<Test>::AttributeTooLong);
+            assert_noop!(send(1, 1, Some(vec![1; 17])), Error::<Test>::AttributeTooLong);
+            assert_ok!(send(1, 16, Some(vec![1; 16])));
+
+            // Metadata and URI make two attributes
+            MaxAttributes::set(1);
+            assert_noop!(send(2, 1, Some(b"ipfs".to_vec())), Error::<Test>::TooManyAttributes);
+            assert_ok!(send(2, 1, None));
+            MaxAttributes::set(2);
+            assert_ok!(send(3, 1, Some(b"ipfs".to_vec())));
+        });
+    }
+
+    type TestNftTransactor = NftTransactor<Test, LocationToAccount>;
+
+    fn state_root() -> Vec<u8> {
+        sp_io::storage::root(sp_runtime::StateVersion::V1)
+    }
+
+    #[test]
+    fn degenerate_inbound_assets_are_rejected() {
+        use xcm_executor::traits::{Error as MatchError, TransactAsset};
+        new_test_ext().execute_with(|| {
+            let who = AccountToLocation::convert(2);
+            let valid = NftBridge::nft_asset(5, 1).unwrap();
+            let Concrete(location) = valid.id else { panic!("expected a concrete asset") };
+            let with_fun = |fun: Fungibility| MultiAsset { id: Concrete(location), fun };
+            let at =
+                |location: MultiLocation| MultiAsset { id: Concrete(location), ..valid.clone() };
+            let pallet = location.interior.first().cloned().unwrap();
+            let context = XcmContext { origin: None, message_hash: [0; 32], topic: None };
+            let undefined = NonFungible(AssetInstance::Undefined);
+            let array = NonFungible(AssetInstance::Array4([0; 4]));
+            let general_key = GeneralKey { length: 1, data: [0; 32] };
+
+            let cases = vec![
+                (with_fun(undefined), MatchError::InstanceConversionFailed),
+                (with_fun(array), MatchError::InstanceConversionFailed),
+                (with_fun(Fungible(1)), MatchError::AssetNotHandled),
+                (
+                    MultiAsset { id: Abstract([0; 32]), ..valid.clone() },
+                    MatchError::AssetNotHandled,
+                ),
+                (at(MultiLocation::new(1, location.interior)), MatchError::AssetNotHandled),
+                (at(MultiLocation::here()), MatchError::AssetNotHandled),
+                (at(MultiLocation::new(0, X1(pallet))), MatchError::AssetNotHandled),
+                (
+                    at(MultiLocation::new(0, X2(PalletInstance(250), GeneralIndex(5)))),
+                    MatchError::AssetNotHandled,
+                ),
+                (at(MultiLocation::new(0, X2(pallet, general_key))), MatchError::AssetNotHandled),
+                (
+                    at(location.pushed_with_interior(GeneralIndex(1)).unwrap()),
+                    MatchError::AssetNotHandled,
+                ),
+                // Folded bytes that decode to no collection id
+                (
+                    at(MultiLocation::new(0, X2(pallet, GeneralIndex(u128::MAX)))),
+                    MatchError::AssetIdConversionFailed,
+                ),
+                (
+                    with_fun(NonFungible(AssetInstance::Index(u128::MAX))),
+                    MatchError::InstanceConversionFailed,
+                ),
+            ];
+
+            let root = state_root();
+            for (asset, error) in cases {
+                assert_eq!(NftBridge::item_of_asset(&asset), Err(error), "{:?}", asset);
+                assert_eq!(
+                    TestNftTransactor::deposit_asset(&asset, &who, &context),
+                    Err(XcmError::AssetNotFound)
+                );
+                assert!(TestNftTransactor::withdraw_asset(&asset, &who, None).is_err());
+            }
+            assert_eq!(state_root(), root);
+
+            // The well-formed asset round-trips and moves
+            assert_eq!(NftBridge::item_of_asset(&valid), Ok((5, 1)));
+            assert_ok!(TestNftTransactor::deposit_asset(&valid, &who, &context));
+            assert_eq!(NftBridge::owner(5, 1), Some(2));
+            assert_ok!(TestNftTransactor::withdraw_asset(&valid, &who, None));
+            assert_eq!(NftBridge::owner(5, 1), None);
+        });
+    }
+
+    #[test]
+    fn random_assets_never_panic_or_write_unless_matched() {
+        use xcm_executor::traits::TransactAsset;
+        new_test_ext().execute_with(|| {
+            let who = AccountToLocation::convert(2);
+            let pallet = NftBridge::nft_asset_prefix().unwrap().first().cloned().unwrap();
+            let context = XcmContext { origin: None, message_hash: [0; 32], topic: None };
+            // xorshift, so failures reproduce
+            let mut seed = 0x2545_f491_4f6c_dd1du64;
+            let mut next = move || {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            };
+
+            for _ in 0..2_000 {
+                let wide = ((next() as u128) << 64) | next() as u128;
+                let junction = match next() % 4 {
+                    0 => pallet,
+                    1 => GeneralIndex(wide),
+                    2 => PalletInstance(next() as u8),
+                    _ => GeneralKey { length: (next() % 40) as u8, data: [next() as u8; 32] },
+                };
+                let interior = match next() % 4 {
+                    0 => Here,
+                    1 => X1(junction),
+                    2 => X2(pallet, junction),
+                    _ => X3(pallet, junction, GeneralIndex(wide)),
+                };
+                let id = match next() % 5 {
+                    0 => Abstract([next() as u8; 32]),
+                    _ => Concrete(MultiLocation::new((next() % 3) as u8, interior)),
+                };
+                let fun = match next() % 5 {
+                    0 => Fungible(wide),
+                    1 => NonFungible(AssetInstance::Undefined),
+                    2 => NonFungible(AssetInstance::Array8(next().to_le_bytes())),
+                    3 => NonFungible(AssetInstance::Index(next() as u128)),
+                    _ => NonFungible(AssetInstance::Index(wide)),
+                };
+                let asset = MultiAsset { id, fun };
+
+                let root = state_root();
+                let matched = NftBridge::item_of_asset(&asset).is_ok();
+                let _ = TestNftTransactor::deposit_asset(&asset, &who, &context);
+                let _ = TestNftTransactor::withdraw_asset(&asset, &who, None);
+                if !matched {
+                    assert_eq!(state_root(), root, "{:?}", asset);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn nft_asset_wire_format_is_stable() {
+        use xcm_executor::traits::Convert as XcmConvert;
+        new_test_ext().execute_with(|| {
+            NftAssetPrefix::set(X1(PalletInstance(50)));
+            // (collection, item, GeneralIndex, Index, SCALE encoding of the asset)
+            let golden: [(u32, u32, u128, u128, &[u8]); 2] = [
+                (
+                    5,
+                    1,
+                    83_886_080,
+                    16_777_216,
+                    &[
+                        0x00, 0x00, 0x02, 0x04, 0x32, 0x05, 0x02, 0x00,
+                        0x00, 0x14, 0x01, 0x01, 0x02, 0x00, 0x00, 0x04,
+                    ],
+                ),
+                (
+                    0x0102_0304,
+                    300,
+                    67_305_985,
+                    738_263_040,
+                    &[
+                        0x00, 0x00, 0x02, 0x04, 0x32, 0x05, 0x06, 0x08,
+                        0x0c, 0x10, 0x01, 0x01, 0x02, 0x00, 0x04, 0xb0,
+                    ],
+                ),
+            ];
+            for (collection_id, item_id, index, instance, encoded) in golden {
+                let asset = <NftAssetConverter<Test> as Convert<_, Option<MultiAsset>>>::convert(
+                    (collection_id, item_id),
+                )
+                .unwrap();
+                assert_eq!(
+                    asset,
+                    MultiAsset {
+                        id: Concrete(MultiLocation::new(
+                            0,
+                            X2(PalletInstance(50), GeneralIndex(index))
+                        )),
+                        fun: NonFungible(AssetInstance::Index(instance)),
+                    }
+                );
+                assert_eq!(asset.encode(), encoded);
+                assert_eq!(NftBridge::nft_asset(collection_id, item_id), Ok(asset.clone()));
+                assert_eq!(
+                    <NftAssetConverter<Test> as XcmConvert<_, _>>::convert_ref(&asset),
+                    Ok((collection_id, item_id))
+                );
+                assert_eq!(
+                    <NftAssetConverter<Test> as XcmConvert<MultiAsset, _>>::reverse_ref((
+                        collection_id,
+                        item_id
+                    )),
+                    Ok(asset)
+                );
+            }
+        });
+    }
+
+    // The call bytes of the `Transact` in `message`
+    fn transact_call(message: &Xcm<()>) -> Vec<u8> {
+        message
+            .0
+            .iter()
+            .find_map(|instruction| match instruction {
+                Transact { call, .. } => Some(call.clone().into_encoded()),
+                _ => None,
+            })
+            .expect("expected a Transact")
+    }
+
+    #[test]
+    fn transact_protocol_carries_a_bridge_message() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig {
+                    protocol: TransferProtocol::Transact,
+                    remote_bridge_pallet_index: 77,
+                    remote_receive_call_index: 3,
+                    ..Default::default()
+                })
+            ));
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                Some(b"ipfs://item".to_vec()),
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None,
+            ));
+
+            let (_, message) = sent_xcm().pop().unwrap();
+            assert!(matches!(
+                message.0.as_slice(),
+                [WithdrawAsset(_), BuyExecution { .. }, Transact { .. }]
+            ));
+            let call = transact_call(&message);
+            assert_eq!(&call[..2], &[77, 3]);
+            assert_eq!(
+                BridgeMessage::<u64, u32, u32>::decode(&mut &call[2..]).unwrap(),
+                BridgeMessage {
+                    collection_id: 1,
+                    item_id: 1,
+                    recipient: 1,
+                    metadata: b"test_metadata".to_vec(),
+                    metadata_uris: vec![(MediaKind::Primary, b"ipfs://item".to_vec())],
+                    sent_at: UnixNow::get(),
+                }
+            );
+            assert!(PendingTransfers::<Test>::contains_key(1, 1));
+
+            // Attached calls have nowhere to go
+            NFTOwners::<Test>::insert(1, 2, 1);
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    2,
+                    2000,
+                    vec![],
+                    None,
+                    Some(vec![0].try_into().unwrap()),
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                ),
+                Error::<Test>::ProgramBuildFailed
+            );
+        });
+    }
+
+    #[test]
+    fn remote_call_indices_come_from_the_destination_config() {
+        new_test_ext().execute_with(|| {
+            let config = |pallet: u8, receive: u8, confirm: u8| DestinationConfig {
+                protocol: TransferProtocol::Transact,
+                remote_bridge_pallet_index: pallet,
+                remote_receive_call_index: receive,
+                remote_confirm_call_index: confirm,
+                ..Default::default()
+            };
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(config(200, 17, 18))
+            ));
+
+            // A delivery from the partner is confirmed through its own confirmation call
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                5,
+                1,
+                2000,
+                2,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+            ));
+            let (dest, confirmation) = sent_xcm().pop().unwrap();
+            assert_eq!(dest, MultiLocation::new(1, X1(Parachain(2000))));
+            assert_eq!(transact_call(&confirmation), (200u8, 18u8, 5u32, 1u32).encode());
+
+            // A transfer whose send fails keeps the indices it was built with
+            NFTOwners::<Test>::insert(1, 1, 1);
+            FailSends::set(1);
+            let send = |item_id: u32| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item_id,
+                    2000,
+                    vec![],
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            assert_ok!(send(1));
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(config(201, 40, 41))
+            ));
+            NFTOwners::<Test>::insert(1, 2, 1);
+            assert_ok!(send(2));
+            assert_eq!(&transact_call(&sent_xcm().pop().unwrap().1)[..2], &[201, 40]);
+
+            System::set_block_number(100);
+            NftBridge::retry_failed_sends(100, Weight::MAX);
+            assert_eq!(&transact_call(&sent_xcm().pop().unwrap().1)[..2], &[200, 17]);
+        });
+    }
+
+    #[test]
+    fn bridge_callbacks_use_the_partners_pallet_index() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let own_index =
+                <Test as frame_system::Config>::PalletInfo::index::<NftBridge>().unwrap() as u8;
+            let unlock = crate::Call::<Test>::unlock_bounced {
+                collection_id: 1,
+                item_id: 1,
+                owner: 2,
+                reason: RejectionReason::AdminRejected,
+            };
+
+            // A partner without a config runs the pallet at our index
+            assert_ok!(NftBridge::do_bounce_inbound(1, 1, 2000, 2, RejectionReason::AdminRejected));
+            let expected: Vec<u8> = [own_index].into_iter().chain(unlock.encode()).collect();
+            assert_eq!(transact_call(&sent_xcm().pop().unwrap().1), expected);
+
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig { remote_bridge_pallet_index: 200, ..Default::default() })
+            ));
+            assert_ok!(NftBridge::do_bounce_inbound(1, 1, 2000, 2, RejectionReason::AdminRejected));
+            let expected: Vec<u8> = [200].into_iter().chain(unlock.encode()).collect();
+            assert_eq!(transact_call(&sent_xcm().pop().unwrap().1), expected);
+
+            // Paid reports of refused deliveries are addressed the same way
+            assert_ok!(NftBridge::set_inbound_opt_out(RuntimeOrigin::signed(2), true));
+            assert_eq!(
+                receive(Delivery { transfer_id: Some(9), ..Default::default() }),
+                Err(Error::<Test>::RecipientOptedOut.into())
+            );
+            let (_, report) = sent_xcm().pop().unwrap();
+            assert!(matches!(report.0[0], WithdrawAsset(_)));
+            let call = transact_call(&report);
+            assert_eq!(call[0], 200);
+            assert_eq!(
+                crate::Call::<Test>::decode(&mut &call[1..]).unwrap(),
+                crate::Call::<Test>::report_rejection {
+                    transfer_id: 9,
+                    code: RejectionCode::RecipientOptedOut
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn transact_payload_follows_the_partner_version() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            for para_id in [2000, 2001] {
+                assert_ok!(NftBridge::set_destination_config(
+                    RuntimeOrigin::root(),
+                    para_id,
+                    Some(DestinationConfig {
+                        protocol: TransferProtocol::Transact,
+                        remote_bridge_pallet_index: 77,
+                        remote_receive_call_index: 3,
+                        ..Default::default()
+                    })
+                ));
+            }
+            // 2000 is pinned to v1 by governance, 2001 announces v2 itself
+            assert_ok!(NftBridge::set_partner_version(RuntimeOrigin::root(), 2000, Some(1)));
+            assert_ok!(NftBridge::advertise_version(RuntimeOrigin::signed(2001), 2));
+            System::assert_last_event(
+                Event::PartnerVersionSet { para_id: 2001, version: Some(2) }.into(),
+            );
+
+            let send = |item_id: u32, dest: u32, metadata: Vec<u8>| {
+                NFTOwners::<Test>::insert(1, item_id, 1);
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item_id,
+                    dest,
+                    metadata,
+                    Some(b"ipfs://item".to_vec()),
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            assert_ok!(send(1, 2000, b"meta".to_vec()));
+            let v1 = transact_call(&sent_xcm().pop().unwrap().1);
+            assert_ok!(send(2, 2001, b"meta".to_vec()));
+            let v2 = transact_call(&sent_xcm().pop().unwrap().1);
+
+            // The v1 partner gets the same transfer without the URI
+            assert_eq!(
+                BridgeMessageV1::<u64, u32, u32>::decode(&mut &v1[2..]).unwrap(),
+                BridgeMessageV1 {
+                    collection_id: 1,
+                    item_id: 1,
+                    recipient: 1,
+                    metadata: b"meta".to_vec(),
+                }
+            );
+            assert_eq!(
+                BridgeMessageV2::<u64, u32, u32>::decode(&mut &v2[2..]).unwrap(),
+                BridgeMessageV2 {
+                    collection_id: 1,
+                    item_id: 2,
+                    recipient: 1,
+                    metadata: b"meta".to_vec(),
+                    metadata_uri: Some(b"ipfs://item".to_vec()),
+                }
+            );
+            assert_eq!(v2.len() - v1.len(), b"ipfs://item".len() + 2);
+
+            // An item known only by its URI can't be sent to the v1 partner
+            assert_noop!(send(3, 2000, vec![]), Error::<Test>::IncompatiblePartnerVersion);
+            assert_ok!(send(3, 2001, vec![]));
+        });
+    }
+
+    #[test]
+    fn migration_to_v1_converts_pending_destinations() {
+        use frame_support::{
+            storage::unhashed,
+            traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        };
+        use migrations::v1::{MigrateToV1, OldTransferInfo};
+        new_test_ext().execute_with(|| {
+            StorageVersion::new(0).put::<NftBridge>();
+            let old = |dest: xcm::v2::MultiLocation| OldTransferInfo::<u64, u64> {
+                id: 7,
+                sender: 1,
+                dest,
+                sent_at: 3,
+                status: TransferStatus::InFlight,
+                retry_count: 0,
+            };
+            let sibling = xcm::v2::MultiLocation::new(
+                1,
+                xcm::v2::Junctions::X1(xcm::v2::Junction::Parachain(2000)),
+            );
+            // Named networks have no v3 equivalent
+            let named = xcm::v2::MultiLocation::new(
+                1,
+                xcm::v2::Junctions::X1(xcm::v2::Junction::AccountId32 {
+                    network: xcm::v2::NetworkId::Named(b"net".to_vec().try_into().unwrap()),
+                    id: [0; 32],
+                }),
+            );
+            let seeds = [
+                ((1, 1), old(sibling).encode()),
+                ((1, 2), old(named).encode()),
+                ((1, 3), vec![1, 2, 3]),
+            ];
+            for ((collection_id, item_id), raw) in &seeds {
+                let key = PendingTransfers::<Test>::hashed_key_for(collection_id, item_id);
+                unhashed::put_raw(&key, raw);
+            }
+
+            MigrateToV1::<Test>::on_runtime_upgrade();
+
+            assert_eq!(
+                PendingTransfers::<Test>::get(1, 1).unwrap().dest,
+                MultiLocation::new(1, X1(Parachain(2000)))
+            );
+            // Unconvertible entries are left for governance
+            for ((collection_id, item_id), raw) in &seeds[1..] {
+                let key = PendingTransfers::<Test>::hashed_key_for(collection_id, item_id);
+                assert_eq!(unhashed::get_raw(&key).as_ref(), Some(raw));
+            }
+            assert_eq!(NftBridge::on_chain_storage_version(), 1);
+
+            // Running it again changes nothing
+            assert_eq!(
+                MigrateToV1::<Test>::on_runtime_upgrade(),
+                <Test as frame_system::Config>::DbWeight::get().reads(1)
+            );
+        });
+    }
+
+    #[test]
+    fn kill_pending_refunds_the_nft() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None
+            ));
+            let transfer = NftBridge::pending_transfer(1, 1).unwrap();
+            let reason_hash = H256::repeat_byte(7);
+
+            assert_noop!(
+                NftBridge::kill_pending(RuntimeOrigin::signed(1), 1, 1, Some(2), reason_hash),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(NftBridge::kill_pending(RuntimeOrigin::root(), 1, 1, Some(2), reason_hash));
+
+            assert!(NftBridge::pending_transfer(1, 1).is_none());
+            assert_eq!(NftBridge::owner(1, 1), Some(2));
+            assert_eq!(InFlightCount::<Test>::get(2000), 0);
+            assert_eq!(NFTMetadata::<Test>::get(1, 1), Some(b"test_metadata".to_vec()));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::PendingKilled {
+                collection_id: 1,
+                item_id: 1,
+                transfer_id: Some(transfer.id),
+                refund_to: Some(2),
+                reason_hash,
+            }));
+        });
+    }
+
+    #[test]
+    fn kill_pending_removes_undecodable_record() {
+        use frame_support::storage::unhashed;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig::default())
+            ));
+            let key = PendingTransfers::<Test>::hashed_key_for(1, 1);
+            unhashed::put_raw(&key, &[1, 2, 3]);
+            PendingByDestination::<Test>::insert(2000, (1, 1), ());
+            BridgedAt::<Test>::insert(1, 1, 1);
+            NFTMetadata::<Test>::insert(1, 1, b"test_metadata".to_vec());
+            assert!(PendingTransfers::<Test>::try_get(1, 1).is_err());
+
+            let reason_hash = H256::repeat_byte(7);
+            assert_ok!(NftBridge::kill_pending(RuntimeOrigin::root(), 1, 1, None, reason_hash));
+
+            assert!(!unhashed::exists(&key));
+            assert!(!PendingByDestination::<Test>::contains_key(2000, (1, 1)));
+            assert!(!BridgedAt::<Test>::contains_key(1, 1));
+            assert!(NFTMetadata::<Test>::get(1, 1).is_none());
+            assert!(NftBridge::owner(1, 1).is_none());
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::PendingKilled {
+                collection_id: 1,
+                item_id: 1,
+                transfer_id: None,
+                refund_to: None,
+                reason_hash,
+            }));
+
+            assert_noop!(
+                NftBridge::kill_pending(RuntimeOrigin::root(), 1, 1, None, reason_hash),
+                Error::<Test>::NFTNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn kill_pending_releases_counters_of_a_truncated_record() {
+        use frame_support::storage::unhashed;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(send(1, 1, 1));
+            let transfer = NftBridge::pending_transfer(1, 1).unwrap();
+            assert_eq!(PendingPerAccount::<Test>::get(1), 1);
+            assert_eq!(InFlightCount::<Test>::get(2000), 1);
+
+            // Only the id, sender and destination survive
+            let mut raw = (transfer.id, transfer.sender, transfer.dest).encode();
+            raw.push(0xff);
+            let key = PendingTransfers::<Test>::hashed_key_for(1, 1);
+            unhashed::put_raw(&key, &raw);
+            assert!(PendingTransfers::<Test>::try_get(1, 1).is_err());
+
+            let reason_hash = H256::repeat_byte(7);
+            assert_ok!(NftBridge::kill_pending(RuntimeOrigin::root(), 1, 1, Some(1), reason_hash));
+
+            assert!(!unhashed::exists(&key));
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+            assert_eq!(PendingPerAccount::<Test>::get(1), 0);
+            assert_eq!(InFlightCount::<Test>::get(2000), 0);
+            assert!(!PendingByDestination::<Test>::contains_key(2000, (1, 1)));
+            assert!(!BridgedAt::<Test>::contains_key(1, 1));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::PendingKilled {
+                collection_id: 1,
+                item_id: 1,
+                transfer_id: Some(transfer.id),
+                refund_to: Some(1),
+                reason_hash,
+            }));
+        });
+    }
+
+    #[test]
+    fn lowered_metadata_limits_apply_to_new_payloads_only() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let limits = MetadataSizeLimits { max_metadata: 16, max_uri: 8 };
+            assert_eq!(
+                NftBridge::metadata_limits(),
+                MetadataSizeLimits { max_metadata: 1024, max_uri: 256 }
+            );
+            assert_noop!(
+                NftBridge::set_metadata_limits(
+                    RuntimeOrigin::root(),
+                    MetadataSizeLimits { max_metadata: 1025, max_uri: 8 }
+                ),
+                Error::<Test>::MetadataLimitAboveCeiling
+            );
+            assert_noop!(
+                NftBridge::set_metadata_limits(RuntimeOrigin::signed(1), limits),
+                sp_runtime::DispatchError::BadOrigin
+            );
+
+            let stored = vec![7u8; 32];
+            NFTMetadata::<Test>::insert(1, 1, stored.clone());
+            assert_ok!(NftBridge::set_metadata_limits(RuntimeOrigin::root(), limits));
+            System::assert_last_event(RuntimeEvent::NftBridge(
+                crate::Event::MetadataLimitsUpdated { limits },
+            ));
+            assert_eq!(NFTMetadata::<Test>::get(1, 1), Some(stored));
+
+            // 32 bytes passed the initial limit and are now refused both ways
+            NFTOwners::<Test>::insert(1, 2, 1);
+            let send = |metadata: Vec<u8>, uri: Option<Vec<u8>>| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    2,
+                    2000,
+                    metadata,
+                    uri,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            assert_noop!(send(vec![7; 32], None), Error::<Test>::MetadataTooLong);
+            assert_noop!(send(vec![7; 16], Some(vec![1; 9])), Error::<Test>::MetadataTooLong);
+            let delivery =
+                Delivery { item_id: 3, owner: 1, metadata: vec![7; 32], ..Default::default() };
+            assert_eq!(receive(delivery), Err(Error::<Test>::MetadataTooLong.into()));
+            assert_ok!(send(vec![7; 16], Some(vec![1; 8])));
+        });
+    }
+
+    #[test]
+    fn destination_metadata_cap_fails_before_locking() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig { max_metadata_len: Some(64), ..Default::default() })
+            ));
+            NFTOwners::<Test>::insert(1, 1, 1);
+            let send = |dest_para_id: u32, metadata: Vec<u8>| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    1,
+                    dest_para_id,
+                    metadata,
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+
+            assert_noop!(send(2000, vec![7; 100]), Error::<Test>::MetadataTooLongForDestination);
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+            assert!(sent_xcm().is_empty());
+
+            // Bundled items are held to the same cap, before anything is charged or locked
+            let mut items = batch(&[(1, 1)]);
+            items[0].metadata = vec![7; 100];
+            assert_noop!(
+                NftBridge::send_nfts(
+                    RuntimeOrigin::signed(1),
+                    items,
+                    2000,
+                    BatchMode::Bundle,
+                    None
+                ),
+                Error::<Test>::MetadataTooLongForDestination
+            );
+            assert_noop!(
+                NftBridge::validate_send(1, 1, 1, 2000, 100, 0),
+                Error::<Test>::MetadataTooLongForDestination
+            );
+
+            // The dry run reports the effective limit of each destination
+            let quote = |dest_para_id: u32, metadata_len: u32| {
+                NftBridge::validate_send(1, 1, 1, dest_para_id, metadata_len, 0).unwrap()
+            };
+            assert_eq!(quote(2000, 64).max_metadata_len, 64);
+            assert_eq!(quote(2001, 100).max_metadata_len, 1024);
+            assert_eq!(NftBridge::estimate_send_fee(1, 1, 2000, 0).unwrap().max_metadata_len, 64);
+
+            assert_ok!(send(2001, vec![7; 100]));
+        });
+    }
+
+    #[test]
+    fn typed_uris_round_trip_through_the_bridge() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig {
+                    protocol: TransferProtocol::Transact,
+                    remote_bridge_pallet_index: 77,
+                    remote_receive_call_index: 3,
+                    ..Default::default()
+                })
+            ));
+            NFTOwners::<Test>::insert(1, 1, 1);
+            let extra = vec![
+                (MediaKind::Image, b"ipfs://image".to_vec()),
+                (MediaKind::Animation, b"ipfs://animation".to_vec()),
+            ];
+            let send = |extra: ItemUris| {
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    1,
+                    2000,
+                    b"meta".to_vec(),
+                    Some(b"ipfs://primary".to_vec()),
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    extra,
+                    None,
+                )
+            };
+            let duplicate = vec![(MediaKind::Primary, b"ipfs://other".to_vec())];
+            assert_noop!(send(duplicate), Error::<Test>::DuplicateMediaKind);
+            assert_ok!(send(extra.clone()));
+
+            // The payload carries all three URIs, the primary one first
+            let call = transact_call(&sent_xcm().pop().unwrap().1);
+            let message = BridgeMessage::<u64, u32, u32>::decode(&mut &call[2..]).unwrap();
+            let mut expected = vec![(MediaKind::Primary, b"ipfs://primary".to_vec())];
+            expected.extend(extra);
+            assert_eq!(message.metadata_uris, expected);
+
+            // Delivered back as a typed list only
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                message.collection_id,
+                message.item_id,
+                2000,
+                message.recipient,
+                message.metadata,
+                None,
+                None,
+                None,
+                None,
+                message.metadata_uris,
+                None,
+                None,
+                None,
+                None,
+            ));
+            let stored: ItemUris = NftBridge::metadata_uris(1, 1)
+                .unwrap()
+                .into_iter()
+                .map(|(kind, uri)| (kind, uri.into_inner()))
+                .collect();
+            assert_eq!(stored, expected);
+            assert_eq!(NftBridge::nft_metadata_uri(1, 1), Some(b"ipfs://primary".to_vec()));
+        });
+    }
+
+    #[test]
+    fn migration_to_v2_moves_single_uris() {
+        use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        use migrations::v2::{MigrateToV2, NFTMetadataUri};
+        new_test_ext().execute_with(|| {
+            StorageVersion::new(1).put::<NftBridge>();
+            NFTMetadataUri::<Test>::insert(1, 1, b"ipfs://item".to_vec());
+            NFTMetadataUri::<Test>::insert(1, 2, vec![0; MaxUriLen::get() as usize + 1]);
+
+            MigrateToV2::<Test>::on_runtime_upgrade();
+
+            assert_eq!(NftBridge::nft_metadata_uri(1, 1), Some(b"ipfs://item".to_vec()));
+            assert_eq!(NftBridge::metadata_uris(1, 1).unwrap().len(), 1);
+            assert!(NftBridge::metadata_uris(1, 2).is_none());
+            assert!(NFTMetadataUri::<Test>::iter().next().is_none());
+            assert_eq!(NftBridge::on_chain_storage_version(), 2);
+        });
+    }
+
+    #[test]
+    fn priced_transfer_pays_the_royalty() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 3));
+            let royalty = RoyaltyInfo {
+                beneficiary: RoyaltyBeneficiary::Local(4),
+                rate: Perbill::from_rational(25u32, 1000),
+            };
+            assert_noop!(
+                NftBridge::set_royalty(RuntimeOrigin::signed(1), 1, 1, Some(royalty.clone())),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::set_royalty(RuntimeOrigin::signed(3), 1, 1, Some(royalty)));
+            NFTOwners::<Test>::insert(1, 1, 1);
+            Balances::make_free_balance_be(&1, 1_000);
+            Balances::make_free_balance_be(&4, 1);
+
+            // 2.5% of 999 is 24.975, rounded down
+            assert_ok!(NftBridge::transfer(RuntimeOrigin::signed(1), 1, 1, 2, Some(999)));
+            assert_eq!(Balances::free_balance(4), 1 + 24);
+            assert_eq!(Balances::free_balance(1), 1_000 - 24);
+            assert_eq!(NftBridge::owner(1, 1), Some(2));
+            System::assert_has_event(RuntimeEvent::NftBridge(crate::Event::RoyaltyPaid {
+                collection_id: 1,
+                item_id: 1,
+                beneficiary: RoyaltyBeneficiary::Local(4),
+                amount: 24,
+            }));
+
+            // Gifts owe nothing
+            assert_ok!(NftBridge::transfer(RuntimeOrigin::signed(2), 1, 1, 1, Some(0)));
+            assert_ok!(NftBridge::transfer(RuntimeOrigin::signed(1), 1, 1, 2, None));
+            assert_eq!(Balances::free_balance(4), 1 + 24);
+
+            // Items without a royalty record move for any price
+            NFTOwners::<Test>::insert(1, 2, 1);
+            assert_ok!(NftBridge::transfer(RuntimeOrigin::signed(1), 1, 2, 2, Some(500)));
+            assert_eq!(Balances::free_balance(1), 1_000 - 24);
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::Transferred {
+                collection_id: 1,
+                item_id: 2,
+                from: 1,
+                to: 2,
+                sale_price: Some(500),
+            }));
+        });
+    }
+
+    #[test]
+    fn remote_royalties_are_escrowed_until_claimed() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 3));
+            let creator = MultiLocation::new(1, X1(Parachain(2000)));
+            let royalty = RoyaltyInfo {
+                beneficiary: RoyaltyBeneficiary::Remote(creator),
+                rate: Perbill::from_percent(10),
+            };
+            assert_ok!(NftBridge::set_royalty(RuntimeOrigin::signed(3), 1, 1, Some(royalty)));
+            NFTOwners::<Test>::insert(1, 1, 1);
+            Balances::make_free_balance_be(&1, 1_000);
+            Balances::make_free_balance_be(&NftBridge::escrow_account(), 1);
+
+            assert_ok!(NftBridge::transfer(RuntimeOrigin::signed(1), 1, 1, 2, Some(300)));
+            assert_eq!(NftBridge::royalty_pot(creator), 30);
+            assert_eq!(Balances::free_balance(NftBridge::escrow_account()), 1 + 30);
+
+            // Only the beneficiary location can claim, and only once
+            assert_noop!(
+                NftBridge::claim_royalties(RuntimeOrigin::signed(2001), 5),
+                Error::<Test>::NoRoyaltiesToClaim
+            );
+            assert_ok!(NftBridge::claim_royalties(RuntimeOrigin::signed(2000), 5));
+            assert_eq!(Balances::free_balance(5), 30);
+            assert_eq!(NftBridge::royalty_pot(creator), 0);
+            assert_noop!(
+                NftBridge::claim_royalties(RuntimeOrigin::signed(2000), 5),
+                Error::<Test>::NoRoyaltiesToClaim
+            );
+        });
+    }
+
+    #[test]
+    fn burning_a_wrapper_drops_its_records_and_tells_the_origin() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            // A wrapper of 2000's item 9, held here as item 1
+            NFTOwners::<Test>::insert(5, 1, 1);
+            ItemOrigin::<Test>::insert(5, 1, 2000);
+            ItemRemap::<Test>::insert(5, 1, (2000, 9));
+            RemoteItemRemap::<Test>::insert((2000, 5), 9, 1);
+            NFTMetadata::<Test>::insert(5, 1, b"meta".to_vec());
+
+            // Held by the bridge: refused
+            BridgeLocked::<Test>::insert(5, 1, ());
+            assert_noop!(
+                <NftBridge as OnNftBurned<u32, u32>>::on_nft_burned(5, 1),
+                Error::<Test>::TransferAlreadyPending
+            );
+            BridgeLocked::<Test>::remove(5, 1);
+
+            assert_ok!(<NftBridge as OnNftBurned<u32, u32>>::on_nft_burned(5, 1));
+            assert_eq!(NftBridge::owner(5, 1), None);
+            assert_eq!(NftBridge::item_origin(5, 1), None);
+            assert!(NFTMetadata::<Test>::get(5, 1).is_none());
+            assert!(ItemRemap::<Test>::get(5, 1).is_none());
+            assert!(RemoteItemRemap::<Test>::get((2000, 5), 9).is_none());
+
+            // The origin hears about it under its own id
+            assert!(matches!(
+                last_bridge_call(2000),
+                crate::Call::wrapper_burned { collection_id: 5, item_id: 9 }
+            ));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::ItemBurned {
+                collection_id: 5,
+                item_id: 1,
+                origin_para: Some(2000),
+                notified: true,
+            }));
+        });
+    }
+
+    #[test]
+    fn burned_wrapper_orphans_the_original() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            ItemsAbroad::<Test>::insert(1, 1, 2000);
+
+            // Only the chain holding the item can report it burned
+            assert_noop!(
+                NftBridge::wrapper_burned(RuntimeOrigin::signed(3000), 1, 1),
+                Error::<Test>::WrongReserve
+            );
+            assert_ok!(NftBridge::wrapper_burned(RuntimeOrigin::signed(2000), 1, 1));
+            assert!(ItemsAbroad::<Test>::get(1, 1).is_none());
+            assert_eq!(NftBridge::orphaned_item(1, 1), Some(2000));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::OriginalOrphaned {
+                collection_id: 1,
+                item_id: 1,
+                para_id: 2000,
+            }));
+        });
+    }
+
+    #[test]
+    fn delivery_failures_are_told_apart_from_refusals() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            DeliveryFee::set(25);
+            Balances::make_free_balance_be(&1, 1_000);
+            let retrying = |item_id: u32| {
+                matches!(
+                    NftBridge::pending_transfer(1, item_id).unwrap().status,
+                    TransferStatus::SendFailed { .. }
+                )
+            };
+            let failed_stage = || {
+                System::events().into_iter().rev().find_map(|record| match record.event {
+                    RuntimeEvent::NftBridge(crate::Event::TransferSendFailed { stage, .. }) => {
+                        Some(stage)
+                    },
+                    _ => None,
+                })
+            };
+
+            // Validated and delivered: sent and paid for
+            assert_ok!(send(1, 1, 1));
+            assert_eq!(Balances::free_balance(1), 1_000 - 25);
+            assert_eq!(sent_xcm().len(), 1);
+            assert_eq!(InFlightCount::<Test>::get(2000), 1);
+
+            // Validated, not delivered: the fee comes back and the send is retried
+            FailDeliveries::set(1);
+            assert_ok!(send(1, 1, 2));
+            assert!(retrying(2));
+            assert_eq!(failed_stage(), Some(SendStage::Deliver));
+            assert_eq!(Balances::free_balance(1), 1_000 - 25);
+            assert_eq!(Balances::free_balance(FeeAccount::get()), 25);
+
+            // Refused by validate: nothing charged, delivery never attempted
+            FailSends::set(1);
+            FailDeliveries::set(1);
+            assert_ok!(send(1, 1, 3));
+            assert!(retrying(3));
+            assert_eq!(failed_stage(), Some(SendStage::Validate));
+            assert_eq!(FailDeliveries::get(), 1);
+            FailDeliveries::set(0);
+
+            FailSends::set(1);
+            assert_ok!(send(1, 1, 4));
+            assert!(retrying(4));
+            assert_eq!(Balances::free_balance(1), 1_000 - 25);
+            assert_eq!(sent_xcm().len(), 1);
+
+            // Only the delivered transfer takes an in-flight slot
+            assert_eq!(InFlightCount::<Test>::get(2000), 1);
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 2));
+            assert_eq!(InFlightCount::<Test>::get(2000), 1);
+        });
+    }
+
+    #[test]
+    fn retried_sends_take_an_in_flight_slot_once_delivered() {
+        new_test_ext().execute_with(|| {
+            use frame_support::traits::Hooks;
+            System::set_block_number(1);
+            FailDeliveries::set(2);
+            assert_ok!(send(1, 1, 1));
+            assert_eq!(InFlightCount::<Test>::get(2000), 0);
+
+            // The retry's delivery fails again and says so
+            System::set_block_number(11);
+            NftBridge::on_idle(11, Weight::MAX);
+            let transfer = NftBridge::pending_transfer(1, 1).unwrap();
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::TransferSendFailed {
+                collection_id: 1,
+                item_id: 1,
+                transfer_id: transfer.id,
+                next_attempt: 51,
+                stage: SendStage::Deliver,
+            }));
+            assert_eq!(InFlightCount::<Test>::get(2000), 0);
+
+            System::set_block_number(51);
+            NftBridge::on_idle(51, Weight::MAX);
+            assert_eq!(NftBridge::pending_transfer(1, 1).unwrap().status, TransferStatus::InFlight);
+            assert_eq!(InFlightCount::<Test>::get(2000), 1);
+
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert_eq!(InFlightCount::<Test>::get(2000), 0);
+        });
+    }
+
+    #[test]
+    fn immediate_sends_report_the_failed_stage() {
+        new_test_ext().execute_with(|| {
+            DeliveryFee::set(25);
+            Balances::make_free_balance_be(&1, 1_000);
+            let items = [(1, 1), (2, 1)];
+            for (collection, item) in items {
+                NFTOwners::<Test>::insert(collection, item, 1);
+            }
+            let bundle = || {
+                let items = batch(&items);
+                NftBridge::send_nfts(RuntimeOrigin::signed(1), items, 2000, BatchMode::Bundle, None)
+            };
+
+            FailSends::set(1);
+            assert_noop!(bundle(), Error::<Test>::FailedToSendXCM);
+            FailDeliveries::set(1);
+            assert_noop!(bundle(), Error::<Test>::DeliveryFailed);
+            assert_eq!(Balances::free_balance(1), 1_000);
+
+            assert_ok!(bundle());
+            assert_eq!(Balances::free_balance(1), 1_000 - 25);
+        });
+    }
+
+    #[test]
+    fn bridge_stats_summarise_bridge_health() {
+        new_test_ext().execute_with(|| {
+            let send_to = |item_id: u32, dest: u32| {
+                NFTOwners::<Test>::insert(1, item_id, 1);
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item_id,
+                    dest,
+                    vec![],
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                3000,
+                Some(DestinationConfig { max_in_flight: Some(1), ..Default::default() })
+            ));
+
+            System::set_block_number(1);
+            assert_ok!(send_to(1, 2000));
+            System::set_block_number(5);
+            assert_ok!(send_to(2, 3000));
+            // Over 3000's in-flight limit
+            assert_ok!(send_to(3, 3000));
+            FailSends::set(1);
+            assert_ok!(send_to(4, 2000));
+            NonTransferableCollections::<Test>::insert(7, TransferRestriction::Outbound);
+            let holder = SOVEREIGN_BASE + 2000;
+            let orphan = OrphanedNft {
+                from_para_id: 2000,
+                holder,
+                beneficiary: AccountToLocation::convert(2),
+            };
+            OrphanedInbound::<Test>::insert(5, 1, orphan);
+            System::set_block_number(10);
+
+            assert_eq!(
+                NftBridge::bridge_stats(),
+                BridgeStats {
+                    pending_transfers: 4,
+                    oldest_pending_age: Some(9),
+                    in_flight: vec![(2000, 1), (3000, 1)],
+                    restricted_collections: 1,
+                    queued_transfers: 1,
+                    failed_sends: 1,
+                    orphaned_inbound: 1,
+                    metadata_limits: MetadataSizeLimits { max_metadata: 1024, max_uri: 256 },
+                    max_outbound_queue: 4,
+                    max_outbound_message_size: 64 * 1024,
+                    max_retries: 3,
+                    delivery_fee_spend: vec![],
+                    complete: true,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn block_hooks_stay_within_their_weight() {
+        new_test_ext().execute_with(|| {
+            type Weights = <Test as crate::Config>::WeightInfo;
+            System::set_block_number(1);
+            for item in 1..=5 {
+                assert_ok!(send(1, 1, item));
+                reject(1, item);
+            }
+            let pending = || PendingTransfers::<Test>::iter().count();
+
+            // Not even the fixed part of the sweep fits
+            let short = Weights::on_initialize_expiries(0).saturating_sub(Weight::from_parts(1, 0));
+            assert_eq!(NftBridge::expire_transfers(1001, short), Weight::zero());
+            assert_eq!(pending(), 5);
+
+            // Room for two, with some to spare
+            let spare = Weight::from_parts(1, 0);
+            let budget = Weights::on_initialize_expiries(2).saturating_add(spare);
+            assert!(NftBridge::expire_transfers(1001, budget).all_lte(budget));
+            assert_eq!(pending(), 3);
+        });
+    }
+
+    #[test]
+    fn queue_drain_and_retries_stay_within_their_weight() {
+        new_test_ext().execute_with(|| {
+            type Weights = <Test as crate::Config>::WeightInfo;
+            System::set_block_number(1);
+            let limit = |max_in_flight| {
+                assert_ok!(NftBridge::set_destination_config(
+                    RuntimeOrigin::root(),
+                    2000,
+                    Some(DestinationConfig { max_in_flight, ..Default::default() })
+                ));
+            };
+
+            // One in flight, four queued
+            limit(Some(1));
+            for item in 1..=5 {
+                assert_ok!(send(1, 1, item));
+            }
+            limit(None);
+            let budget = Weights::on_idle_queue_drain(2);
+            assert!(NftBridge::drain_outbound_queues(budget).all_lte(budget));
+            assert_eq!(OutboundQueue::<Test>::get(2000).len(), 2);
+
+            // Three failed sends, two of them retried
+            FailSends::set(3);
+            for item in 6..=8 {
+                assert_ok!(send(1, 1, item));
+            }
+            let sent = sent_xcm().len();
+            let budget = Weights::on_idle_retries(2);
+            assert!(NftBridge::retry_failed_sends(100, budget).all_lte(budget));
+            assert_eq!(sent_xcm().len(), sent + 2);
+        });
+    }
+
+    // Random inputs tried by each fuzz test, kept low so `cargo test` stays fast
+    const FUZZ_CASES: u32 = 64;
+
+    mod fuzz {
+        use super::*;
+        use frame_support::traits::UnfilteredDispatchable;
+        use proptest::prelude::*;
+        use xcm_executor::traits::Convert as XcmConvert;
+
+        // Run `f` on chain state holding a registered collection and a few owned items. Any
+        // error must leave the state exactly as it was.
+        fn assert_atomic(f: impl FnOnce() -> DispatchResult) {
+            new_test_ext().execute_with(|| {
+                System::set_block_number(1);
+                assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 2, None, 3));
+                NFTOwners::<Test>::insert(1, 1, 1);
+                NFTOwners::<Test>::insert(2, 2, 1);
+                let before = state_root();
+                if f().is_err() {
+                    assert_eq!(state_root(), before);
+                }
+            });
+        }
+
+        fn bytes(max: usize) -> impl Strategy<Value = Vec<u8>> {
+            proptest::collection::vec(any::<u8>(), 0..max)
+        }
+
+        fn uris() -> impl Strategy<Value = ItemUris> {
+            let kind = prop_oneof![
+                Just(MediaKind::Primary),
+                Just(MediaKind::Image),
+                Just(MediaKind::Animation),
+                Just(MediaKind::Document),
+                any::<u8>().prop_map(MediaKind::Other),
+            ];
+            proptest::collection::vec((kind, bytes(300)), 0..6)
+        }
+
+        fn junction() -> impl Strategy<Value = Junction> {
+            prop_oneof![
+                any::<u8>().prop_map(PalletInstance),
+                any::<u128>().prop_map(GeneralIndex),
+                any::<u32>().prop_map(Parachain),
+                (any::<u8>(), any::<[u8; 32]>())
+                    .prop_map(|(length, data)| GeneralKey { length, data }),
+            ]
+        }
+
+        fn multi_asset() -> impl Strategy<Value = MultiAsset> {
+            let interior = proptest::collection::vec(junction(), 0..4).prop_map(|junctions| {
+                junctions.into_iter().fold(Here, |interior, junction| {
+                    interior.pushed_with(junction).unwrap_or_else(|(interior, _)| interior)
+                })
+            });
+            let id = prop_oneof![
+                (0u8..3, interior).prop_map(|(parents, interior)| {
+                    Concrete(MultiLocation { parents, interior })
+                }),
+                any::<[u8; 32]>().prop_map(Abstract),
+            ];
+            let fun = prop_oneof![
+                any::<u128>().prop_map(Fungible),
+                any::<u128>().prop_map(|item| NonFungible(AssetInstance::Index(item))),
+                any::<[u8; 32]>().prop_map(|item| NonFungible(AssetInstance::Array32(item))),
+                Just(NonFungible(AssetInstance::Undefined)),
+            ];
+            // Assets under the mock's NFT prefix get past the location checks
+            let near_miss = (any::<u128>(), any::<u128>()).prop_map(|(collection, item)| {
+                MultiAsset {
+                    id: Concrete(MultiLocation::new(
+                        0,
+                        X2(PalletInstance(50), GeneralIndex(collection)),
+                    )),
+                    fun: NonFungible(AssetInstance::Index(item)),
+                }
+            });
+            prop_oneof![(id, fun).prop_map(|(id, fun)| MultiAsset { id, fun }), near_miss]
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(FUZZ_CASES))]
+
+            #[test]
+            fn receive_rejects_garbage_without_side_effects(
+                collection_id in 0u32..4,
+                item_id in 0u32..4,
+                from_para_id in prop_oneof![Just(2000u32), Just(3000), any::<u32>()],
+                recipient in 0u64..4,
+                metadata in bytes(1100),
+                metadata_uris in proptest::option::of(uris()),
+                nonce in proptest::option::of(any::<u64>()),
+                metadata_hash in proptest::option::of(any::<[u8; 32]>().prop_map(H256)),
+                lock_proof in proptest::option::of(bytes(64)),
+            ) {
+                assert_atomic(|| {
+                    NftBridge::do_receive_nft(
+                        collection_id,
+                        item_id,
+                        from_para_id,
+                        recipient,
+                        Some(metadata),
+                        metadata_uris,
+                        nonce,
+                        metadata_hash,
+                        lock_proof,
+                        None,
+                    )
+                });
+            }
+
+            #[test]
+            fn bridge_message_bytes_never_panic(
+                message in (0u32..4, 0u32..4, 0u64..4, bytes(1100), uris()),
+                tail in bytes(64),
+                cut in any::<prop::sample::Index>(),
+                flip in proptest::option::of((any::<prop::sample::Index>(), any::<u8>())),
+            ) {
+                let (collection_id, item_id, recipient, metadata, metadata_uris) = message;
+                let message = BridgeMessage {
+                    collection_id,
+                    item_id,
+                    recipient,
+                    metadata,
+                    metadata_uris,
+                    sent_at: 0,
+                };
+                // A message behind `receive_nft`'s call index, cut short and possibly corrupted
+                let mut encoded = vec![1u8];
+                encoded.extend(message.encode());
+                encoded.extend(tail);
+                encoded.truncate(cut.index(encoded.len()) + 1);
+                if let Some((at, byte)) = flip {
+                    let at = at.index(encoded.len());
+                    encoded[at] ^= byte;
+                }
+
+                if let Ok(call) = crate::Call::<Test>::decode(&mut &encoded[..]) {
+                    assert_atomic(|| {
+                        call.dispatch_bypass_filter(RuntimeOrigin::signed(2000))
+                            .map(|_| ())
+                            .map_err(|e| e.error)
+                    });
+                }
+            }
+
+            #[test]
+            fn asset_matcher_never_panics(
+                asset in multi_asset(),
+                raw in bytes(96),
+            ) {
+                new_test_ext().execute_with(|| {
+                    let assets = MultiAsset::decode(&mut &raw[..]).into_iter().chain([asset]);
+                    for asset in assets {
+                        let matched = NftBridge::match_nft_asset(&asset);
+                        let converted =
+                            <NftAssetConverter<Test> as XcmConvert<_, _>>::convert_ref(&asset);
+                        // Anything converted to an item is one of ours by the raw match too
+                        if converted.is_ok() {
+                            assert!(matched.is_some());
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn bridge_instances_keep_separate_state() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(NftBridge::set_collection_transferability(
+                RuntimeOrigin::root(),
+                1,
+                Some(TransferRestriction::Outbound)
+            ));
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_noop!(send(1, 1, 1), Error::<Test>::CollectionNonTransferable);
+
+            // The restriction belongs to the first lane only
+            NFTOwners::<Test, Instance2>::insert(1, 1, 1);
+            assert_ok!(OtherBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                vec![],
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None,
+            ));
+            assert!(PendingTransfers::<Test, Instance2>::contains_key(1, 1));
+            assert!(!PendingTransfers::<Test>::contains_key(1, 1));
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+
+            // Each lane escrows into its own accounts
+            assert_ne!(NftBridge::escrow_account_for(2000), OtherBridge::escrow_account_for(2000));
+            assert_eq!(OtherBridge::owner(1, 1), Some(OtherBridge::escrow_account_for(2000)));
+        });
+    }
+
+    #[test]
+    fn staging_deposits_are_held_under_their_reason() {
+        use frame_support::traits::fungible::InspectHold;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            Balances::make_free_balance_be(&1, 100);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+            let metadata_deposit = RuntimeHoldReason::NftBridge(HoldReason::MetadataDeposit);
+            let transfer_deposit = RuntimeHoldReason::NftBridge(HoldReason::TransferDeposit);
+
+            assert_ok!(NftBridge::stage_metadata(RuntimeOrigin::signed(1), 1, 1, vec![1; 40], None));
+            assert_ok!(NftBridge::stage_metadata(RuntimeOrigin::signed(1), 1, 2, vec![1; 15], None));
+            assert_eq!(Balances::balance_on_hold(&metadata_deposit, &1), 55);
+            assert_eq!(Balances::balance_on_hold(&transfer_deposit, &1), 0);
+            assert_eq!(Balances::free_balance(1), 45);
+            assert_eq!(Balances::free_balance(NftBridge::escrow_account()), 0);
+
+            // Each release gives back exactly that staging's deposit
+            System::set_block_number(21);
+            assert_ok!(NftBridge::purge_staged_metadata(RuntimeOrigin::signed(3), 1, 2));
+            assert_eq!(Balances::balance_on_hold(&metadata_deposit, &1), 40);
+            assert_eq!(Balances::free_balance(1), 60);
+            assert_ok!(NftBridge::purge_staged_metadata(RuntimeOrigin::signed(3), 1, 1));
+            assert_eq!(Balances::balance_on_hold(&metadata_deposit, &1), 0);
+            assert_eq!(Balances::free_balance(1), 100);
+        });
+    }
+
+    #[test]
+    fn migration_to_v3_moves_staging_deposits_to_holds() {
+        use frame_support::traits::{
+            fungible::InspectHold, GetStorageVersion, OnRuntimeUpgrade, StorageVersion,
+        };
+        use migrations::v3::MigrateToV3;
+        new_test_ext().execute_with(|| {
+            StorageVersion::new(2).put::<NftBridge>();
+            Balances::make_free_balance_be(&NftBridge::escrow_account(), 1 + 30);
+            Balances::make_free_balance_be(&1, 5);
+            let staged = |depositor: u64, deposit: u64| StagedMetadataInfo {
+                depositor,
+                deposit,
+                metadata: vec![1; deposit as usize],
+                metadata_uri: None,
+                metadata_hash: H256::zero(),
+                staged_at: 1,
+            };
+            StagedMetadata::<Test>::insert(1, 1, staged(1, 30));
+            // More than the escrow account still has: logged and left there for governance
+            StagedMetadata::<Test>::insert(1, 2, staged(2, 50));
+
+            MigrateToV3::<Test>::on_runtime_upgrade();
+
+            let reason = RuntimeHoldReason::NftBridge(HoldReason::MetadataDeposit);
+            assert_eq!(Balances::balance_on_hold(&reason, &1), 30);
+            assert_eq!(Balances::free_balance(1), 5);
+            assert_eq!(Balances::balance_on_hold(&reason, &2), 0);
+            assert_eq!(StagedMetadata::<Test>::get(1, 2), Some(staged(2, 50)));
+            assert_eq!(Balances::free_balance(NftBridge::escrow_account()), 1);
+            assert_eq!(NftBridge::on_chain_storage_version(), 3);
+
+            // Migrated deposits are released like fresh ones
+            System::set_block_number(21);
+            assert_ok!(NftBridge::purge_staged_metadata(RuntimeOrigin::signed(3), 1, 1));
+            assert_eq!(Balances::free_balance(1), 35);
+        });
+    }
+
+    #[test]
+    fn wrapped_collections_take_free_ids_from_the_range() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            Permissionless::set(false);
+            AutoRegister::set(true);
+            WrappedRange::set(Some((100, 103)));
+            // A user already created collection 101 in the backend
+            assert_ok!(Uniques::force_create(RuntimeOrigin::root(), 101, 1, true));
+            let deliver = |from: u32, collection_id: u32, item_id: u32| {
+                receive(Delivery { from, collection_id, item_id, ..Default::default() })
+            };
+
+            assert_ok!(deliver(2000, 5, 1));
+            assert_ok!(deliver(2001, 5, 1));
+            assert_ok!(deliver(2000, 6, 1));
+            assert_eq!(NftBridge::wrapped_collection(2000, 5), Some(100));
+            assert_eq!(NftBridge::wrapped_collection(2001, 5), Some(102));
+            assert_eq!(NftBridge::wrapped_collection(2000, 6), Some(103));
+            System::assert_has_event(
+                Event::WrappedCollectionCreated { local: 102, remote_para: 2001, remote_collection: 5 }
+                    .into(),
+            );
+            assert_eq!(NftBridge::owner(102, 1), Some(2));
+            assert_eq!(NftBridge::collection_info(102).unwrap().reserve, Some(2001));
+
+            // Known collections keep their id, both ways
+            assert_ok!(deliver(2000, 5, 2));
+            assert_eq!(NftBridge::owner(100, 2), Some(2));
+            assert_eq!(NftBridge::remote_collection_id(100, 2000), 5);
+            assert_eq!(NftBridge::remote_collection_id(100, 2001), 100);
+
+            // The range is spent
+            assert_eq!(deliver(2000, 7, 1), Err(Error::<Test>::CollectionIdExhausted.into()));
+        });
+    }
+
+    #[test]
+    fn aging_transfers_are_reported_once() {
+        use frame_support::traits::Hooks;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(send(1, 1, 1));
+            assert_ok!(send(1, 1, 2));
+            let aging = || {
+                System::events()
+                    .into_iter()
+                    .filter(|record| {
+                        matches!(record.event, RuntimeEvent::NftBridge(Event::TransferAging { .. }))
+                    })
+                    .count()
+            };
+
+            NftBridge::on_idle(500, Weight::MAX);
+            assert_eq!(aging(), 0);
+            NftBridge::on_idle(501, Weight::MAX);
+            System::assert_has_event(
+                Event::TransferAging { collection_id: 1, item_id: 2, age: 500 }.into(),
+            );
+            assert_eq!(aging(), 2);
+            NftBridge::on_idle(600, Weight::MAX);
+            assert_eq!(aging(), 2);
+
+            // Settling drops the flag along with the transfer
+            let transfer_id = NftBridge::pending_transfer(1, 1).unwrap().id;
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert!(!AgingWarned::<Test>::contains_key(transfer_id));
+        });
+    }
+
+    #[test]
+    fn aging_scan_stays_within_its_budget() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            for item_id in 1..=3 {
+                assert_ok!(send(1, 1, item_id));
+            }
+            let one = <() as WeightInfo>::on_idle_aging_scan(1);
+
+            // Each call covers one transfer, continuing where the last one stopped
+            for warned in 1..=3 {
+                assert_eq!(NftBridge::warn_aging_transfers(501, one), one);
+                assert_eq!(AgingWarned::<Test>::iter().count(), warned);
+            }
+            // The end of the map resets the cursor; the next pass warns no one twice
+            let none = <() as WeightInfo>::on_idle_aging_scan(0);
+            assert_eq!(NftBridge::warn_aging_transfers(501, one), none);
+            assert_eq!(NftBridge::warn_aging_transfers(501, one), one);
+            assert_eq!(AgingWarned::<Test>::iter().count(), 3);
+            assert_eq!(NftBridge::warn_aging_transfers(501, Weight::zero()), Weight::zero());
+        });
+    }
+
+    #[test]
+    fn remote_ownership_query_round_trip() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            Balances::make_free_balance_be(&1, 100);
+            assert_ok!(NftBridge::query_remote_ownership(RuntimeOrigin::signed(1), 2000, 5, 1));
+            assert_eq!(Balances::free_balance(1), 95);
+            System::assert_last_event(
+                Event::OwnershipQueried { query_id: 0, para_id: 2000, collection_id: 5, item_id: 1 }
+                    .into(),
+            );
+            let crate::Call::ownership_query { query_id, collection_id, item_id } =
+                last_bridge_call(2000)
+            else {
+                panic!("expected an ownership query")
+            };
+            assert_eq!((query_id, collection_id, item_id), (0, 5, 1));
+
+            // The chain asked answers with the owner it knows; both run in this runtime
+            NFTOwners::<Test>::insert(5, 1, 7);
+            assert_ok!(NftBridge::ownership_query(RuntimeOrigin::signed(1000), 0, 5, 1));
+            let crate::Call::report_ownership { query_id, owner } = last_bridge_call(1000) else {
+                panic!("expected an ownership report")
+            };
+            assert_eq!((query_id, owner), (0, Some(7)));
+
+            // Only the chain asked may answer, and only once
+            assert_noop!(
+                NftBridge::report_ownership(RuntimeOrigin::signed(2001), 0, Some(7)),
+                Error::<Test>::OriginMismatch
+            );
+            System::set_block_number(11);
+            assert_ok!(NftBridge::report_ownership(RuntimeOrigin::signed(2000), 0, Some(7)));
+            System::assert_last_event(
+                Event::RemoteOwnershipReported {
+                    para_id: 2000,
+                    collection_id: 5,
+                    item_id: 1,
+                    owner: Some(7),
+                }
+                .into(),
+            );
+            assert_eq!(
+                NftBridge::remote_ownership((2000, 5), 1),
+                Some(RemoteOwner { owner: Some(7), reported_at: 11 })
+            );
+            assert!(NftBridge::pending_ownership_query(0).is_none());
+            // The answer gives the asker back the deposit
+            assert_eq!(Balances::free_balance(1), 100);
+            assert_noop!(
+                NftBridge::report_ownership(RuntimeOrigin::signed(2000), 0, None),
+                Error::<Test>::UnknownQuery
+            );
+        });
+    }
+
+    #[test]
+    fn ownership_queries_are_paid_for_and_bounded() {
+        use frame_support::traits::fungible::InspectHold;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let reason = RuntimeHoldReason::NftBridge(HoldReason::OwnershipQueryDeposit);
+            // An account that can't put down the deposit can't ask
+            assert!(NftBridge::query_remote_ownership(RuntimeOrigin::signed(4), 2000, 5, 1).is_err());
+
+            Balances::make_free_balance_be(&1, 100);
+            assert_ok!(NftBridge::query_remote_ownership(RuntimeOrigin::signed(1), 2000, 5, 1));
+            assert_ok!(NftBridge::query_remote_ownership(RuntimeOrigin::root(), 2000, 5, 2));
+            assert_eq!(Balances::balance_on_hold(&reason, &1), 5);
+            assert_eq!(OwnershipQueryCount::<Test>::get(), 2);
+            assert_noop!(
+                NftBridge::query_remote_ownership(RuntimeOrigin::root(), 2000, 5, 3),
+                Error::<Test>::TooManyOwnershipQueries
+            );
+
+            // Purging a lapsed query frees its slot and refunds whoever asked
+            System::set_block_number(12);
+            assert_ok!(NftBridge::purge_ownership_query(RuntimeOrigin::signed(3), 0));
+            assert_eq!(Balances::balance_on_hold(&reason, &1), 0);
+            assert_eq!(Balances::free_balance(1), 100);
+            assert_eq!(OwnershipQueryCount::<Test>::get(), 1);
+            assert_ok!(NftBridge::query_remote_ownership(RuntimeOrigin::root(), 2000, 5, 3));
+        });
+    }
+
+    #[test]
+    fn unanswered_ownership_query_expires() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(NftBridge::query_remote_ownership(RuntimeOrigin::root(), 2000, 5, 1));
+            assert_noop!(
+                NftBridge::purge_ownership_query(RuntimeOrigin::signed(3), 0),
+                Error::<Test>::QueryNotExpired
+            );
+
+            System::set_block_number(12);
+            assert_noop!(
+                NftBridge::report_ownership(RuntimeOrigin::signed(2000), 0, Some(7)),
+                Error::<Test>::QueryExpired
+            );
+            assert_ok!(NftBridge::purge_ownership_query(RuntimeOrigin::signed(3), 0));
+            System::assert_last_event(Event::OwnershipQueryPurged { query_id: 0 }.into());
+            assert!(NftBridge::pending_ownership_query(0).is_none());
+            assert!(NftBridge::remote_ownership((2000, 5), 1).is_none());
+        });
+    }
+
+    #[test]
+    fn collection_admin_manages_its_own_collection() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 9));
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 2, None, 8));
+            MetadataSubscribers::<Test>::insert(1, 2000, ());
+            let admin = RuntimeOrigin::signed(9);
+            let stranger = RuntimeOrigin::signed(8);
+
+            // Collection 1's admin, the admin of another collection, then root
+            assert_noop!(
+                NftBridge::set_collection_transferability(
+                    stranger.clone(),
+                    1,
+                    Some(TransferRestriction::Outbound)
+                ),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::set_collection_transferability(
+                admin.clone(),
+                1,
+                Some(TransferRestriction::Outbound)
+            ));
+            assert_ok!(NftBridge::set_collection_transferability(RuntimeOrigin::root(), 1, None));
+
+            assert_noop!(
+                NftBridge::set_collection_fee_policy(stranger.clone(), 1, FeePolicy::Waived),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::set_collection_fee_policy(admin.clone(), 1, FeePolicy::Waived));
+            assert_ok!(NftBridge::set_collection_fee_policy(
+                RuntimeOrigin::root(),
+                1,
+                FeePolicy::Fixed(3)
+            ));
+
+            assert_noop!(
+                NftBridge::set_collection_reserve(stranger.clone(), 1, Some(2000)),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::set_collection_reserve(admin.clone(), 1, Some(2000)));
+            assert_eq!(NftBridge::collection_info(1).unwrap().mode, CollectionMode::Wrapped);
+            assert_ok!(NftBridge::set_collection_reserve(RuntimeOrigin::root(), 1, None));
+            System::assert_last_event(
+                Event::CollectionReserveSet { collection_id: 1, reserve: None }.into(),
+            );
+
+            assert_noop!(
+                NftBridge::remove_metadata_subscriber(stranger.clone(), 1, 2000),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::remove_metadata_subscriber(admin.clone(), 1, 2000));
+            assert_ok!(NftBridge::remove_metadata_subscriber(RuntimeOrigin::root(), 1, 2001));
+
+            // Handing the role over takes it away from the old admin
+            assert_noop!(
+                NftBridge::set_collection_admin(stranger, 1, 8),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::set_collection_admin(admin.clone(), 1, 7));
+            assert_eq!(NftBridge::collection_admin(1), Some(7));
+            assert_noop!(
+                NftBridge::set_collection_fee_policy(admin, 1, FeePolicy::Default),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::set_collection_admin(RuntimeOrigin::root(), 1, 9));
+            assert_eq!(NftBridge::collection_admin(1), Some(9));
+        });
+    }
+
+    #[test]
+    fn collection_reserve_cannot_change_while_items_are_out() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 9));
+            assert_ok!(send(1, 1, 1));
+            assert_noop!(
+                NftBridge::set_collection_reserve(RuntimeOrigin::signed(9), 1, Some(2000)),
+                Error::<Test>::CollectionInUse
+            );
+        });
+    }
+
+    #[test]
+    fn escrowed_items_pages_through_escrow() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(send(1, 1, 1));
+            assert_ok!(send(1, 1, 2));
+            assert_ok!(send(1, 1, 3));
+
+            let first = NftBridge::escrowed_items(None, 2);
+            assert_eq!(first.len(), 2);
+            let rest = NftBridge::escrowed_items(first.last().copied(), 10);
+            assert_eq!(rest.len(), 1);
+            let mut all = [first, rest].concat();
+            all.sort();
+            assert_eq!(all, vec![(1, 1, 2000), (1, 2, 2000), (1, 3, 2000)]);
+            assert_ok!(NftBridge::do_try_state());
+        });
+    }
+
+    #[test]
+    fn try_state_detects_an_item_missing_from_escrow() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(send(1, 1, 1));
+            assert_ok!(send(1, 1, 2));
+            assert_ok!(NftBridge::do_try_state());
+
+            // The escrow entry of a pending item disappears
+            NFTOwners::<Test>::remove(1, 2);
+            assert_eq!(NftBridge::escrowed_items(None, 10), vec![(1, 1, 2000)]);
+            assert_eq!(
+                NftBridge::do_try_state(),
+                Err("escrow does not match the pending transfers")
+            );
+        });
+    }
+
+    #[test]
+    fn sends_past_their_deadline_fail_without_side_effects() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(10);
+            BridgeFee::set(10);
+            Balances::make_free_balance_be(&1, 100);
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+
+            assert_noop!(
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    1,
+                    2000,
+                    b"test_metadata".to_vec(),
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    Some(9)
+                ),
+                Error::<Test>::TransferDeadlineExpired
+            );
+            assert_noop!(
+                NftBridge::send_nfts(
+                    RuntimeOrigin::signed(1),
+                    batch(&[(1, 1), (1, 2)]),
+                    2000,
+                    BatchMode::BestEffort,
+                    Some(9)
+                ),
+                Error::<Test>::TransferDeadlineExpired
+            );
+            assert!(sent_xcm().is_empty());
+            assert_eq!(Balances::free_balance(1), 100);
+
+            // The deadline block itself is still in time and travels with the transfer
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                2000,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                Some(10)
+            ));
+            assert_eq!(NftBridge::pending_transfer(1, 1).unwrap().valid_until, Some(10));
+        });
+    }
+
+    #[test]
+    fn queued_transfers_expire_once_their_deadline_passes() {
+        new_test_ext().execute_with(|| {
+            use frame_support::traits::Hooks;
+            System::set_block_number(1);
+            assert_ok!(NftBridge::set_destination_config(
+                RuntimeOrigin::root(),
+                2000,
+                Some(DestinationConfig { max_in_flight: Some(1), ..Default::default() })
+            ));
+            NFTOwners::<Test>::insert(1, 1, 1);
+            NFTOwners::<Test>::insert(1, 2, 1);
+            assert_ok!(NftBridge::send_nfts(
+                RuntimeOrigin::signed(1),
+                batch(&[(1, 1), (1, 2)]),
+                2000,
+                BatchMode::Atomic,
+                Some(5)
+            ));
+            assert_eq!(NftBridge::pending_transfer(1, 2).unwrap().status, TransferStatus::Queued);
+
+            NftBridge::on_idle(5, Weight::MAX);
+            assert!(NftBridge::pending_transfer(1, 2).is_some());
+
+            // The queued message never left and goes back to its sender; the one in flight waits
+            System::set_block_number(6);
+            NftBridge::on_idle(6, Weight::MAX);
+            assert!(NftBridge::pending_transfer(1, 2).is_none());
+            assert_eq!(NftBridge::owner(1, 2), Some(1));
+            assert!(OutboundQueue::<Test>::get(2000).is_empty());
+            assert!(NftBridge::pending_transfer(1, 1).is_some());
+        });
+    }
+
+    #[test]
+    fn retried_deliveries_are_ignored() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let deliver = |item_id: u32, delivery_id: [u8; 32]| {
+                receive(Delivery { item_id, delivery_id: Some(delivery_id), ..Default::default() })
+            };
+            let received = || {
+                System::events()
+                    .into_iter()
+                    .filter(|record| {
+                        matches!(
+                            record.event,
+                            RuntimeEvent::NftBridge(crate::Event::NFTReceived { .. })
+                        )
+                    })
+                    .count()
+            };
+
+            assert_ok!(deliver(1, [1; 32]));
+            assert_ok!(deliver(1, [1; 32]));
+            assert_eq!(received(), 1);
+            assert_eq!(NftBridge::owner(1, 1), Some(2));
+            System::assert_last_event(
+                Event::DuplicateDeliveryIgnored { from_para_id: 2000, delivery_id: [1; 32] }.into(),
+            );
+
+            // Only the latest `DeliveryDedupWindow` ids are kept
+            assert_ok!(deliver(2, [2; 32]));
+            assert_ok!(deliver(3, [3; 32]));
+            assert_eq!(ProcessedDeliveries::<Test>::get(2000).into_inner(), vec![[2; 32], [3; 32]]);
+        });
+    }
+
+    #[test]
+    fn collection_data_is_cleared_over_several_calls() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 7, None, 3));
+            for item_id in 1..=3 {
+                NFTMetadata::<Test>::insert(7, item_id, b"test_metadata".to_vec());
+            }
+            ItemOrigin::<Test>::insert(7, 1, 2000);
+            ItemOrigin::<Test>::insert(7, 2, 2000);
+            ItemRemap::<Test>::insert(7, 1, (2000, 9));
+            RemoteItemRemap::<Test>::insert((2000, 7), 9, 1);
+            MetadataSubscribers::<Test>::insert(7, 2000, ());
+
+            assert_noop!(
+                NftBridge::clear_collection_data(RuntimeOrigin::signed(3), 7, 3),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(NftBridge::clear_collection_data(RuntimeOrigin::root(), 7, 3));
+            System::assert_last_event(
+                Event::CollectionDataClearing { collection_id: 7, removed: 3 }.into(),
+            );
+            assert_ok!(NftBridge::clear_collection_data(RuntimeOrigin::root(), 7, 3));
+            System::assert_last_event(
+                Event::CollectionDataClearing { collection_id: 7, removed: 3 }.into(),
+            );
+            assert!(NftBridge::collection_info(7).is_some());
+
+            assert_ok!(NftBridge::clear_collection_data(RuntimeOrigin::root(), 7, 3));
+            System::assert_last_event(
+                Event::CollectionDataCleared { collection_id: 7, removed: 2 }.into(),
+            );
+            assert!(NftBridge::collection_info(7).is_none());
+            assert!(NFTMetadata::<Test>::iter_prefix(7).next().is_none());
+            assert!(ItemOrigin::<Test>::iter_prefix(7).next().is_none());
+            assert_eq!(RemoteItemRemap::<Test>::get((2000, 7), 9), None);
+            assert!(MetadataSubscribers::<Test>::iter_prefix(7).next().is_none());
+            assert!(!CollectionClearCursor::<Test>::contains_key(7));
+        });
+    }
+
+    #[test]
+    fn collection_data_stays_while_items_are_active() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 3));
+            NFTOwners::<Test>::insert(1, 5, 1);
+            assert_noop!(
+                NftBridge::clear_collection_data(RuntimeOrigin::root(), 1, 10),
+                Error::<Test>::CollectionInUse
+            );
+
+            // A pending item has its escrow as owner; the transfer alone also blocks clearing
+            assert_ok!(send(1, 1, 1));
+            NFTOwners::<Test>::remove(1, 1);
+            NFTOwners::<Test>::remove(1, 5);
+            assert_noop!(
+                NftBridge::clear_collection_data(RuntimeOrigin::root(), 1, 10),
+                Error::<Test>::CollectionInUse
+            );
+        });
+    }
+
+    #[test]
+    fn delivery_fee_spend_accumulates_per_destination() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            DeliveryFee::set(25);
+            Balances::make_free_balance_be(&1, 200);
+            let send_to = |item_id: u32, dest_para_id: u32| {
+                NFTOwners::<Test>::insert(1, item_id, 1);
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item_id,
+                    dest_para_id,
+                    vec![],
+                    None,
+                    None,
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+
+            assert_ok!(send_to(1, 2000));
+            System::assert_last_event(
+                Event::NFTSent {
+                    collection_id: 1,
+                    item_id: 1,
+                    dest_para_id: 2000,
+                    fee: 0,
+                    delivery_fee: 25,
+                    canonical_id: NftBridge::canonical_id(1, 1),
+                }
+                .into(),
+            );
+            assert_ok!(send_to(2, 2000));
+            assert_ok!(send_to(3, 3000));
+            assert_eq!(NftBridge::delivery_fee_spend(2000), 50);
+            assert_eq!(NftBridge::bridge_stats().delivery_fee_spend, vec![(2000, 50), (3000, 25)]);
+
+            // Counters restart at an accounting period boundary
+            assert_noop!(
+                NftBridge::reset_delivery_fee_spend(RuntimeOrigin::signed(1), None),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(NftBridge::reset_delivery_fee_spend(RuntimeOrigin::root(), Some(2000)));
+            assert_eq!(NftBridge::bridge_stats().delivery_fee_spend, vec![(3000, 25)]);
+            assert_ok!(NftBridge::reset_delivery_fee_spend(RuntimeOrigin::root(), None));
+            assert!(NftBridge::bridge_stats().delivery_fee_spend.is_empty());
+            System::assert_last_event(Event::DeliveryFeeSpendReset { dest_para_id: None }.into());
+        });
+    }
+
+    #[test]
+    fn pool_validation_rejects_sends_that_would_fail() {
+        use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction};
+        new_test_ext().execute_with(|| {
+            let send_call = |collection_id: u32| {
+                RuntimeCall::NftBridge(crate::Call::send_nft {
+                    collection_id,
+                    item_id: 1,
+                    dest_para_id: 2000,
+                    metadata: vec![],
+                    metadata_uri: None,
+                    remote_call: None,
+                    execution_limit: ExecutionLimit::Default,
+                    priority: false,
+                    metadata_uris: vec![],
+                    valid_until: None,
+                })
+            };
+            let validate = |call: &RuntimeCall| {
+                CheckBridgeAvailable::<Test>::new().validate(&1, call, &Default::default(), 0)
+            };
+            assert_eq!(
+                validate(&send_call(1)),
+                Err(InvalidTransaction::Custom(extension::DESTINATION_NOT_REGISTERED).into())
+            );
+            let config = Some(DestinationConfig::default());
+            assert_ok!(NftBridge::set_destination_config(RuntimeOrigin::root(), 2000, config));
+            assert_ok!(validate(&send_call(1)));
+
+            assert_ok!(NftBridge::set_bridge_paused(RuntimeOrigin::root(), true));
+            assert_eq!(
+                validate(&send_call(1)),
+                Err(InvalidTransaction::Custom(extension::BRIDGE_PAUSED).into())
+            );
+            // Other calls are untouched by the pause
+            let other = RuntimeCall::NftBridge(crate::Call::set_bridge_paused { paused: false });
+            assert_ok!(validate(&other));
+            assert_eq!(send(1, 1, 1), Err(Error::<Test>::BridgePaused.into()));
+            assert_eq!(
+                NftBridge::validate_send(1, 1, 1, 2000, 0, 0).map(|_| ()),
+                Err(Error::<Test>::BridgePaused.into())
+            );
+            assert_ok!(NftBridge::set_bridge_paused(RuntimeOrigin::root(), false));
+
+            Permissionless::set(false);
+            assert_eq!(
+                validate(&send_call(1)),
+                Err(InvalidTransaction::Custom(extension::COLLECTION_NOT_REGISTERED).into())
+            );
+            let batch_call = RuntimeCall::NftBridge(crate::Call::send_nfts {
+                items: batch(&[(1, 1), (2, 1)]),
+                dest_para_id: 2000,
+                mode: BatchMode::Bundle,
+                valid_until: None,
+            });
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, None, 3));
+            assert_ok!(validate(&send_call(1)));
+            assert_eq!(
+                validate(&batch_call),
+                Err(InvalidTransaction::Custom(extension::COLLECTION_NOT_REGISTERED).into())
+            );
+
+            // A lane still waiting for the partner's handshake doesn't take sends yet
+            let capabilities = LaneCapabilities { remote_calls: false, metadata_updates: false };
+            let lane = lane_config(capabilities);
+            assert_ok!(NftBridge::open_lane(RuntimeOrigin::root(), 3000, lane));
+            let to_lane = RuntimeCall::NftBridge(crate::Call::send_staged_nft {
+                collection_id: 1,
+                item_id: 1,
+                dest_para_id: 3000,
+                valid_until: None,
+            });
+            assert_eq!(
+                validate(&to_lane),
+                Err(InvalidTransaction::Custom(extension::DESTINATION_NOT_REGISTERED).into())
+            );
+            assert_ok!(NftBridge::confirm_lane(RuntimeOrigin::signed(3000), capabilities));
+            assert_ok!(validate(&to_lane));
+        });
+    }
+
+    #[test]
+    fn senders_are_limited_in_pending_transfers() {
+        use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction};
+        new_test_ext().execute_with(|| {
+            MaxPendingPerAccount::set(2);
+            let config = Some(DestinationConfig::default());
+            assert_ok!(NftBridge::set_destination_config(RuntimeOrigin::root(), 2000, config));
+            assert_ok!(send(1, 1, 1));
+            assert_ok!(send(1, 1, 2));
+            assert_eq!(PendingPerAccount::<Test>::get(1), 2);
+
+            let batch_call = RuntimeCall::NftBridge(crate::Call::send_nfts {
+                items: batch(&[(1, 3)]),
+                dest_para_id: 2000,
+                mode: BatchMode::Bundle,
+                valid_until: None,
+            });
+            assert_eq!(
+                CheckBridgeAvailable::<Test>::new()
+                    .validate(&1, &batch_call, &Default::default(), 0),
+                Err(InvalidTransaction::Custom(extension::TOO_MANY_PENDING).into())
+            );
+            assert_eq!(send(1, 1, 3), Err(Error::<Test>::TooManyPending.into()));
+            assert_eq!(
+                NftBridge::validate_send(1, 1, 3, 2000, 0, 0).map(|_| ()),
+                Err(Error::<Test>::TooManyPending.into())
+            );
+            // Another sender has their own allowance
+            assert_ok!(send(2, 1, 3));
+
+            reject(1, 1);
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 1));
+            assert_eq!(PendingPerAccount::<Test>::get(1), 1);
+            assert_ok!(send(1, 1, 4));
+        });
+    }
+
+    #[test]
+    fn unresolvable_beneficiaries_leave_the_item_with_the_source_chain() {
+        use xcm_executor::traits::TransactAsset;
+        new_test_ext().execute_with(|| {
+            let asset = NftBridge::nft_asset(5, 1).unwrap();
+            let unknown = MultiLocation::new(0, X1(AccountId32 { network: None, id: [7; 32] }));
+            let from_sibling = XcmContext {
+                origin: Some(MultiLocation::new(1, X1(Parachain(2000)))),
+                message_hash: [0; 32],
+                topic: None,
+            };
+
+            // Without a sibling to fall back on the deposit still fails
+            let context = XcmContext { origin: None, message_hash: [0; 32], topic: None };
+            assert!(TestNftTransactor::deposit_asset(&asset, &unknown, &context).is_err());
+            assert_eq!(NftBridge::owner(5, 1), None);
+
+            assert_ok!(TestNftTransactor::deposit_asset(&asset, &unknown, &from_sibling));
+            let sovereign = SOVEREIGN_BASE + 2000;
+            assert_eq!(NftBridge::owner(5, 1), Some(sovereign));
+            assert_eq!(
+                NftBridge::orphaned_inbound(5, 1),
+                Some(OrphanedNft { from_para_id: 2000, holder: sovereign, beneficiary: unknown })
+            );
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::InboundOrphaned {
+                collection_id: 5,
+                item_id: 1,
+                from_para_id: 2000,
+                beneficiary: unknown,
+            }));
+
+            assert_noop!(
+                NftBridge::reassign_orphaned(RuntimeOrigin::signed(sovereign), 5, 1, 4),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_noop!(
+                NftBridge::reassign_orphaned(RuntimeOrigin::root(), 5, 2, 4),
+                Error::<Test>::NotOrphaned
+            );
+            assert_ok!(NftBridge::reassign_orphaned(RuntimeOrigin::root(), 5, 1, 4));
+            assert_eq!(NftBridge::owner(5, 1), Some(4));
+            assert_eq!(NftBridge::orphaned_inbound(5, 1), None);
+
+            // The new owner holds it like any other item
+            let who = AccountToLocation::convert(4);
+            assert_ok!(TestNftTransactor::withdraw_asset(&asset, &who, None));
+            assert_eq!(NftBridge::owner(5, 1), None);
+        });
+    }
+
+    #[test]
+    fn orphans_that_left_the_sovereign_account_cannot_be_reassigned() {
+        use xcm_executor::traits::TransactAsset;
+        new_test_ext().execute_with(|| {
+            let asset = NftBridge::nft_asset(5, 1).unwrap();
+            let unknown = MultiLocation::new(0, X1(AccountId32 { network: None, id: [7; 32] }));
+            let sibling = MultiLocation::new(1, X1(Parachain(2000)));
+            let context = XcmContext { origin: Some(sibling), message_hash: [0; 32], topic: None };
+            assert_ok!(TestNftTransactor::deposit_asset(&asset, &unknown, &context));
+
+            NFTOwners::<Test>::insert(5, 1, 9);
+            assert_noop!(
+                NftBridge::reassign_orphaned(RuntimeOrigin::root(), 5, 1, 4),
+                Error::<Test>::OrphanNoLongerHeld
+            );
+
+            // The source chain withdrawing it settles the orphan
+            NFTOwners::<Test>::insert(5, 1, SOVEREIGN_BASE + 2000);
+            assert_ok!(TestNftTransactor::withdraw_asset(&asset, &sibling, None));
+            assert_eq!(NftBridge::orphaned_inbound(5, 1), None);
+        });
+    }
+
+    #[test]
+    fn canonical_ids_survive_a_round_trip() {
+        new_test_ext().execute_with(|| {
+            let id = NftBridge::canonical_id(1, 1);
+            assert_eq!(id.to_vec(), b"polkadot:1000/01000000/01000000".to_vec());
+            assert_eq!(NftBridge::canonical_ids(1, 1), None);
+
+            assert_ok!(send(1, 1, 1));
+            assert_eq!(NftBridge::canonical_ids(1, 1), Some(id.clone()));
+            assert_eq!(NftBridge::item_by_canonical_id(BlakeTwo256::hash(&id)), Some((1, 1)));
+
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                1,
+                2000,
+                1,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::NFTReceived {
+                collection_id: 1,
+                item_id: 1,
+                from_para_id: 2000,
+                canonical_id: id.clone(),
+            }));
+            assert_eq!(NftBridge::canonical_id(1, 1), id);
+        });
+    }
+
+    #[test]
+    fn wrappers_are_identified_by_their_source_chain() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                7,
+                2000,
+                2,
+                b"test_metadata".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            let id = NftBridge::canonical_ids(1, 7).unwrap();
+            assert_eq!(id.to_vec(), b"polkadot:2000/01000000/07000000".to_vec());
+
+            // Fixed once given, even if the chain's view of its network changes
+            RelayNetwork::set(NetworkId::ByGenesis([0xab; 32]));
+            assert_eq!(NftBridge::canonical_id(1, 7), id);
+            assert_eq!(
+                NftBridge::canonical_id(1, 8).to_vec(),
+                b"abababababababababababababababab:1000/01000000/08000000".to_vec()
+            );
+        });
+    }
+
+    fn lane_config(capabilities: LaneCapabilities) -> LaneConfig {
+        LaneConfig {
+            capabilities,
+            destination: DestinationConfig { max_in_flight: Some(4), ..Default::default() },
+            protocol_version: Some(2),
+            xcm_version: Some(3),
+            reserve: None,
+        }
+    }
+
+    #[test]
+    fn lanes_go_through_open_confirm_use_and_close() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            UntrustedSources::set(vec![3000]);
+            let ours = LaneCapabilities { remote_calls: true, metadata_updates: true };
+            let theirs = LaneCapabilities { remote_calls: false, metadata_updates: true };
+            let send_to_3000 = |item_id: u32, remote_call: Option<Vec<u8>>| {
+                NFTOwners::<Test>::insert(1, item_id, 1);
+                NftBridge::send_nft(
+                    RuntimeOrigin::signed(1),
+                    1,
+                    item_id,
+                    3000,
+                    vec![],
+                    None,
+                    remote_call.map(|call| call.try_into().unwrap()),
+                    ExecutionLimit::Default,
+                    false,
+                    vec![],
+                    None,
+                )
+            };
+            let receive_from_3000 = |item_id: u32| {
+                receive(Delivery { from: 3000, collection_id: 9, item_id, ..Default::default() })
+            };
+
+            // Opening configures the partner and sends the handshake
+            assert_ok!(NftBridge::open_lane(RuntimeOrigin::root(), 3000, lane_config(ours)));
+            assert_eq!(
+                NftBridge::lane(3000),
+                Some(Lane { status: LaneStatus::Pending, capabilities: ours })
+            );
+            assert_eq!(NftBridge::destination_config(3000).unwrap().max_in_flight, Some(4));
+            assert_eq!(NftBridge::partner_protocol_version(3000), Some(2));
+            assert_eq!(NftBridge::destination_xcm_version(3000), Some(3));
+            assert_eq!(last_bridge_call(3000), crate::Call::confirm_lane { capabilities: ours });
+            assert_noop!(
+                NftBridge::open_lane(RuntimeOrigin::root(), 3000, lane_config(ours)),
+                Error::<Test>::LaneAlreadyOpen
+            );
+
+            // Not usable either way until the partner answers
+            assert_eq!(send_to_3000(1, None), Err(Error::<Test>::LaneNotActive.into()));
+            assert_eq!(receive_from_3000(1), Err(Error::<Test>::UntrustedSourceChain.into()));
+
+            let sent = sent_xcm().len();
+            assert_ok!(NftBridge::confirm_lane(RuntimeOrigin::signed(3000), theirs));
+            let agreed = ours.intersect(theirs);
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::LaneActivated {
+                para_id: 3000,
+                capabilities: agreed,
+            }));
+            // Answered once, so a partner that opened first activates too
+            assert_eq!(sent_xcm().len(), sent + 1);
+            assert_eq!(last_bridge_call(3000), crate::Call::confirm_lane { capabilities: ours });
+
+            assert_ok!(send_to_3000(1, None));
+            assert!(MetadataSubscribers::<Test>::contains_key(1, 3000));
+            assert_eq!(
+                send_to_3000(2, Some(vec![0])),
+                Err(Error::<Test>::RemoteCallsDisabled.into())
+            );
+            assert_ok!(receive_from_3000(1));
+
+            // Closing waits for the pending transfer
+            assert_noop!(
+                NftBridge::close_lane(RuntimeOrigin::root(), 3000),
+                Error::<Test>::LaneInUse
+            );
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(3000), 1, 1));
+            assert_ok!(NftBridge::close_lane(RuntimeOrigin::root(), 3000));
+            assert_eq!(NftBridge::lane(3000), None);
+            assert_eq!(NftBridge::destination_config(3000), None);
+            assert_eq!(NftBridge::partner_protocol_version(3000), None);
+            assert_eq!(NftBridge::destination_xcm_version(3000), None);
+            assert_eq!(receive_from_3000(2), Err(Error::<Test>::UntrustedSourceChain.into()));
+        });
+    }
+
+    #[test]
+    fn lane_handshakes_are_not_answered_twice() {
+        new_test_ext().execute_with(|| {
+            let caps = LaneCapabilities { remote_calls: true, metadata_updates: false };
+            assert_noop!(
+                NftBridge::confirm_lane(RuntimeOrigin::signed(3000), caps),
+                Error::<Test>::LaneNotFound
+            );
+
+            assert_ok!(NftBridge::open_lane(RuntimeOrigin::root(), 3000, lane_config(caps)));
+            assert_ok!(NftBridge::confirm_lane(RuntimeOrigin::signed(3000), caps));
+            let sent = sent_xcm().len();
+            assert_ok!(NftBridge::confirm_lane(RuntimeOrigin::signed(3000), caps));
+            assert_eq!(sent_xcm().len(), sent);
+            assert_eq!(NftBridge::lane(3000).unwrap().status, LaneStatus::Active);
+
+            // A lane without metadata updates doesn't subscribe its partner
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_ok!(NftBridge::send_nft(
+                RuntimeOrigin::signed(1),
+                1,
+                1,
+                3000,
+                vec![],
+                None,
+                None,
+                ExecutionLimit::Default,
+                false,
+                vec![],
+                None,
+            ));
+            assert!(!MetadataSubscribers::<Test>::contains_key(1, 3000));
+        });
+    }
+
+    #[test]
+    fn state_export_pages_reassemble_into_storage() {
+        use sp_std::collections::btree_map::BTreeMap;
+        new_test_ext().execute_with(|| {
+            for item_id in 1..=4 {
+                NFTOwners::<Test>::insert(1, item_id, 7);
+                NFTMetadata::<Test>::insert(1, item_id, vec![item_id as u8]);
+            }
+            NFTOwners::<Test>::insert(2, 1, 8);
+            assert_ok!(send(1, 3, 1));
+            assert_ok!(send(1, 3, 2));
+
+            let mut owners = BTreeMap::new();
+            let mut pending = BTreeMap::new();
+            let mut metadata = BTreeMap::new();
+            let (mut cursor, mut pages) = (None, 0);
+            loop {
+                let (page, next) = NftBridge::export_state(cursor, 3);
+                assert!(page.len() <= 3);
+                pages += 1;
+                for entry in page {
+                    match entry {
+                        BridgeStateEntry::Owner { collection_id, item_id, owner } => {
+                            assert!(owners.insert((collection_id, item_id), owner).is_none())
+                        },
+                        BridgeStateEntry::Pending { collection_id, item_id, transfer } => {
+                            assert!(pending.insert((collection_id, item_id), transfer).is_none())
+                        },
+                        BridgeStateEntry::Metadata { collection_id, item_id, metadata: m } => {
+                            assert!(metadata.insert((collection_id, item_id), m).is_none())
+                        },
+                    }
+                }
+                cursor = next;
+                if cursor.is_none() {
+                    break
+                }
+            }
+
+            assert_eq!(owners, NFTOwners::<Test>::iter().map(|(c, i, o)| ((c, i), o)).collect());
+            assert_eq!(
+                pending,
+                PendingTransfers::<Test>::iter().map(|(c, i, t)| ((c, i), t)).collect()
+            );
+            assert_eq!(
+                metadata,
+                NFTMetadata::<Test>::iter().map(|(c, i, m)| ((c, i), m)).collect()
+            );
+            assert_eq!(pending.keys().copied().collect::<Vec<_>>(), vec![(3, 1), (3, 2)]);
+            assert!(pages > 3);
+        });
+    }
+
+    #[test]
+    fn state_export_is_bounded_and_rejects_foreign_cursors() {
+        new_test_ext().execute_with(|| {
+            for item_id in 0..(MAX_EXPORT_STATE_PAGE + 5) {
+                NFTOwners::<Test>::insert(1, item_id, 7);
+            }
+            let (page, next) = NftBridge::export_state(None, u32::MAX);
+            assert_eq!(page.len(), MAX_EXPORT_STATE_PAGE as usize);
+            let (rest, next) = NftBridge::export_state(next, u32::MAX);
+            assert_eq!(rest.len(), 5);
+            assert_eq!(next, None);
+
+            assert_eq!(NftBridge::export_state(Some(b"elsewhere".to_vec()), 10), (vec![], None));
+        });
+    }
+
+    #[test]
+    fn inbound_nfts_need_some_provenance() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let deliver = |item_id: u32, metadata: Vec<u8>, uri: Option<&[u8]>| {
+                receive(Delivery {
+                    item_id,
+                    metadata,
+                    metadata_uri: uri.map(|uri| uri.to_vec()),
+                    ..Default::default()
+                })
+            };
+
+            // URI only
+            assert_ok!(deliver(1, vec![], Some(b"ipfs://item")));
+            assert_eq!(NftBridge::nft_metadata_uri(1, 1), Some(b"ipfs://item".to_vec()));
+            assert_eq!(NftBridge::nft_metadata(1, 1), Some(vec![]));
+
+            // Metadata only
+            assert_ok!(deliver(2, b"test_metadata".to_vec(), None));
+            assert_eq!(NftBridge::nft_metadata(1, 2), Some(b"test_metadata".to_vec()));
+            assert_eq!(NftBridge::nft_metadata_uri(1, 2), None);
+
+            // Neither, and nothing known here
+            assert_eq!(deliver(3, vec![], None), Err(Error::<Test>::MissingProvenance.into()));
+        });
+    }
+
+    #[test]
+    fn returning_nfts_without_metadata_keep_their_record() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(send(1, 1, 1));
+            NFTMetadata::<Test>::insert(1, 1, b"kept".to_vec());
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                1,
+                2000,
+                1,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None
+            ));
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+            assert_eq!(NftBridge::nft_metadata(1, 1), Some(b"kept".to_vec()));
+        });
+    }
+
+    #[test]
+    fn failed_inbound_deliveries_are_recorded() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(5);
+            let deliver = |item_id: u32, metadata_hash: Option<H256>| {
+                receive(Delivery { item_id, metadata_hash, ..Default::default() })
+            };
+            let failure = |item_id: u32, error: Error<Test>, at: u64| InboundFailure {
+                collection_id: 1,
+                item_id,
+                reason: error.into(),
+                at,
+            };
+
+            UntrustedSources::set(vec![2000]);
+            assert_eq!(deliver(1, None), Err(Error::<Test>::UntrustedSourceChain.into()));
+            UntrustedSources::set(vec![]);
+            System::set_block_number(6);
+            let wrong_hash = Some(BlakeTwo256::hash(b"other"));
+            assert_eq!(deliver(2, wrong_hash), Err(Error::<Test>::MetadataHashMismatch.into()));
+
+            assert_eq!(
+                NftBridge::inbound_failures(2000),
+                vec![
+                    failure(1, Error::<Test>::UntrustedSourceChain, 5),
+                    failure(2, Error::<Test>::MetadataHashMismatch, 6),
+                ]
+            );
+            assert!(NftBridge::inbound_failures(3000).is_empty());
+            // The failed deliveries themselves left nothing behind
+            assert_eq!(NftBridge::owner(1, 1), None);
+            assert_eq!(NftBridge::owner(1, 2), None);
+
+            // The oldest record makes room for a new one
+            assert_ok!(deliver(3, None));
+            assert!(deliver(3, None).is_err());
+            let failures = NftBridge::inbound_failures(2000);
+            assert_eq!(failures.len(), 2);
+            assert_eq!(failures[0], failure(2, Error::<Test>::MetadataHashMismatch, 6));
+            assert_eq!(failures[1].item_id, 3);
+
+            assert_noop!(
+                NftBridge::clear_inbound_failures(RuntimeOrigin::signed(1), 2000),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(NftBridge::clear_inbound_failures(RuntimeOrigin::root(), 2000));
+            assert!(NftBridge::inbound_failures(2000).is_empty());
+        });
+    }
+
+    #[test]
+    fn bridge_authorities_pass_admin_origin_where_configured() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(OtherBridge::add_authority(RuntimeOrigin::root(), 5));
+            assert_ok!(NftBridge::add_authority(RuntimeOrigin::root(), 5));
+            assert_eq!(OtherBridge::bridge_authorities().to_vec(), vec![5]);
+
+            // OtherBridge's AdminOrigin accepts listed authorities
+            assert_ok!(OtherBridge::set_bridge_paused(RuntimeOrigin::signed(5), true));
+            assert!(OtherBridge::bridge_paused());
+            assert_noop!(
+                OtherBridge::set_bridge_paused(RuntimeOrigin::signed(6), false),
+                sp_runtime::DispatchError::BadOrigin
+            );
+
+            // NftBridge keeps plain root, whatever its authorities list holds
+            assert_noop!(
+                NftBridge::set_bridge_paused(RuntimeOrigin::signed(5), true),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(NftBridge::set_bridge_paused(RuntimeOrigin::root(), true));
+        });
+    }
+
+    #[test]
+    fn bridge_authorities_are_managed_by_root_and_never_emptied() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_noop!(
+                NftBridge::add_authority(RuntimeOrigin::signed(5), 5),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(NftBridge::add_authority(RuntimeOrigin::root(), 5));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::AuthorityAdded { who: 5 }));
+            assert_noop!(
+                NftBridge::add_authority(RuntimeOrigin::root(), 5),
+                Error::<Test>::AlreadyAuthority
+            );
+            assert_noop!(
+                NftBridge::remove_authority(RuntimeOrigin::root(), 5),
+                Error::<Test>::LastAuthority
+            );
+
+            assert_ok!(NftBridge::add_authority(RuntimeOrigin::root(), 6));
+            assert_ok!(NftBridge::add_authority(RuntimeOrigin::root(), 7));
+            assert_noop!(
+                NftBridge::add_authority(RuntimeOrigin::root(), 8),
+                Error::<Test>::TooManyAuthorities
+            );
+            assert_noop!(
+                NftBridge::remove_authority(RuntimeOrigin::root(), 8),
+                Error::<Test>::NotAuthority
+            );
+
+            assert_ok!(NftBridge::remove_authority(RuntimeOrigin::root(), 5));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::AuthorityRemoved { who: 5 }));
+            assert_eq!(NftBridge::bridge_authorities().to_vec(), vec![6, 7]);
+        });
+    }
+
+    #[test]
+    fn receives_past_max_supply_are_diverted_until_a_wrapper_returns() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let deliver = |item_id| {
+                receive(Delivery { item_id, metadata: b"meta".to_vec(), ..Default::default() })
+            };
+            assert_ok!(NftBridge::register_collection(RuntimeOrigin::root(), 1, Some(2000), 3));
+            assert_noop!(
+                NftBridge::set_collection_max_supply(RuntimeOrigin::signed(4), 1, Some(2)),
+                Error::<Test>::NotCollectionAdmin
+            );
+            assert_ok!(NftBridge::set_collection_max_supply(RuntimeOrigin::signed(3), 1, Some(2)));
+            assert_eq!(NftBridge::collection_info(1).unwrap().max_supply, Some(2));
+
+            assert_ok!(deliver(1));
+            assert_ok!(deliver(2));
+            assert_eq!(NftBridge::wrapped_supply(1), 2);
+
+            // The third is parked with the source chain's sovereign account instead of credited
+            assert_ok!(deliver(3));
+            let sovereign = SOVEREIGN_BASE + 2000;
+            assert_eq!(NftBridge::owner(1, 3), Some(sovereign));
+            assert_eq!(NftBridge::wrapped_supply(1), 2);
+            assert_eq!(
+                NftBridge::orphaned_inbound(1, 3),
+                Some(OrphanedNft {
+                    from_para_id: 2000,
+                    holder: sovereign,
+                    beneficiary: AccountToLocation::convert(2),
+                })
+            );
+            assert_ok!(NftBridge::do_try_state());
+            let failures = NftBridge::inbound_failures(2000);
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].reason, Error::<Test>::CollectionSupplyExceeded.into());
+            assert_eq!(NftBridge::nft_metadata(1, 3), Some(b"meta".to_vec()));
+
+            assert_noop!(
+                NftBridge::reassign_orphaned(RuntimeOrigin::root(), 1, 3, 2),
+                Error::<Test>::CollectionSupplyExceeded
+            );
+
+            // A wrapper going back to its reserve frees room for the next one
+            assert_ok!(send(2, 1, 1));
+            assert_eq!(NftBridge::wrapped_supply(1), 2);
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert_eq!(NftBridge::wrapped_supply(1), 1);
+            assert_ok!(deliver(4));
+            assert_eq!(NftBridge::owner(1, 4), Some(2));
+            assert_eq!(NftBridge::wrapped_supply(1), 2);
+
+            // Once there is room, governance hands the parked item to its recipient
+            assert_ok!(NftBridge::on_item_burned(1, 2));
+            assert_eq!(NftBridge::wrapped_supply(1), 1);
+            assert_ok!(NftBridge::reassign_orphaned(RuntimeOrigin::root(), 1, 3, 2));
+            assert_eq!(NftBridge::owner(1, 3), Some(2));
+            assert_eq!(NftBridge::wrapped_supply(1), 2);
+            assert_ok!(NftBridge::do_try_state());
+        });
+    }
+
+    #[test]
+    fn burned_wrappers_leave_the_supply_and_native_items_never_join() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let deliver = |item_id| {
+                receive(Delivery { item_id, metadata: b"meta".to_vec(), ..Default::default() })
+            };
+            assert_ok!(deliver(1));
+            assert_ok!(deliver(2));
+            assert_eq!(NftBridge::wrapped_supply(1), 2);
+
+            assert_ok!(NftBridge::on_item_burned(1, 1));
+            assert_eq!(NftBridge::wrapped_supply(1), 1);
+
+            // Native items don't count
+            assert_ok!(send(5, 1, 7));
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 7));
+            assert_ok!(deliver(7));
+            assert_eq!(NftBridge::wrapped_supply(1), 1);
+        });
+    }
+
+    #[test]
+    fn refused_deliveries_are_reported_and_cancellable_right_away() {
+        use frame_support::traits::UnfilteredDispatchable;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            // This chain sends to 2000, played by the second instance
+            assert_ok!(send(1, 1, 1));
+            let transfer_id = NftBridge::pending_transfer(1, 1).unwrap().id;
+            assert_ok!(OtherBridge::set_inbound_opt_out(RuntimeOrigin::signed(2), true));
+            assert_noop!(
+                NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 1),
+                Error::<Test>::TransferInFlight
+            );
+
+            assert_ok!(OtherBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                1,
+                2000,
+                2,
+                b"meta".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                Some(transfer_id),
+                None,
+            ));
+            let refused = OtherBridge::inbound_failures(2000);
+            assert_eq!(refused[0].reason, Error::<Test, Instance2>::RecipientOptedOut.into());
+            let (_, report) = sent_xcm().last().cloned().unwrap();
+            let fees: MultiAsset = (Parent, RejectionReportFee::get()).into();
+            assert_eq!(report.0[0], WithdrawAsset(fees.into()));
+            let code = RejectionCode::RecipientOptedOut;
+            let call = last_bridge_call(2000);
+            assert_eq!(call, crate::Call::report_rejection { transfer_id, code });
+
+            // The report arrives back here as a call from 2000
+            assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::signed(2000)));
+            let transfer = NftBridge::pending_transfer(1, 1).unwrap();
+            assert_eq!(transfer.status, TransferStatus::Rejected { code });
+            System::assert_last_event(RuntimeEvent::NftBridge(
+                crate::Event::NFTTransferRejected {
+                    collection_id: 1,
+                    item_id: 1,
+                    transfer_id,
+                    code,
+                },
+            ));
+
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 1));
+            assert_eq!(NftBridge::owner(1, 1), Some(1));
+        });
+    }
+
+    #[test]
+    fn replayed_deliveries_are_refused_without_a_report() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(send(1, 1, 1));
+            let transfer_id = NftBridge::pending_transfer(1, 1).unwrap().id;
+            let deliver = |nonce| {
+                OtherBridge::receive_nft(
+                    RuntimeOrigin::signed(2000),
+                    1,
+                    1,
+                    2000,
+                    2,
+                    b"meta".to_vec(),
+                    None,
+                    nonce,
+                    None,
+                    None,
+                    vec![],
+                    None,
+                    None,
+                    Some(transfer_id),
+                    None,
+                )
+            };
+            assert_ok!(deliver(Some(1)));
+            assert_eq!(OtherBridge::owner(1, 1), Some(2));
+            let sent = sent_xcm().len();
+
+            // A late duplicate of the delivered transfer, and a replay of its nonce, are
+            // recorded but must not hand the NFT back to its sender
+            assert_ok!(deliver(Some(1)));
+            assert_ok!(deliver(Some(2)));
+            let reasons: Vec<_> = OtherBridge::inbound_failures(2000)
+                .into_iter()
+                .map(|failure| failure.reason)
+                .collect();
+            assert_eq!(
+                reasons,
+                vec![
+                    Error::<Test, Instance2>::InvalidNonce.into(),
+                    Error::<Test, Instance2>::NFTAlreadyExists.into()
+                ]
+            );
+            assert_eq!(sent_xcm().len(), sent);
+            assert_eq!(NftBridge::pending_transfer(1, 1).unwrap().status, TransferStatus::InFlight);
+        });
+    }
+
+    #[test]
+    fn only_the_destination_may_reject_a_transfer() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(send(1, 1, 1));
+            let transfer_id = NftBridge::pending_transfer(1, 1).unwrap().id;
+            let code = RejectionCode::Other;
+            assert_noop!(
+                NftBridge::report_rejection(RuntimeOrigin::signed(2001), transfer_id, code),
+                Error::<Test>::OriginMismatch
+            );
+            assert_noop!(
+                NftBridge::report_rejection(RuntimeOrigin::signed(2000), transfer_id + 1, code),
+                Error::<Test>::TransferNotRejectable
+            );
+            // A transfer already refused, or since delivered, can't be refused again
+            assert_ok!(NftBridge::report_rejection(RuntimeOrigin::signed(2000), transfer_id, code));
+            assert_noop!(
+                NftBridge::report_rejection(RuntimeOrigin::signed(2000), transfer_id, code),
+                Error::<Test>::TransferNotRejectable
+            );
+            assert_ok!(send(1, 1, 2));
+            let delivered = NftBridge::pending_transfer(1, 2).unwrap().id;
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 2));
+            assert_noop!(
+                NftBridge::report_rejection(RuntimeOrigin::signed(2000), delivered, code),
+                Error::<Test>::TransferNotRejectable
+            );
+            assert_eq!(
+                NftBridge::rejection_code(Error::<Test>::InvalidLockProof.into()),
+                RejectionCode::InvalidLockProof
+            );
+            assert_eq!(
+                NftBridge::rejection_code(sp_runtime::DispatchError::BadOrigin),
+                RejectionCode::Other
+            );
+        });
+    }
+
+    #[test]
+    fn deliveries_older_than_max_inbound_age_are_refused() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let deliver = |item_id, sent_at| {
+                receive(Delivery {
+                    item_id,
+                    metadata: b"meta".to_vec(),
+                    sent_at,
+                    transfer_id: Some(item_id as TransferId),
+                    ..Default::default()
+                })
+            };
+            assert_ok!(deliver(1, Some(UnixNow::get() - 3_600)));
+            assert_ok!(deliver(2, None));
+            assert_eq!(
+                deliver(3, Some(UnixNow::get() - 3_601)),
+                Err(Error::<Test>::MessageTooOld.into())
+            );
+            assert_eq!(NftBridge::owner(1, 3), None);
+            let code = RejectionCode::MessageTooOld;
+            assert_eq!(
+                last_bridge_call(2000),
+                crate::Call::report_rejection { transfer_id: 3, code }
+            );
+
+            // A delivery stamped by a clock ahead of ours isn't old
+            assert_ok!(deliver(4, Some(UnixNow::get() + 60)));
+        });
+    }
+
+    #[test]
+    fn zero_max_inbound_age_accepts_any_delivery() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(OtherBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                1,
+                2000,
+                2,
+                b"meta".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                Some(0),
+                None,
+                None,
+            ));
+            assert_eq!(OtherBridge::owner(1, 1), Some(2));
+        });
+    }
+
+    #[test]
+    fn period_stats_are_emitted_at_period_boundaries() {
+        use frame_support::traits::Hooks;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let stats = || {
+                System::events()
+                    .into_iter()
+                    .filter_map(|record| match record.event {
+                        RuntimeEvent::NftBridge(event @ crate::Event::PeriodStats { .. }) => {
+                            Some(event)
+                        },
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            // First period: two sent, one received
+            assert_ok!(send(1, 1, 1));
+            assert_ok!(send(1, 1, 2));
+            assert_ok!(NftBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                5,
+                2000,
+                2,
+                b"meta".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+            ));
+            NftBridge::on_initialize(5);
+            assert!(stats().is_empty());
+            NftBridge::on_initialize(10);
+            assert_eq!(
+                stats(),
+                vec![crate::Event::PeriodStats {
+                    sent: 2,
+                    received: 1,
+                    cancelled: 0,
+                    expired: 0,
+                    pending: 2,
+                }]
+            );
+
+            // Second period: one cancelled, one expired
+            System::set_block_number(11);
+            reject(1, 1);
+            reject(1, 2);
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 1));
+            NftBridge::on_idle(1001, Weight::MAX);
+            NftBridge::on_initialize(1010);
+            assert_eq!(
+                stats()[1],
+                crate::Event::PeriodStats {
+                    sent: 0,
+                    received: 0,
+                    cancelled: 1,
+                    expired: 1,
+                    pending: 0,
+                }
+            );
+
+            assert_eq!(NftBridge::period_counters(), BridgeCounters::default());
+            assert_eq!(
+                NftBridge::lifetime_counters(),
+                BridgeCounters { sent: 2, received: 1, cancelled: 1, expired: 1 }
+            );
+        });
+    }
+
+    #[test]
+    fn zero_stats_period_emits_nothing() {
+        use frame_support::traits::Hooks;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_eq!(OtherBridge::on_initialize(10), Weight::zero());
+            assert!(System::events().is_empty());
+        });
+    }
+
+    #[test]
+    fn bridge_locks_and_external_escrows_are_isolated() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let rentals = EscrowReason::External(*b"rentals_");
+            NFTOwners::<Test>::insert(1, 2, 1);
+            let escrow_id = <NftBridge as NonfungibleEscrow<_, _, _>>::escrow(1, 1, 2, rentals);
+            assert_eq!(escrow_id, Ok(0));
+            assert_ok!(send(1, 1, 1));
+
+            // Neither side can take the other's item
+            assert_noop!(send(1, 1, 2), Error::<Test>::EscrowedElsewhere);
+            assert_noop!(
+                <NftBridge as NonfungibleEscrow<_, _, _>>::escrow(1, 1, 1, rentals),
+                Error::<Test>::EscrowedElsewhere
+            );
+            assert_eq!(NftBridge::escrow_reason_of(1, 1), Some(EscrowReason::Bridge));
+            assert_eq!(NftBridge::escrow_reason_of(1, 2), Some(rentals));
+            assert!(NftBridge::is_bridge_locked(&1, &2));
+
+            // Releasing the escrow leaves the bridge lock alone
+            assert_ok!(<NftBridge as NonfungibleEscrow<_, _, _>>::release(0, 3));
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::EscrowReleased {
+                escrow_id: 0,
+                collection_id: 1,
+                item_id: 2,
+                to: 3,
+            }));
+            assert_eq!(NftBridge::owner(1, 2), Some(3));
+            assert_eq!(NftBridge::escrow_reason_of(1, 2), None);
+            assert!(NftBridge::pending_transfer(1, 1).is_some());
+            assert_noop!(
+                <NftBridge as NonfungibleEscrow<_, _, _>>::release(0, 1),
+                Error::<Test>::EscrowNotFound
+            );
+            assert_ok!(send(3, 1, 2));
+        });
+    }
+
+    #[test]
+    fn only_owners_escrow_and_never_under_the_bridge_reason() {
+        new_test_ext().execute_with(|| {
+            NFTOwners::<Test>::insert(1, 1, 1);
+            assert_noop!(
+                <NftBridge as NonfungibleEscrow<_, _, _>>::escrow(1, 1, 1, EscrowReason::Bridge),
+                Error::<Test>::ReservedEscrowReason
+            );
+            assert_noop!(
+                <NftBridge as NonfungibleEscrow<_, _, _>>::escrow(
+                    2,
+                    1,
+                    1,
+                    EscrowReason::External(*b"lending_")
+                ),
+                Error::<Test>::NotOwner
+            );
+        });
+    }
+
+    #[test]
+    fn items_frozen_by_the_bridge_move_on_no_path() {
+        new_test_ext().execute_with(|| {
+            NFTOwners::<Test>::insert(1, 1, 1);
+            BridgeLocked::<Test>::insert(1, 1, ());
+            assert_noop!(send(1, 1, 1), Error::<Test>::TransferAlreadyPending);
+            assert_noop!(
+                NftBridge::transfer(RuntimeOrigin::signed(1), 1, 1, 2, None),
+                Error::<Test>::TransferAlreadyPending
+            );
+            assert_noop!(NftBridge::on_item_burned(1, 1), Error::<Test>::TransferAlreadyPending);
+
+            BridgeLocked::<Test>::remove(1, 1);
+            assert_ok!(NftBridge::transfer(RuntimeOrigin::signed(1), 1, 1, 2, None));
+        });
+    }
+
+    #[test]
+    fn transfers_in_flight_are_never_unlocked_by_the_source() {
+        use frame_support::traits::Hooks;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(send(1, 1, 1));
+            let escrow = NftBridge::escrow_account_for(2000);
+
+            // The message may still execute on 2000, so neither the sender nor the sweep may
+            // hand the NFT back
+            System::set_block_number(2_000);
+            assert_noop!(
+                NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 1),
+                Error::<Test>::TransferInFlight
+            );
+            NftBridge::on_idle(2_000, Weight::MAX);
+            assert_eq!(NftBridge::owner(1, 1), Some(escrow));
+            assert_eq!(
+                NftBridge::pending_transfer(1, 1).map(|t| t.status),
+                Some(TransferStatus::InFlight)
+            );
+
+            // A send that failed never reached the transport and may be taken back
+            FailSends::set(1);
+            assert_ok!(send(1, 1, 2));
+            assert!(matches!(
+                NftBridge::pending_transfer(1, 2).map(|t| t.status),
+                Some(TransferStatus::SendFailed { .. })
+            ));
+            assert_ok!(NftBridge::cancel_transfer(RuntimeOrigin::signed(1), 1, 2));
+            assert_eq!(NftBridge::owner(1, 2), Some(1));
+        });
+    }
+
+    #[test]
+    fn retry_sweep_resumes_where_it_stopped() {
+        new_test_ext().execute_with(|| {
+            type Weights = <Test as crate::Config>::WeightInfo;
+            System::set_block_number(1);
+            FailSends::set(3);
+            for item in 1..=3 {
+                assert_ok!(send(1, 1, item));
+            }
+            assert_eq!(FailedSends::<Test>::iter().count(), 3);
+
+            // Each sweep examines a single entry and the next one picks up after it
+            let budget = Weights::on_idle_retries(1);
+            for sent in 1..=3 {
+                assert!(NftBridge::retry_failed_sends(11, budget).all_lte(budget));
+                assert_eq!(sent_xcm().len(), sent);
+            }
+            assert_eq!(FailedSends::<Test>::iter().count(), 0);
+
+            // Past the end of the map the sweep starts over
+            NftBridge::retry_failed_sends(11, budget);
+            assert!(RetryCursor::<Test>::get().is_none());
+        });
+    }
+
+    #[test]
+    fn provenance_trails_are_bounded_compacted_and_carried_across() {
+        use frame_support::traits::fungible::InspectHold;
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            HistoryDepositPerHop::set(1);
+            Balances::make_free_balance_be(&2, 100);
+            let deposit = RuntimeHoldReason::NftBridge(HoldReason::HistoryDeposit);
+            let hop = |from_para_id| ProvenanceHop { from_para_id, received_at: UnixNow::get() };
+            let trail = |hops: Vec<ProvenanceHop>, elided| -> ProvenanceTrailOf<Test> {
+                ProvenanceTrail { hops: BoundedVec::truncate_from(hops), elided }
+            };
+
+            // Item 1 arrives from 2000 after passing through five other chains
+            let travelled: Vec<_> = (3000..3005).map(hop).collect();
+            let history = Some(trail(travelled.clone(), 0));
+            assert_ok!(receive(Delivery { history, ..Default::default() }));
+            let mut full = travelled.clone();
+            full.push(hop(2000));
+            assert_eq!(NftBridge::item_history(1, 1).unwrap().trail, trail(full.clone(), 0));
+            assert_eq!(Balances::balance_on_hold(&deposit, &2), 6);
+
+            // A full trail makes room by dropping its oldest hop after the first
+            let history = Some(trail(full, 0));
+            assert_ok!(receive(Delivery { item_id: 2, owner: 3, history, ..Default::default() }));
+            let pruned = [3000, 3002, 3003, 3004, 2000, 2000].into_iter().map(hop).collect();
+            assert_eq!(NftBridge::item_history(1, 2).unwrap().trail, trail(pruned, 1));
+            // Its recipient can't pay for it, and gets it all the same
+            assert_eq!(NftBridge::item_history(1, 2).unwrap().depositor, None);
+
+            // The owner cuts it down to the first hop and the latest two
+            assert_noop!(
+                NftBridge::compact_item_history(RuntimeOrigin::signed(1), 1, 1),
+                Error::<Test>::NotOwner
+            );
+            assert_ok!(NftBridge::compact_item_history(RuntimeOrigin::signed(2), 1, 1));
+            let compacted = trail(vec![hop(3000), hop(3004), hop(2000)], 3);
+            assert_eq!(NftBridge::item_history(1, 1).unwrap().trail, compacted);
+            assert_eq!(Balances::balance_on_hold(&deposit, &2), 3);
+            System::assert_last_event(RuntimeEvent::NftBridge(crate::Event::HistoryCompacted {
+                collection_id: 1,
+                item_id: 1,
+                elided: 3,
+                released: 3,
+            }));
+            assert_noop!(
+                NftBridge::compact_item_history(RuntimeOrigin::signed(2), 1, 1),
+                Error::<Test>::NothingToCompact
+            );
+
+            // Once the item leaves, its trail does too
+            assert_ok!(send(2, 1, 1));
+            assert_ok!(NftBridge::confirm_transfer(RuntimeOrigin::signed(2000), 1, 1));
+            assert_eq!(NftBridge::item_history(1, 1), None);
+            assert_eq!(Balances::balance_on_hold(&deposit, &2), 0);
+
+            // Relayed to the destination, played by the second instance, it gains only the hop
+            // just made and keeps its elided count
+            assert_ok!(OtherBridge::receive_nft(
+                RuntimeOrigin::signed(2000),
+                1,
+                1,
+                2000,
+                2,
+                b"meta".to_vec(),
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                None,
+                None,
+                Some(compacted),
+            ));
+            let carried = vec![hop(3000), hop(3004), hop(2000), hop(2000)];
+            assert_eq!(OtherBridge::item_history(1, 1).unwrap().trail, trail(carried, 3));
+        });
+    }
+}
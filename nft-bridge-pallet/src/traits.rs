@@ -0,0 +1,220 @@
+//! Traits other pallets use to integrate with the NFT bridge
+
+use crate::{BuildError, Config, EscrowId, EscrowReason, TransferId, TransferParams};
+use codec::Decode;
+use frame_support::traits::tokens::nonfungibles::{Inspect, Mutate, Transfer};
+use sp_runtime::{
+	traits::{Hash, TrailingZeroInput},
+	DispatchError, DispatchResult,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+use xcm::{
+	v3::{MultiLocation, SendError, SendResult, SendXcm, Xcm, XcmHash},
+	VersionedXcm,
+};
+
+/// Reacts to an NFT crossing the bridge.
+///
+/// Hooks run after the transfer has been committed and cannot revert it.
+pub trait OnNftBridged<T: Config<I>, I: 'static = ()> {
+	/// Called once the NFT has been sent to, or received from, `counterpart_para`.
+	/// `account` is the sender for outbound transfers and the recipient for inbound ones.
+	fn on_bridged(
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		counterpart_para: u32,
+		account: &T::AccountId,
+	);
+}
+
+impl<T: Config<I>, I: 'static> OnNftBridged<T, I> for () {
+	fn on_bridged(_: T::CollectionId, _: T::ItemId, _: u32, _: &T::AccountId) {}
+}
+
+/// Programmatic access to the bridge for trusted runtime code.
+///
+/// No origin checks are performed: the caller is responsible for having authorized `owner`.
+pub trait NftBridging<AccountId, CollectionId, ItemId> {
+	/// Send an NFT owned by `owner` to `dest_para_id`
+	fn send(
+		owner: AccountId,
+		collection_id: CollectionId,
+		item_id: ItemId,
+		dest_para_id: u32,
+		metadata: Vec<u8>,
+		metadata_uri: Option<Vec<u8>>,
+	) -> Result<TransferId, DispatchError>;
+
+	/// Cancel a pending transfer sent by `owner`, returning the NFT to them
+	fn cancel(owner: AccountId, collection_id: CollectionId, item_id: ItemId) -> DispatchResult;
+}
+
+/// Custody of NFTs for other pallets (rentals, lending, ...), sharing the bridge's locks.
+///
+/// An escrowed item stays with its owner but can't be moved, bridged or escrowed again until it
+/// is released. Each pallet escrows under its own `EscrowReason::External`; items the bridge
+/// holds report `EscrowReason::Bridge`. No origin checks are performed.
+pub trait NonfungibleEscrow<AccountId, CollectionId, ItemId> {
+	/// Take custody of `who`'s NFT under `reason`
+	fn escrow(
+		who: AccountId,
+		collection_id: CollectionId,
+		item_id: ItemId,
+		reason: EscrowReason,
+	) -> Result<EscrowId, DispatchError>;
+
+	/// End an escrow, handing its NFT to `to`
+	fn release(escrow_id: EscrowId, to: AccountId) -> DispatchResult;
+
+	/// Why an NFT is held in escrow, if it is
+	fn escrow_reason(collection_id: CollectionId, item_id: ItemId) -> Option<EscrowReason>;
+}
+
+/// Callback for an NFT backend burning an item, so the bridge can drop its records of it.
+///
+/// The runtime invokes it from the backend's burn path. Items the bridge holds are refused and
+/// should be kept from burning by `BridgeLockFilter` in the first place.
+pub trait OnNftBurned<CollectionId, ItemId> {
+	fn on_nft_burned(collection_id: CollectionId, item_id: ItemId) -> DispatchResult;
+}
+
+/// Runtime policy deciding whether an NFT may be bridged out (KYC tiers, geofencing, ...).
+///
+/// Runs after the bridge's own checks and before any state change. The returned error is passed
+/// on to the caller unchanged. The `()` implementation allows every transfer.
+pub trait EnsureNftTransfer<T: Config<I>, I: 'static = ()> {
+	fn check(
+		sender: &T::AccountId,
+		collection_id: &T::CollectionId,
+		item_id: &T::ItemId,
+		dest_para_id: u32,
+	) -> DispatchResult;
+}
+
+impl<T: Config<I>, I: 'static> EnsureNftTransfer<T, I> for () {
+	fn check(_: &T::AccountId, _: &T::CollectionId, _: &T::ItemId, _: u32) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// Chooses the local id of a foreign item wrapped on this chain.
+///
+/// `attempt` counts up from 0 while the previous candidates are taken by other items. `None`
+/// means there are no further candidates, and the receive then fails on the collision.
+pub trait DeriveItemId<T: Config<I>, I: 'static = ()> {
+	fn derive(
+		from_para_id: u32,
+		collection_id: &T::CollectionId,
+		remote_item_id: &T::ItemId,
+		attempt: u32,
+	) -> Option<T::ItemId>;
+}
+
+/// Keeps the id the item has on its source chain, without alternatives
+impl<T: Config<I>, I: 'static> DeriveItemId<T, I> for () {
+	fn derive(
+		_: u32,
+		_: &T::CollectionId,
+		remote_item_id: &T::ItemId,
+		attempt: u32,
+	) -> Option<T::ItemId> {
+		(attempt == 0).then_some(*remote_item_id)
+	}
+}
+
+/// Hashes the source chain, collection, item and attempt into the local `ItemId` space
+pub struct HashItemId;
+
+impl<T: Config<I>, I: 'static> DeriveItemId<T, I> for HashItemId {
+	fn derive(
+		from_para_id: u32,
+		collection_id: &T::CollectionId,
+		remote_item_id: &T::ItemId,
+		attempt: u32,
+	) -> Option<T::ItemId> {
+		let hash = T::Hashing::hash_of(&(from_para_id, collection_id, remote_item_id, attempt));
+		T::ItemId::decode(&mut TrailingZeroInput::new(hash.as_ref())).ok()
+	}
+}
+
+/// Checks that an inbound NFT really is locked on its source chain.
+///
+/// `proof` is a storage proof of the source's `PendingTransfers` entry, anchored in a relay
+/// chain block the implementation trusts. The `()` implementation accepts no proofs.
+pub trait VerifyLockProof<T: Config<I>, I: 'static = ()> {
+	fn verify(
+		from_para_id: u32,
+		collection_id: T::CollectionId,
+		item_id: T::ItemId,
+		proof: &[u8],
+	) -> bool;
+}
+
+impl<T: Config<I>, I: 'static> VerifyLockProof<T, I> for () {
+	fn verify(_: u32, _: T::CollectionId, _: T::ItemId, _: &[u8]) -> bool {
+		false
+	}
+}
+
+/// Assembles the XCM program for an outbound transfer.
+///
+/// The pallet does all bookkeeping and resolves the asset, beneficiary and fees; implementations
+/// only choose the instruction sequence (reserve vs teleport, where fees are paid, ...).
+pub trait BuildNftTransferProgram<T: Config<I>, I: 'static = ()> {
+	fn build(params: TransferParams<T, I>) -> Result<Xcm<()>, BuildError>;
+}
+
+/// A transport that delivers a message in the XCM version it was encoded in.
+///
+/// The bridge encodes each message for the version recorded in `DestinationXcmVersion` and hands
+/// it over as is, rather than converting it back to the latest version for `SendXcm`.
+pub trait SendVersionedXcm {
+	/// Everything needed to deliver a validated message
+	type Ticket;
+
+	/// Check that `message` can be sent to `dest`, returning the delivery fees
+	fn validate(dest: MultiLocation, message: VersionedXcm<()>) -> SendResult<Self::Ticket>;
+
+	/// Hand a validated message to the transport
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError>;
+}
+
+/// `SendVersionedXcm` for a `SendXcm` transport that negotiates versions itself. It takes
+/// messages in the latest version only; the rest are `DestinationUnsupported`.
+pub struct LatestXcmSender<S>(PhantomData<S>);
+
+impl<S: SendXcm> SendVersionedXcm for LatestXcmSender<S> {
+	type Ticket = S::Ticket;
+
+	fn validate(dest: MultiLocation, message: VersionedXcm<()>) -> SendResult<S::Ticket> {
+		let VersionedXcm::V3(message) = message else {
+			return Err(SendError::DestinationUnsupported)
+		};
+		S::validate(&mut Some(dest), &mut Some(message))
+	}
+
+	fn deliver(ticket: S::Ticket) -> Result<XcmHash, SendError> {
+		S::deliver(ticket)
+	}
+}
+
+/// NFT backend for runtimes where the pallet's own `NFTOwners` is the only record of ownership.
+/// It knows no items, so no ownership check consults it, and mints or moves nothing.
+pub struct NoNftBackend<CollectionId, ItemId>(PhantomData<(CollectionId, ItemId)>);
+
+impl<AccountId, CollectionId, ItemId> Inspect<AccountId> for NoNftBackend<CollectionId, ItemId> {
+	type CollectionId = CollectionId;
+	type ItemId = ItemId;
+
+	fn owner(_: &CollectionId, _: &ItemId) -> Option<AccountId> {
+		None
+	}
+}
+
+impl<AccountId, CollectionId, ItemId> Mutate<AccountId> for NoNftBackend<CollectionId, ItemId> {}
+
+impl<AccountId, CollectionId, ItemId> Transfer<AccountId> for NoNftBackend<CollectionId, ItemId> {
+	fn transfer(_: &CollectionId, _: &ItemId, _: &AccountId) -> DispatchResult {
+		Ok(())
+	}
+}
@@ -0,0 +1,858 @@
+//! Types stored or emitted by the NFT bridge pallet
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use frame_support::{traits::ConstU32, weights::Weight, BoundedVec};
+use sp_runtime::{DispatchError, Perbill, RuntimeDebug};
+use sp_std::vec::Vec;
+use xcm::v3::{MultiAsset, MultiAssets, MultiLocation, WeightLimit, XcmHash};
+
+use crate::Config;
+
+/// Identifier assigned to every outbound transfer
+pub type TransferId = u64;
+
+/// What a metadata URI points at
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum MediaKind {
+	/// The item's main metadata document, the single URI of earlier versions
+	Primary,
+	Image,
+	Animation,
+	/// A JSON metadata document
+	Document,
+	/// Any other media, by a tag agreed with partner chains
+	Other(u8),
+}
+
+/// An item's metadata URIs as passed in calls and bridge messages
+pub type ItemUris = Vec<(MediaKind, Vec<u8>)>;
+
+/// An item's chain-independent id, see `Pallet::canonical_id`
+pub type CanonicalId = BoundedVec<u8, ConstU32<128>>;
+
+/// The typed URI list of a legacy single `metadata_uri` and a list of further URIs. `None`, which
+/// keeps the URIs stored for the item, if both are empty.
+pub fn combine_uris(metadata_uri: Option<Vec<u8>>, metadata_uris: ItemUris) -> Option<ItemUris> {
+	let uris: ItemUris = metadata_uri
+		.map(|uri| (MediaKind::Primary, uri))
+		.into_iter()
+		.chain(metadata_uris)
+		.collect();
+	(!uris.is_empty()).then_some(uris)
+}
+
+/// Attributes written to the NFT backend for an item with `metadata_len` bytes of metadata and a
+/// `Primary` URI of `uri_len` bytes, as `WeightInfo` counts them
+pub fn attribute_count(metadata_len: u32, uri_len: u32) -> u32 {
+	u32::from(metadata_len > 0) + u32::from(uri_len > 0)
+}
+
+/// Where an outbound transfer's message stands
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TransferStatus<BlockNumber> {
+	/// The message was handed to the transport
+	InFlight,
+	/// Sending the message failed; it is retried from `on_idle` at `next_attempt`
+	SendFailed { next_attempt: BlockNumber },
+	/// The destination's in-flight cap was reached; the message waits in its `OutboundQueue`
+	Queued,
+	/// The destination reported refusing the NFT; the sender may cancel right away
+	Rejected { code: RejectionCode },
+}
+
+impl<BlockNumber> TransferStatus<BlockNumber> {
+	/// Whether the transfer's message can't reach the destination any more: it never left, or
+	/// the destination refused the NFT
+	pub fn is_recallable(&self) -> bool {
+		matches!(self, Self::Queued | Self::SendFailed { .. } | Self::Rejected { .. })
+	}
+
+	/// Whether the transfer's message reached the transport and counts towards `InFlightCount`
+	pub fn is_delivered(&self) -> bool {
+		matches!(self, Self::InFlight | Self::Rejected { .. })
+	}
+}
+
+/// The step at which sending a transfer's message failed
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SendStage {
+	/// The message never reached the transport: `validate` refused it or its fee couldn't be paid
+	Validate,
+	/// The transport accepted the message in `validate` but `deliver` failed
+	Deliver,
+}
+
+/// An outbound transfer waiting to be settled
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TransferInfo<AccountId, BlockNumber> {
+	/// Identifier of the transfer
+	pub id: TransferId,
+	/// The account that sent the NFT and gets it back if the transfer is cancelled
+	pub sender: AccountId,
+	/// Where the NFT was sent
+	pub dest: MultiLocation,
+	/// Block in which the NFT was sent
+	pub sent_at: BlockNumber,
+	/// Whether the message went out or is waiting to be retried
+	pub status: TransferStatus<BlockNumber>,
+	/// Number of automatic re-sends that have failed
+	pub retry_count: u32,
+	/// Last block the sender accepted the transfer to go out in. A message still waiting to be
+	/// sent after it is expired by the sweep.
+	pub valid_until: Option<BlockNumber>,
+}
+
+/// An inbound NFT whose beneficiary couldn't be resolved, or whose collection had no supply left
+/// for it, parked with its source chain's sovereign account until it is reassigned
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct OrphanedNft<AccountId> {
+	/// The parachain the NFT was sent from
+	pub from_para_id: u32,
+	/// The sovereign account of `from_para_id` holding the NFT
+	pub holder: AccountId,
+	/// The beneficiary as the message gave it
+	pub beneficiary: MultiLocation,
+}
+
+/// An inbound delivery that failed, kept for the source chain's operators to debug against
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct InboundFailure<CollectionId, ItemId, BlockNumber> {
+	/// Collection id as the source chain sent it
+	pub collection_id: CollectionId,
+	/// Item id as the source chain sent it
+	pub item_id: ItemId,
+	/// The error the delivery failed with
+	pub reason: DispatchError,
+	/// Block in which the delivery failed
+	pub at: BlockNumber,
+}
+
+/// Why an inbound NFT was refused and bounced back to its source chain
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RejectionReason {
+	/// Governance refused delivery of this specific item
+	AdminRejected,
+}
+
+/// Why a destination's `receive_nft` refused an NFT, as reported back to the chain that sent it.
+/// Mirrors the receive path's errors; anything else is `Other`.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RejectionCode {
+	/// The sending chain is neither trusted nor on an active lane
+	UntrustedSource,
+	/// The delivery's nonce was already used
+	InvalidNonce,
+	/// The NFT may only come back from the chain it was sent to
+	WrongReserve,
+	/// The collection isn't registered at the destination
+	CollectionNotRegistered,
+	/// The collection may not be bridged to the destination
+	CollectionNonTransferable,
+	/// The destination already has an item with that id
+	AlreadyExists,
+	/// The recipient doesn't accept bridged NFTs
+	RecipientOptedOut,
+	/// The metadata doesn't match the hash sent with it
+	MetadataHashMismatch,
+	/// The metadata or its URIs exceed the destination's limits
+	MetadataTooLong,
+	/// The delivery carried neither metadata nor a reference to it
+	MissingProvenance,
+	/// The lock proof was missing or didn't verify
+	InvalidLockProof,
+	/// The destination's NFT backend couldn't mint or deposit the item
+	MintFailed,
+	/// The delivery was older than the destination's `MaxInboundAge`
+	MessageTooOld,
+	/// Any other error
+	Other,
+}
+
+/// An outbound transfer as found through the hash of the message that carried it
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct TransferRecord<CollectionId, ItemId, AccountId, BlockNumber> {
+	/// Identifier of the transfer
+	pub id: TransferId,
+	/// Collection of the transferred NFT
+	pub collection_id: CollectionId,
+	/// The transferred NFT
+	pub item_id: ItemId,
+	/// Hash of the XCM message that carried the transfer
+	pub message_hash: XcmHash,
+	/// The pending transfer, or `None` once it has settled
+	pub pending: Option<TransferInfo<AccountId, BlockNumber>>,
+}
+
+/// Where an NFT stands with respect to the bridge, for wallets and frontends
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BridgeStatus<AccountId, BlockNumber> {
+	/// The outbound transfer the NFT is locked in, if any
+	pub pending: Option<TransferInfo<AccountId, BlockNumber>>,
+	/// Block in which the NFT was last locked outbound or minted inbound
+	pub bridged_at: Option<BlockNumber>,
+}
+
+/// One piece of metadata streamed after a transfer
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct MetadataChunk<Data, Hash> {
+	/// The sender's id of the transfer the metadata belongs to
+	pub transfer_id: TransferId,
+	/// Position of the chunk, from 0
+	pub index: u32,
+	/// Number of chunks in the whole blob
+	pub total: u32,
+	/// The chunk's bytes
+	pub data: Data,
+	/// Hash of `data`
+	pub chunk_hash: Hash,
+}
+
+/// Chunked metadata being reassembled on the receiving chain
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MetadataAssembly<CollectionId, ItemId, Hash, BlockNumber> {
+	/// Collection of the item, as the sending chain knows it
+	pub collection_id: CollectionId,
+	/// The item, as the sending chain knows it
+	pub item_id: ItemId,
+	/// Number of chunks announced
+	pub total: u32,
+	/// Number of distinct chunks received
+	pub received: u32,
+	/// Hash of the whole blob
+	pub metadata_hash: Hash,
+	/// Block from which the assembly may be purged
+	pub expires_at: BlockNumber,
+}
+
+/// Metadata uploaded ahead of a transfer, kept until the item is sent or the staging expires
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct StagedMetadataInfo<AccountId, Balance, BlockNumber, Hash> {
+	/// The account that staged the metadata and paid the deposit
+	pub depositor: AccountId,
+	/// Deposit held for the stored bytes
+	pub deposit: Balance,
+	/// Raw metadata bytes
+	pub metadata: Vec<u8>,
+	/// Metadata URI
+	pub metadata_uri: Option<Vec<u8>>,
+	/// Hash of `metadata`, which is all the transfer carries
+	pub metadata_hash: Hash,
+	/// Block in which the metadata was staged
+	pub staged_at: BlockNumber,
+}
+
+/// Whether an NFT was minted on this chain or wraps one from another chain
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum NftOrigin {
+	/// Native to this chain
+	Native,
+	/// Wrapper of an item whose reserve is `from_para_id`
+	Wrapped { from_para_id: u32 },
+}
+
+/// One storage entry of a bridge state export
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum BridgeStateEntry<CollectionId, ItemId, AccountId, BlockNumber> {
+	/// An `NFTOwners` entry
+	Owner { collection_id: CollectionId, item_id: ItemId, owner: AccountId },
+	/// A `PendingTransfers` entry
+	Pending {
+		collection_id: CollectionId,
+		item_id: ItemId,
+		transfer: TransferInfo<AccountId, BlockNumber>,
+	},
+	/// An `NFTMetadata` entry
+	Metadata { collection_id: CollectionId, item_id: ItemId, metadata: Vec<u8> },
+}
+
+/// Everything the bridge knows about an NFT, read in one go
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct NftRecord<AccountId, BlockNumber> {
+	/// The holder, or for an NFT locked in a transfer the sender who gets it back
+	pub owner: AccountId,
+	/// Raw metadata bytes
+	pub metadata: Option<Vec<u8>>,
+	/// Metadata URI
+	pub metadata_uri: Option<Vec<u8>>,
+	/// Where the NFT was minted
+	pub origin: NftOrigin,
+	/// State of the outbound transfer the NFT is locked in, if any
+	pub status: Option<TransferStatus<BlockNumber>>,
+	/// Block in which the NFT was last locked outbound or minted inbound
+	pub bridged_at: Option<BlockNumber>,
+}
+
+/// The storage entries proving where an NFT stands, for relayers to fetch state proofs of
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct OwnershipStatement<AccountId, BlockNumber> {
+	/// Storage key of the NFT's `NFTOwners` entry
+	pub owner_key: Vec<u8>,
+	/// Current owner, if the NFT is owned on this chain
+	pub owner: Option<AccountId>,
+	/// Storage key of the NFT's `PendingTransfers` entry
+	pub pending_key: Vec<u8>,
+	/// The pending transfer, if the NFT is locked in one
+	pub pending: Option<TransferInfo<AccountId, BlockNumber>>,
+}
+
+/// What a bridge transfer would cost, as reported by the dry-run
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct SendQuote<Balance> {
+	/// Bridging fee charged to the sender, including the per-byte metadata component
+	pub bridge_fee: Balance,
+	/// Fees the XCM transport charges to deliver the message
+	pub delivery_fees: MultiAssets,
+	/// The fee bought for execution on the destination chain
+	pub remote_execution_fee: MultiAsset,
+	/// Weight of the local `send_nft` extrinsic
+	pub weight: Weight,
+	/// Size of the encoded message in the destination's XCM version
+	pub message_size: u32,
+	/// Longest metadata the destination accepts from this chain
+	pub max_metadata_len: u32,
+}
+
+/// Separate components of the cost of a bridge transfer, as reported by the fee estimate
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FeeBreakdown<Balance> {
+	/// Weight of the local `send_nft` extrinsic
+	pub local_weight: Weight,
+	/// Bridging fee charged to the sender, including the per-byte metadata component
+	pub bridge_fee: Balance,
+	/// Fees the XCM transport charges to deliver the message
+	pub delivery_fees: MultiAssets,
+	/// Amount of the relay chain's native token bought for execution on the destination
+	pub remote_execution_fee: u128,
+	/// Longest metadata the destination accepts from this chain
+	pub max_metadata_len: u32,
+}
+
+/// Bridge health figures for operator dashboards, as reported by the runtime API.
+///
+/// Counts come from scans of at most `STATS_SCAN_LIMIT` entries per map; `complete` is false
+/// if any scan stopped there.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BridgeStats<BlockNumber> {
+	/// Outbound transfers not settled yet, including queued and failed ones
+	pub pending_transfers: u32,
+	/// Blocks since the oldest pending transfer was sent
+	pub oldest_pending_age: Option<BlockNumber>,
+	/// Destinations with the most unsettled sent transfers and their counts, busiest first
+	pub in_flight: Vec<(u32, u32)>,
+	/// Collections whose items may not be bridged out
+	pub restricted_collections: u32,
+	/// Transfers waiting in destination queues for an in-flight slot
+	pub queued_transfers: u32,
+	/// Transfers whose send failed and waits for a retry
+	pub failed_sends: u32,
+	/// Inbound NFTs parked in `OrphanedInbound`, not credited to their beneficiary
+	pub orphaned_inbound: u32,
+	/// Metadata size limits in force
+	pub metadata_limits: MetadataSizeLimits,
+	/// Capacity of each destination's outbound queue
+	pub max_outbound_queue: u32,
+	/// Largest encoded outbound message
+	pub max_outbound_message_size: u32,
+	/// Automatic re-sends of a failed transfer before it is given up
+	pub max_retries: u32,
+	/// Delivery fees paid per destination since the last reset
+	pub delivery_fee_spend: Vec<(u32, u128)>,
+	/// Whether every scan covered its whole map
+	pub complete: bool,
+}
+
+/// Weight limit requested for execution of a transfer on the destination
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ExecutionLimit {
+	/// The destination's configured limit, or the pallet default
+	Default,
+	/// An explicit limit
+	Limited(Weight),
+	/// No limit. Only accepted for trusted destinations.
+	Unlimited,
+}
+
+impl Default for ExecutionLimit {
+	fn default() -> Self {
+		ExecutionLimit::Default
+	}
+}
+
+/// How the bridging fee is determined for a collection
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum FeePolicy<Balance> {
+	/// No fee is charged
+	Waived,
+	/// A negotiated fee replaces the default
+	Fixed(Balance),
+	/// The pallet's `BridgeFee`
+	Default,
+}
+
+impl<Balance> Default for FeePolicy<Balance> {
+	fn default() -> Self {
+		FeePolicy::Default
+	}
+}
+
+/// What remains on this chain of a transfer the destination confirmed
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TransferTombstone<BlockNumber> {
+	/// Chain the NFT left for
+	pub dest_para_id: u32,
+	/// Block in which the destination confirmed delivery
+	pub confirmed_at: BlockNumber,
+}
+
+/// How an NFT is held while its outbound transfer is pending
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum LockStrategy {
+	/// The owner entry is removed until the transfer settles
+	Detach,
+	/// The owner keeps the NFT but it is flagged in `BridgeLocked` and cannot move
+	Freeze(CompletionMode),
+}
+
+/// What happens to a frozen NFT once the destination confirms the transfer
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CompletionMode {
+	/// The NFT is moved to the pallet's escrow account
+	Escrow,
+	/// The NFT is removed from this chain
+	Burn,
+}
+
+/// How a batch send treats items that fail their checks
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BatchMode {
+	/// Every item is sent or none is
+	Atomic,
+	/// Failing items are skipped and the rest are sent
+	BestEffort,
+	/// Every item travels in a single message, so they arrive together or not at all
+	Bundle,
+}
+
+/// One NFT in a batch send or receive
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BatchItem<CollectionId, ItemId> {
+	pub collection_id: CollectionId,
+	pub item_id: ItemId,
+	pub metadata: Vec<u8>,
+	pub metadata_uri: Option<Vec<u8>>,
+}
+
+/// Per-destination settings managed by governance
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+pub struct DestinationConfig {
+	/// Whether the destination is trusted to accept `Unlimited` execution
+	pub trusted: bool,
+	/// Weight limit used for this destination instead of the pallet default
+	pub weight_limit: Option<Weight>,
+	/// Maximum number of unconfirmed transfers to this destination. Further transfers are
+	/// queued until confirmations free a slot.
+	pub max_in_flight: Option<u32>,
+	/// Largest encoded message this destination accepts, if below `MaxOutboundMessageSize`
+	pub max_message_size: Option<u32>,
+	/// Longest metadata this destination accepts, if below the global `MetadataLimits`
+	pub max_metadata_len: Option<u32>,
+	/// How transfers to this destination are carried
+	pub protocol: TransferProtocol,
+	/// Index of the bridge pallet in the destination's runtime, for `Transact` payloads
+	pub remote_bridge_pallet_index: u8,
+	/// Index of the destination's receive call within its bridge pallet
+	pub remote_receive_call_index: u8,
+	/// Index of the call within the destination's bridge pallet confirming NFTs it sent here
+	pub remote_confirm_call_index: u8,
+}
+
+/// Metadata size limits set by governance, each at most the matching `Config` constant
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MetadataSizeLimits {
+	/// Longest metadata blob accepted, in bytes
+	pub max_metadata: u32,
+	/// Longest metadata URI accepted, in bytes
+	pub max_uri: u32,
+}
+
+/// Counts of bridge activity over some span of blocks
+#[derive(
+	Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+)]
+pub struct BridgeCounters {
+	/// NFTs sent to other chains
+	pub sent: u32,
+	/// NFTs received from other chains
+	pub received: u32,
+	/// Pending transfers cancelled by their senders
+	pub cancelled: u32,
+	/// Pending transfers unlocked after timing out
+	pub expired: u32,
+}
+
+/// How outbound transfers to a destination are carried
+#[derive(
+	Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+)]
+pub enum TransferProtocol {
+	/// XCM asset instructions, for destinations with an NFT-aware `TransactAsset` stack
+	#[default]
+	Asset,
+	/// A `Transact` of the destination's receive call with a SCALE-encoded `BridgeMessage`
+	Transact,
+}
+
+/// Features a lane to a partner chain carries. A lane's capabilities are the ones both sides
+/// opened it with.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct LaneCapabilities {
+	/// Sends may carry a call dispatched on the partner after delivery
+	pub remote_calls: bool,
+	/// The partner subscribes to metadata updates of the collections it holds wrappers of
+	pub metadata_updates: bool,
+}
+
+impl LaneCapabilities {
+	/// The capabilities both `self` and `other` have
+	pub fn intersect(self, other: Self) -> Self {
+		Self {
+			remote_calls: self.remote_calls && other.remote_calls,
+			metadata_updates: self.metadata_updates && other.metadata_updates,
+		}
+	}
+}
+
+/// Stage of a lane's handshake
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum LaneStatus {
+	/// Opened here, waiting for the partner's handshake
+	Pending,
+	/// Both sides opened it; transfers may use it
+	Active,
+}
+
+/// A bridge lane to a partner chain
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Lane {
+	pub status: LaneStatus,
+	/// This side's capabilities while `Pending`, the ones agreed with the partner once `Active`
+	pub capabilities: LaneCapabilities,
+}
+
+/// Everything `open_lane` configures for a partner chain
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct LaneConfig {
+	pub capabilities: LaneCapabilities,
+	/// Stored as the partner's `DestinationConfig`
+	pub destination: DestinationConfig,
+	/// `BridgeMessage` version the partner understands, `None` for the latest
+	pub protocol_version: Option<u32>,
+	/// XCM version the partner understands, `None` for the latest
+	pub xcm_version: Option<u32>,
+	/// Intermediate reserve chain the partner is reached through, `None` to send directly
+	pub reserve: Option<u32>,
+}
+
+/// Latest `BridgeMessage` format. Version 1 had no metadata URI, version 2 only the primary one,
+/// version 3 no send time.
+pub const BRIDGE_MESSAGE_VERSION: u32 = 4;
+
+/// A transfer as passed to the destination's receive call under `TransferProtocol::Transact`
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BridgeMessage<AccountId, CollectionId, ItemId> {
+	pub collection_id: CollectionId,
+	/// The item's id on the destination
+	pub item_id: ItemId,
+	pub recipient: AccountId,
+	pub metadata: Vec<u8>,
+	pub metadata_uris: ItemUris,
+	/// Unix time in seconds the source sent the message, for the destination's age limit
+	pub sent_at: u64,
+}
+
+/// `BridgeMessage` as understood by version 3 partners
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BridgeMessageV3<AccountId, CollectionId, ItemId> {
+	pub collection_id: CollectionId,
+	pub item_id: ItemId,
+	pub recipient: AccountId,
+	pub metadata: Vec<u8>,
+	pub metadata_uris: ItemUris,
+}
+
+/// `BridgeMessage` as understood by version 2 partners
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BridgeMessageV2<AccountId, CollectionId, ItemId> {
+	pub collection_id: CollectionId,
+	pub item_id: ItemId,
+	pub recipient: AccountId,
+	pub metadata: Vec<u8>,
+	pub metadata_uri: Option<Vec<u8>>,
+}
+
+/// `BridgeMessage` as understood by version 1 partners
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BridgeMessageV1<AccountId, CollectionId, ItemId> {
+	pub collection_id: CollectionId,
+	pub item_id: ItemId,
+	pub recipient: AccountId,
+	pub metadata: Vec<u8>,
+}
+
+impl<AccountId: Encode, CollectionId: Encode, ItemId: Encode>
+	BridgeMessage<AccountId, CollectionId, ItemId>
+{
+	/// Encode the message in a partner's `version`, dropping what it doesn't know. `None` if the
+	/// version is unknown or can't carry the item: a URI-only item would arrive without
+	/// metadata in version 1. Version 2 gets the primary URI only, version 3 no send time.
+	pub fn encode_for(self, version: u32) -> Option<Vec<u8>> {
+		match version.min(BRIDGE_MESSAGE_VERSION) {
+			0 => None,
+			1 if self.metadata.is_empty() && !self.metadata_uris.is_empty() => None,
+			1 => Some(
+				BridgeMessageV1 {
+					collection_id: self.collection_id,
+					item_id: self.item_id,
+					recipient: self.recipient,
+					metadata: self.metadata,
+				}
+				.encode(),
+			),
+			2 => Some(
+				BridgeMessageV2 {
+					collection_id: self.collection_id,
+					item_id: self.item_id,
+					recipient: self.recipient,
+					metadata: self.metadata,
+					metadata_uri: self
+						.metadata_uris
+						.into_iter()
+						.find(|(kind, _)| *kind == MediaKind::Primary)
+						.map(|(_, uri)| uri),
+				}
+				.encode(),
+			),
+			3 => Some(
+				BridgeMessageV3 {
+					collection_id: self.collection_id,
+					item_id: self.item_id,
+					recipient: self.recipient,
+					metadata: self.metadata,
+					metadata_uris: self.metadata_uris,
+				}
+				.encode(),
+			),
+			_ => Some(self.encode()),
+		}
+	}
+}
+
+/// Everything a program builder needs to assemble an outbound transfer
+pub struct TransferParams<T: Config<I>, I: 'static = ()> {
+	/// Collection of the NFT being sent, the first one for bundles
+	pub collection_id: <T as Config<I>>::CollectionId,
+	/// The NFT being sent, the first one for bundles
+	pub item_id: <T as Config<I>>::ItemId,
+	/// The XCM assets representing the NFTs
+	pub assets: MultiAssets,
+	/// Where the NFT is going
+	pub dest: MultiLocation,
+	/// Intermediate reserve chain the NFT is routed through, if any. The message is sent there
+	/// and forwarded to `dest`.
+	pub via: Option<MultiLocation>,
+	/// Who receives the NFT on the destination, relative to the destination
+	pub beneficiary: MultiLocation,
+	/// The fee to buy execution with on the destination
+	pub fees: MultiAsset,
+	/// The weight limit for execution on the destination
+	pub weight_limit: WeightLimit,
+	/// A call to dispatch on the destination after delivery
+	pub remote_call: Option<Vec<u8>>,
+}
+
+/// Why a program builder could not produce a message
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum BuildError {
+	/// The builder cannot express a transfer to this destination
+	UnsupportedDestination,
+	/// The builder does not support attached remote calls
+	RemoteCallUnsupported,
+}
+
+/// Identifier the proposing chain assigns to every swap it proposes
+pub type SwapId = u64;
+
+/// Identifier of an escrow taken through `NonfungibleEscrow`
+pub type EscrowId = u64;
+
+/// Why an NFT is held in escrow
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum EscrowReason {
+	/// Locked by the bridge for a transfer or swap
+	Bridge,
+	/// Held for another pallet, under an identifier of its choosing
+	External([u8; 8]),
+}
+
+/// An NFT held for another pallet through `NonfungibleEscrow`
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EscrowInfo<AccountId, CollectionId, ItemId> {
+	pub collection_id: CollectionId,
+	pub item_id: ItemId,
+	/// Owner when the escrow was taken, who keeps the item in `NFTOwners` until it is released
+	pub owner: AccountId,
+	pub reason: EscrowReason,
+}
+
+/// An offer to swap an NFT on the proposing chain for one on `their_para`.
+///
+/// Both chains keep a copy: the proposer's in `PendingSwaps`, the counterpart's in
+/// `IncomingSwaps`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SwapOffer<AccountId, CollectionId, ItemId, BlockNumber> {
+	/// Owner of the offered NFT on the proposing chain
+	pub proposer: AccountId,
+	/// Collection of the offered NFT
+	pub collection_id: CollectionId,
+	/// Item of the offered NFT
+	pub item_id: ItemId,
+	/// Chain holding the requested NFT
+	pub their_para: u32,
+	/// Collection of the requested NFT
+	pub their_collection_id: CollectionId,
+	/// Item of the requested NFT
+	pub their_item_id: ItemId,
+	/// Owner of the requested NFT on `their_para`
+	pub their_owner: AccountId,
+	/// Block of the proposing chain after which the offer can no longer be taken up
+	pub deadline: BlockNumber,
+	/// Where the swap stands on the chain holding this copy
+	pub state: SwapState<BlockNumber>,
+}
+
+/// Progress of a swap, with the local block of the last change
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SwapState<BlockNumber> {
+	/// Proposed or received, waiting for the counterpart's owner
+	Offered { at: BlockNumber },
+	/// The counterpart's owner accepted and escrowed their NFT; waiting for the proposing chain
+	/// to settle
+	Accepted { at: BlockNumber },
+}
+
+/// A collection being migrated to another chain, item by item from `on_idle`
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct CollectionExport<AccountId, BlockNumber> {
+	/// The collection admin who started the export and pays its delivery fees
+	pub admin: AccountId,
+	/// Chain the collection is moving to
+	pub dest_para_id: u32,
+	/// Maximum number of items sent per block
+	pub batch_size: u32,
+	/// Block in which the export started
+	pub started_at: BlockNumber,
+	/// Raw `NFTOwners` key of the last item examined, or `None` before the first batch
+	pub cursor: Option<Vec<u8>>,
+	/// Number of items sent so far
+	pub exported: u32,
+	/// Number of items that could not be sent, e.g. because they are pending or in a swap
+	pub skipped: u32,
+}
+
+/// How strictly a non-transferable collection is kept on this chain
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TransferRestriction {
+	/// Items can't leave this chain but may still arrive from other chains
+	Outbound,
+	/// Items can't leave and only items coming back from a pending transfer may arrive
+	Full,
+}
+
+/// Whether a registered collection's items originate on this chain
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CollectionMode {
+	/// Items are minted here; other chains hold wrappers of them
+	Native,
+	/// Items here are wrappers of items whose reserve is another chain
+	Wrapped,
+}
+
+/// A collection onboarded to the bridge
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CollectionInfo<AccountId> {
+	/// Chain holding the original items, or `None` if it is this one
+	pub reserve: Option<u32>,
+	/// Account managing the collection's bridge settings alongside `AdminOrigin`
+	pub admin: AccountId,
+	/// Whether the items here are originals or wrappers, following `reserve`
+	pub mode: CollectionMode,
+	/// Most wrappers of the collection this chain may hold at once, or `None` for no limit
+	pub max_supply: Option<u32>,
+}
+
+/// Who receives an item's royalties
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RoyaltyBeneficiary<AccountId> {
+	/// An account on this chain, paid directly
+	Local(AccountId),
+	/// A location elsewhere, whose royalties accumulate in `RoyaltyPots` until it claims them
+	Remote(MultiLocation),
+}
+
+/// Royalty owed on priced local transfers of an item
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RoyaltyInfo<AccountId> {
+	pub beneficiary: RoyaltyBeneficiary<AccountId>,
+	/// Share of the sale price owed, rounded down
+	pub rate: Perbill,
+}
+
+/// An ownership query sent with `query_remote_ownership`, awaiting its `report_ownership`
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct OwnershipQuery<AccountId, Balance, CollectionId, ItemId, BlockNumber> {
+	/// The chain asked
+	pub para_id: u32,
+	pub collection_id: CollectionId,
+	pub item_id: ItemId,
+	/// Last block in which an answer is accepted
+	pub expires_at: BlockNumber,
+	/// The signed account that asked and holds `deposit`; `None` for `AdminOrigin`
+	pub depositor: Option<AccountId>,
+	pub deposit: Balance,
+}
+
+/// An item's owner on another chain, as that chain last reported it
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RemoteOwner<AccountId, BlockNumber> {
+	/// `None` if the chain has no such item
+	pub owner: Option<AccountId>,
+	/// Block in which the report arrived
+	pub reported_at: BlockNumber,
+}
+
+/// A chain an item arrived from
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProvenanceHop {
+	pub from_para_id: u32,
+	/// Unix time in seconds the item arrived
+	pub received_at: u64,
+}
+
+/// The chains an item arrived from, oldest first, as kept by `ItemHistories`
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ProvenanceTrail<Hops> {
+	/// The first hop and the latest ones
+	pub hops: Hops,
+	/// Hops dropped from between them
+	pub elided: u32,
+}
+
+/// An item's provenance trail and the deposit held for it
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ItemHistory<AccountId, Balance, Hops> {
+	pub trail: ProvenanceTrail<Hops>,
+	/// The recipient that holds `deposit`; `None` if it couldn't pay
+	pub depositor: Option<AccountId>,
+	pub deposit: Balance,
+}
@@ -0,0 +1,687 @@
+//! Weights for the NFT bridge pallet
+//!
+//! These are hand-estimated. The block hooks, `send_nft` and `receive_nft` have benchmarks in
+//! `benchmarking.rs`, but their figures here are estimates too until those are run on reference
+//! hardware.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{constants::RocksDbWeight, Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for the NFT bridge pallet
+pub trait WeightInfo {
+	fn send_nft(m: u32, a: u32) -> Weight;
+	fn receive_nft(m: u32, a: u32) -> Weight;
+	fn force_receive() -> Weight;
+	fn reject_inbound() -> Weight;
+	fn unlock_bounced() -> Weight;
+	fn cancel_transfer() -> Weight;
+	fn set_destination_xcm_version() -> Weight;
+	fn set_destination_config() -> Weight;
+	fn report_stale() -> Weight;
+	fn retry_send() -> Weight;
+	fn set_inbound_opt_out() -> Weight;
+	fn set_collection_metadata_quota() -> Weight;
+	fn confirm_transfer() -> Weight;
+	fn expire_transfer() -> Weight;
+	fn dequeue_send() -> Weight;
+	fn set_route() -> Weight;
+	fn purge_orphaned_metadata(n: u32) -> Weight;
+	fn propose_swap() -> Weight;
+	fn accept_swap() -> Weight;
+	fn cancel_swap() -> Weight;
+	fn swap_offered() -> Weight;
+	fn swap_accepted() -> Weight;
+	fn swap_settled() -> Weight;
+	fn expire_swap() -> Weight;
+	fn set_collection_fee_policy() -> Weight;
+	fn set_collection_admin() -> Weight;
+	fn sync_metadata() -> Weight;
+	fn update_metadata() -> Weight;
+	fn remove_metadata_subscriber() -> Weight;
+	fn send_queued_metadata_sync() -> Weight;
+	fn update_item_metadata(n: u32) -> Weight;
+	fn export_collection() -> Weight;
+	fn export_item() -> Weight;
+	fn abort_export(n: u32) -> Weight;
+	fn set_collection_transferability() -> Weight;
+	fn register_collection() -> Weight;
+	fn deregister_collection() -> Weight;
+	fn reconcile_escrow(n: u32) -> Weight;
+	fn stage_metadata() -> Weight;
+	fn purge_staged_metadata() -> Weight;
+	fn request_metadata() -> Weight;
+	fn serve_metadata() -> Weight;
+	fn metadata_served() -> Weight;
+	fn metadata_chunk_header() -> Weight;
+	fn send_chunked_nft(n: u32) -> Weight;
+	fn metadata_chunk(n: u32) -> Weight;
+	fn purge_metadata_assembly(n: u32) -> Weight;
+	fn import_nft() -> Weight;
+	fn check_consistency(n: u32) -> Weight;
+	fn set_partner_version() -> Weight;
+	fn advertise_version() -> Weight;
+	fn kill_pending() -> Weight;
+	fn set_metadata_limits() -> Weight;
+	fn set_royalty() -> Weight;
+	fn transfer() -> Weight;
+	fn claim_royalties() -> Weight;
+	fn wrapper_burned() -> Weight;
+	fn on_initialize_expiries(n: u32) -> Weight;
+	fn on_idle_queue_drain(n: u32) -> Weight;
+	fn on_idle_retries(n: u32) -> Weight;
+	fn on_idle_aging_scan(n: u32) -> Weight;
+	fn query_remote_ownership() -> Weight;
+	fn ownership_query() -> Weight;
+	fn report_ownership() -> Weight;
+	fn purge_ownership_query() -> Weight;
+	fn set_collection_reserve() -> Weight;
+	fn clear_collection_data(n: u32) -> Weight;
+	fn reset_delivery_fee_spend() -> Weight;
+	fn set_bridge_paused() -> Weight;
+	fn reassign_orphaned() -> Weight;
+	fn open_lane() -> Weight;
+	fn confirm_lane() -> Weight;
+	fn close_lane() -> Weight;
+	fn clear_inbound_failures() -> Weight;
+	fn add_authority() -> Weight;
+	fn remove_authority() -> Weight;
+	fn set_collection_max_supply() -> Weight;
+	fn report_rejection() -> Weight;
+	fn emit_period_stats() -> Weight;
+	fn prune_transfer_records(n: u32) -> Weight;
+	fn compact_item_history() -> Weight;
+}
+
+/// Weights for the NFT bridge pallet using the runtime's database weights
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn send_nft(m: u32, a: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(100, 0).saturating_mul(m as u64))
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn receive_nft(m: u32, a: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(100, 0).saturating_mul(m as u64))
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(4, 4))
+			.saturating_add(T::DbWeight::get().writes(1).saturating_mul(a as u64))
+	}
+	fn force_receive() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 3))
+	}
+	fn reject_inbound() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 3))
+	}
+	fn unlock_bounced() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 4))
+	}
+	fn cancel_transfer() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 4))
+	}
+	fn set_destination_xcm_version() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_destination_config() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn report_stale() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 1))
+	}
+	fn retry_send() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 3))
+	}
+	fn set_inbound_opt_out() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_collection_metadata_quota() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn confirm_transfer() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 10))
+	}
+	fn expire_transfer() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 8))
+	}
+	fn dequeue_send() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 5))
+	}
+	fn set_route() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn purge_orphaned_metadata(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+			.saturating_add(T::DbWeight::get().reads_writes(7, 3).saturating_mul(n as u64))
+	}
+	fn propose_swap() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 4))
+	}
+	fn accept_swap() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 2))
+	}
+	fn cancel_swap() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 2))
+	}
+	fn swap_offered() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn swap_accepted() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(6, 9))
+	}
+	fn swap_settled() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(6, 9))
+	}
+	fn expire_swap() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 2))
+	}
+	fn set_collection_fee_policy() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_collection_admin() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn sync_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 1))
+	}
+	fn update_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 3))
+	}
+	fn remove_metadata_subscriber() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn send_queued_metadata_sync() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 1))
+	}
+	fn update_item_metadata(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(4, 3))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1).saturating_mul(n as u64))
+	}
+	fn export_collection() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn export_item() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(8, 7))
+	}
+	fn abort_export(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 4).saturating_mul(n as u64))
+	}
+	fn set_collection_transferability() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn register_collection() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 2))
+	}
+	fn deregister_collection() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 1))
+	}
+	fn reconcile_escrow(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			.saturating_add(T::DbWeight::get().reads(3).saturating_mul(n as u64))
+	}
+	fn stage_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	fn purge_staged_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 2))
+	}
+	fn request_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	fn serve_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads(4))
+	}
+	fn metadata_served() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 4))
+	}
+	fn metadata_chunk_header() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn send_chunked_nft(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(12, 10))
+			.saturating_add(T::DbWeight::get().writes(1).saturating_mul(n as u64))
+	}
+	fn metadata_chunk(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(3, 2))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1).saturating_mul(n as u64))
+	}
+	fn purge_metadata_assembly(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			.saturating_add(T::DbWeight::get().writes(1).saturating_mul(n as u64))
+	}
+	fn import_nft() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 2))
+	}
+	fn check_consistency(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(3).saturating_mul(n as u64))
+	}
+	fn set_partner_version() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn advertise_version() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn kill_pending() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(7, 10))
+	}
+	fn set_metadata_limits() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_royalty() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn transfer() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(8, 4))
+	}
+	fn claim_royalties() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	fn wrapper_burned() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	fn on_initialize_expiries(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 8).saturating_mul(n as u64))
+	}
+	fn on_idle_queue_drain(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads_writes(4, 5).saturating_mul(n as u64))
+	}
+	fn on_idle_retries(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads_writes(2, 3).saturating_mul(n as u64))
+	}
+	fn on_idle_aging_scan(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			.saturating_add(T::DbWeight::get().reads_writes(2, 1).saturating_mul(n as u64))
+	}
+	fn query_remote_ownership() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 4))
+	}
+	fn ownership_query() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads(3))
+	}
+	fn report_ownership() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 4))
+	}
+	fn purge_ownership_query() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 3))
+	}
+	fn set_collection_reserve() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 1))
+	}
+	fn clear_collection_data(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(4, 9))
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2).saturating_mul(n as u64))
+	}
+	fn reset_delivery_fee_spend() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_bridge_paused() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn reassign_orphaned() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	fn open_lane() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 6))
+	}
+	fn confirm_lane() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn close_lane() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 5))
+	}
+	fn clear_inbound_failures() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn add_authority() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn remove_authority() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn set_collection_max_supply() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn report_rejection() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn emit_period_stats() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 2))
+	}
+	fn prune_transfer_records(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			// The transfer's keys plus up to `MAX_MESSAGES_PER_TRANSFER` index entries
+			.saturating_add(T::DbWeight::get().reads_writes(11, 11).saturating_mul(n as u64))
+	}
+	fn compact_item_history() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 2))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn send_nft(m: u32, a: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(100, 0).saturating_mul(m as u64))
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(a as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn receive_nft(m: u32, a: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(100, 0).saturating_mul(m as u64))
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(a as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(4, 4))
+			.saturating_add(RocksDbWeight::get().writes(1).saturating_mul(a as u64))
+	}
+	fn force_receive() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 3))
+	}
+	fn reject_inbound() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 3))
+	}
+	fn unlock_bounced() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 4))
+	}
+	fn cancel_transfer() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 4))
+	}
+	fn set_destination_xcm_version() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn set_destination_config() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn report_stale() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(2, 1))
+	}
+	fn retry_send() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(2, 3))
+	}
+	fn set_inbound_opt_out() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn set_collection_metadata_quota() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn confirm_transfer() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 10))
+	}
+	fn expire_transfer() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 8))
+	}
+	fn dequeue_send() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(4, 5))
+	}
+	fn set_route() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn purge_orphaned_metadata(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(2, 1))
+			.saturating_add(RocksDbWeight::get().reads_writes(7, 3).saturating_mul(n as u64))
+	}
+	fn propose_swap() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 4))
+	}
+	fn accept_swap() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 2))
+	}
+	fn cancel_swap() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 2))
+	}
+	fn swap_offered() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn swap_accepted() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(6, 9))
+	}
+	fn swap_settled() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(6, 9))
+	}
+	fn expire_swap() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 2))
+	}
+	fn set_collection_fee_policy() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn set_collection_admin() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn sync_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(4, 1))
+	}
+	fn update_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 3))
+	}
+	fn remove_metadata_subscriber() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn send_queued_metadata_sync() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 1))
+	}
+	fn update_item_metadata(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(4, 3))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1).saturating_mul(n as u64))
+	}
+	fn export_collection() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn export_item() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(8, 7))
+	}
+	fn abort_export(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 4).saturating_mul(n as u64))
+	}
+	fn set_collection_transferability() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn register_collection() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 2))
+	}
+	fn deregister_collection() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(4, 1))
+	}
+	fn reconcile_escrow(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+			.saturating_add(RocksDbWeight::get().reads(3).saturating_mul(n as u64))
+	}
+	fn stage_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+	fn purge_staged_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 2))
+	}
+	fn request_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+	fn serve_metadata() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads(4))
+	}
+	fn metadata_served() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(4, 4))
+	}
+	fn metadata_chunk_header() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn send_chunked_nft(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(12, 10))
+			.saturating_add(RocksDbWeight::get().writes(1).saturating_mul(n as u64))
+	}
+	fn metadata_chunk(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(3, 2))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1).saturating_mul(n as u64))
+	}
+	fn purge_metadata_assembly(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+			.saturating_add(RocksDbWeight::get().writes(1).saturating_mul(n as u64))
+	}
+	fn import_nft() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(4, 2))
+	}
+	fn check_consistency(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(3).saturating_mul(n as u64))
+	}
+	fn set_partner_version() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn advertise_version() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn kill_pending() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(7, 10))
+	}
+	fn set_metadata_limits() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn set_royalty() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn transfer() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(8, 4))
+	}
+	fn claim_royalties() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+	fn wrapper_burned() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+	fn on_initialize_expiries(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 8).saturating_mul(n as u64))
+	}
+	fn on_idle_queue_drain(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads_writes(4, 5).saturating_mul(n as u64))
+	}
+	fn on_idle_retries(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads_writes(2, 3).saturating_mul(n as u64))
+	}
+	fn on_idle_aging_scan(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+			.saturating_add(RocksDbWeight::get().reads_writes(2, 1).saturating_mul(n as u64))
+	}
+	fn query_remote_ownership() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 4))
+	}
+	fn ownership_query() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads(3))
+	}
+	fn report_ownership() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 4))
+	}
+	fn purge_ownership_query() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 3))
+	}
+	fn set_collection_reserve() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(4, 1))
+	}
+	fn clear_collection_data(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(4, 9))
+			.saturating_add(RocksDbWeight::get().reads_writes(2, 2).saturating_mul(n as u64))
+	}
+	fn reset_delivery_fee_spend() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn set_bridge_paused() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn reassign_orphaned() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+	fn open_lane() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 6))
+	}
+	fn confirm_lane() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn close_lane() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 5))
+	}
+	fn clear_inbound_failures() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn add_authority() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn remove_authority() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn set_collection_max_supply() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn report_rejection() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn emit_period_stats() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 2))
+	}
+	fn prune_transfer_records(n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n as u64))
+			// The transfer's keys plus up to `MAX_MESSAGES_PER_TRANSFER` index entries
+			.saturating_add(RocksDbWeight::get().reads_writes(11, 11).saturating_mul(n as u64))
+	}
+	fn compact_item_history() -> Weight {
+		Weight::from_parts(10_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 2))
+	}
+}